@@ -4,11 +4,12 @@ const BLOCK_SIZE: usize = 512;
 
 fn main() {
     let (mut mixer, signal) = oddio::Mixer::new();
-    let mut signal = oddio::Adapt::new(
+    let (_, mut signal) = oddio::Adapt::new(
         signal,
         1e-3 / 2.0f32.sqrt(),
         oddio::AdaptOptions {
-            tau: 0.1,
+            attack_tau: 0.1,
+            release_tau: 0.1,
             max_gain: 1e6,
             low: 0.1 / 2.0f32.sqrt(),
             high: 0.5 / 2.0f32.sqrt(),
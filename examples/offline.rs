@@ -1,6 +1,5 @@
 const DURATION_SECS: u32 = 3;
 const RATE: u32 = 44100;
-const BLOCK_SIZE: usize = 512;
 const SPEED: f32 = 50.0;
 
 fn main() {
@@ -19,6 +18,7 @@ fn main() {
             position: [-SPEED, 10.0, 0.0].into(),
             velocity: [SPEED, 0.0, 0.0].into(),
             radius: 0.1,
+            ..Default::default()
         },
     );
 
@@ -30,16 +30,13 @@ fn main() {
     };
     let mut writer = hound::WavWriter::create("offline.wav", spec).unwrap();
 
-    for _ in 0..(RATE * DURATION_SECS / BLOCK_SIZE as u32) {
-        let mut block = [[0.0; 2]; BLOCK_SIZE];
-        oddio::run(&mut scene, RATE, &mut block);
-        for &frame in &block {
-            for &sample in &frame {
-                writer
-                    .write_sample((sample * i16::MAX as f32) as i16)
-                    .unwrap();
-            }
-        }
+    let samples = oddio::Offline::new(RATE).render_to_i16(
+        &mut scene,
+        DURATION_SECS as f32,
+        &mut oddio::dither::DitherState::new(),
+    );
+    for sample in samples {
+        writer.write_sample(sample).unwrap();
     }
 
     writer.finalize().unwrap();
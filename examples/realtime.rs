@@ -72,6 +72,7 @@ fn main() {
             position: [-SPEED, 10.0, 0.0].into(),
             velocity: [SPEED, 0.0, 0.0].into(),
             radius: 0.1,
+            ..Default::default()
         },
         1000.0,
         sample_rate.0,
@@ -1,4 +1,7 @@
-use crate::{math::Float, Frame, Signal};
+use alloc::sync::Arc;
+use core::sync::atomic::{AtomicU32, Ordering};
+
+use crate::{math::Float, Frame, Reset, Signal};
 
 /// Smoothly adjusts gain over time to keep average (RMS) signal level within a target range
 ///
@@ -14,6 +17,9 @@ use crate::{math::Float, Frame, Signal};
 pub struct Adapt<T: ?Sized> {
     options: AdaptOptions,
     avg_squared: f32,
+    /// Value of `avg_squared` at construction
+    initial_avg_squared: f32,
+    gain: Arc<AtomicU32>,
     inner: T,
 }
 
@@ -22,21 +28,53 @@ impl<T> Adapt<T> {
     ///
     /// Initialized as if an infinite signal with root mean squared level `initial_rms` had been
     /// processed.
-    pub fn new(signal: T, initial_rms: f32, options: AdaptOptions) -> Self {
-        Self {
+    pub fn new(signal: T, initial_rms: f32, options: AdaptOptions) -> (AdaptControl, Self) {
+        let gain = Arc::new(AtomicU32::new(1.0f32.to_bits()));
+        let control = AdaptControl(gain.clone());
+        let signal = Self {
             options,
             avg_squared: initial_rms * initial_rms,
+            initial_avg_squared: initial_rms * initial_rms,
+            gain,
             inner: signal,
-        }
+        };
+        (control, signal)
+    }
+}
+
+/// The average level returns to `initial_rms`.
+impl<T: Reset> Reset for Adapt<T>
+where
+    T::Frame: Frame,
+{
+    fn reset(&mut self) {
+        self.avg_squared = self.initial_avg_squared;
+        self.gain.store(1.0f32.to_bits(), Ordering::Relaxed);
+        self.inner.reset();
+    }
+}
+
+/// Thread-safe access to the state of an [`Adapt`] filter
+pub struct AdaptControl(Arc<AtomicU32>);
+
+impl AdaptControl {
+    /// Linear gain most recently applied to the signal
+    pub fn gain(&self) -> f32 {
+        f32::from_bits(self.0.load(Ordering::Relaxed))
     }
 }
 
 /// Configuration for an [`Adapt`] filter, passed to [`Adapt::new`]
 #[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AdaptOptions {
-    /// How smoothly the filter should respond. Smaller values reduce time spent outside the target
-    /// range, at the cost of lower perceived dynamic range. 0.1 is a good place to start.
-    pub tau: f32,
+    /// How smoothly the filter should respond to rising levels. Smaller values reduce time spent
+    /// above the target range, at the cost of lower perceived dynamic range. 0.1 is a good place
+    /// to start.
+    pub attack_tau: f32,
+    /// How smoothly the filter should respond to falling levels. Larger values than `attack_tau`
+    /// avoid audibly boosting brief pauses. Set equal to `attack_tau` for symmetric behavior.
+    pub release_tau: f32,
     /// Maximum linear gain to apply regardless of input signal
     pub max_gain: f32,
     /// When the average RMS level is below this, the gain will increase over time, up to at most
@@ -52,7 +90,8 @@ pub struct AdaptOptions {
 impl Default for AdaptOptions {
     fn default() -> Self {
         Self {
-            tau: 0.1,
+            attack_tau: 0.1,
+            release_tau: 0.1,
             max_gain: f32::INFINITY,
             low: 0.1 / 2.0f32.sqrt(),
             high: 0.5 / 2.0f32.sqrt(),
@@ -67,13 +106,22 @@ where
     type Frame = T::Frame;
 
     fn sample(&mut self, interval: f32, out: &mut [T::Frame]) {
-        let alpha = 1.0 - (-interval / self.options.tau).exp();
+        let attack = 1.0 - (-interval / self.options.attack_tau).exp();
+        let release = 1.0 - (-interval / self.options.release_tau).exp();
         self.inner.sample(interval, out);
+        let mut gain = f32::from_bits(self.gain.load(Ordering::Relaxed));
         for x in out {
             let sample = x.channels().iter().sum::<f32>();
-            self.avg_squared = sample * sample * alpha + self.avg_squared * (1.0 - alpha);
+            let squared = sample * sample;
+            // Rising levels call for falling gain, and vice versa
+            let alpha = if squared > self.avg_squared {
+                attack
+            } else {
+                release
+            };
+            self.avg_squared = squared * alpha + self.avg_squared * (1.0 - alpha);
             let avg_peak = self.avg_squared.sqrt() * 2.0f32.sqrt();
-            let gain = if avg_peak < self.options.low {
+            gain = if avg_peak < self.options.low {
                 (self.options.low / avg_peak).min(self.options.max_gain)
             } else if avg_peak > self.options.high {
                 self.options.high / avg_peak
@@ -84,11 +132,16 @@ where
                 *s *= gain;
             }
         }
+        self.gain.store(gain.to_bits(), Ordering::Relaxed);
     }
 
     fn is_finished(&self) -> bool {
         self.inner.is_finished()
     }
+
+    fn duration_remaining(&self) -> Option<f32> {
+        self.inner.duration_remaining()
+    }
 }
 
 #[cfg(test)]
@@ -101,11 +154,12 @@ mod tests {
         const LOW: f32 = 0.1;
         const HIGH: f32 = 1.0;
         const MAX_GAIN: f32 = 10.0;
-        let mut adapt = Adapt::new(
+        let (_, mut adapt) = Adapt::new(
             Constant::new(0.0),
             0.0,
             AdaptOptions {
-                tau: 0.5,
+                attack_tau: 0.5,
+                release_tau: 0.5,
                 low: LOW,
                 high: HIGH,
                 max_gain: MAX_GAIN,
@@ -145,4 +199,29 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn asymmetric() {
+        let options = AdaptOptions {
+            attack_tau: 0.01,
+            release_tau: 1.0,
+            low: 0.1,
+            high: 0.1,
+            max_gain: 100.0,
+        };
+        let (control, mut adapt) = Adapt::new(Constant::new(0.0), 0.01, options);
+        let mut out = [0.0; 10];
+        adapt.sample(0.01, &mut out);
+        assert!(control.gain() > 1.0);
+
+        // Rapidly respond to loudness
+        adapt.inner.0 = 1.0;
+        adapt.sample(0.01, &mut out);
+        assert!(control.gain() < 0.2);
+
+        // Slowly respond to quiet
+        adapt.inner.0 = 0.01;
+        adapt.sample(0.01, &mut out);
+        assert!(control.gain() < 0.2);
+    }
 }
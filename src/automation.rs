@@ -0,0 +1,237 @@
+use alloc::boxed::Box;
+
+use crate::{math::Float, Sample, Seek, Signal};
+
+/// A scalar value that changes over time according to a list of breakpoints
+///
+/// Useful for scripting fades and sweeps in advance, rather than writing to a control from game
+/// logic every frame. The value holds at the first breakpoint's value before its time, and at the
+/// last breakpoint's value after its time. Use [`Automate`] to apply the value to a control.
+///
+/// ```
+/// # use oddio::{Automate, Automation, Breakpoint, Curve, Sine, SignalExt};
+/// // Fade in over a second, then out over two
+/// let fade = Automation::new([
+///     Breakpoint { time: 0.0, value: 0.0, curve: Curve::Linear },
+///     Breakpoint { time: 1.0, value: 1.0, curve: Curve::Linear },
+///     Breakpoint { time: 3.0, value: 0.0, curve: Curve::Hold },
+/// ]);
+/// let (mut control, signal) = Sine::new(0.0, 440.0).gain();
+/// let signal = Automate::new(signal, fade, move |x| control.set_amplitude_ratio(x));
+/// ```
+pub struct Automation {
+    points: Box<[Breakpoint]>,
+    /// Seconds since the start
+    t: f32,
+    /// Index of the breakpoint beginning the segment containing `t`
+    segment: usize,
+}
+
+impl Automation {
+    /// Follow `points`, beginning at time 0
+    ///
+    /// # Panics
+    ///
+    /// Panics if `points` is empty or not sorted by time.
+    pub fn new(points: impl Into<Box<[Breakpoint]>>) -> Self {
+        let points = points.into();
+        assert!(
+            !points.is_empty(),
+            "automation needs at least one breakpoint"
+        );
+        assert!(
+            points.windows(2).all(|w| w[0].time <= w[1].time),
+            "breakpoints must be sorted by time"
+        );
+        Self {
+            points,
+            t: 0.0,
+            segment: 0,
+        }
+    }
+
+    /// Value at `self.t`, advancing `segment` as necessary
+    fn value(&mut self) -> Sample {
+        while self.segment + 1 < self.points.len() && self.points[self.segment + 1].time <= self.t {
+            self.segment += 1;
+        }
+        let a = &self.points[self.segment];
+        if self.t <= a.time || self.segment + 1 == self.points.len() {
+            return a.value;
+        }
+        let b = &self.points[self.segment + 1];
+        let fract = (self.t - a.time) / (b.time - a.time);
+        match a.curve {
+            Curve::Hold => a.value,
+            // Exponential interpolation is only defined between values of the same sign
+            Curve::Exponential if a.value * b.value > 0.0 => {
+                a.value * (b.value / a.value).powf(fract)
+            }
+            Curve::Linear | Curve::Exponential => a.value + (b.value - a.value) * fract,
+        }
+    }
+}
+
+impl Signal for Automation {
+    type Frame = Sample;
+
+    fn sample(&mut self, interval: f32, out: &mut [Sample]) {
+        for x in out {
+            *x = self.value();
+            self.t += interval;
+        }
+    }
+
+    /// Whether the last breakpoint has passed
+    ///
+    /// Unlike most signals, the output remains at the last breakpoint's value thereafter, rather
+    /// than becoming zero.
+    fn is_finished(&self) -> bool {
+        self.t >= self.points[self.points.len() - 1].time
+    }
+
+    fn duration_remaining(&self) -> Option<f32> {
+        Some((self.points[self.points.len() - 1].time - self.t).max(0.0))
+    }
+}
+
+impl Seek for Automation {
+    fn seek(&mut self, seconds: f32) {
+        self.t += seconds;
+        if seconds < 0.0 {
+            self.segment = 0;
+        }
+    }
+}
+
+/// A point along an [`Automation`]
+#[derive(Debug, Copy, Clone)]
+pub struct Breakpoint {
+    /// Seconds since the start of the automation
+    pub time: f32,
+    /// Value at `time`
+    pub value: Sample,
+    /// How the value moves from this breakpoint to the next
+    pub curve: Curve,
+}
+
+/// Shape of the transition between two [`Breakpoint`]s
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Curve {
+    /// Move at a constant rate
+    Linear,
+    /// Move by a constant ratio per second, which sounds even for gains and frequencies
+    ///
+    /// Falls back to linear when either value is zero or they differ in sign.
+    Exponential,
+    /// Keep the earlier value until the next breakpoint is reached
+    Hold,
+}
+
+/// Passes a signal through unchanged while feeding a parameter signal to a control
+///
+/// Before each block of frames is sampled from the inner signal, `f` is called with the current
+/// value of `parameter`, typically an [`Automation`] or other low-frequency signal. Because values
+/// are only delivered once per block, abrupt changes are best smoothed by the control itself, as
+/// [`GainControl`](crate::GainControl) does.
+pub struct Automate<T: ?Sized, A, F> {
+    parameter: A,
+    f: F,
+    inner: T,
+}
+
+impl<T, A, F> Automate<T, A, F> {
+    /// Call `f` with the value of `parameter` as `signal` plays
+    pub fn new(signal: T, parameter: A, f: F) -> Self {
+        Self {
+            parameter,
+            f,
+            inner: signal,
+        }
+    }
+}
+
+impl<T, A, F> Signal for Automate<T, A, F>
+where
+    T: Signal + ?Sized,
+    A: Signal<Frame = Sample>,
+    F: FnMut(Sample),
+{
+    type Frame = T::Frame;
+
+    fn sample(&mut self, interval: f32, out: &mut [T::Frame]) {
+        // Sample a single frame spanning the whole block, advancing the parameter accordingly
+        let mut value = 0.0;
+        self.parameter.sample(
+            interval * out.len() as f32,
+            core::slice::from_mut(&mut value),
+        );
+        (self.f)(value);
+        self.inner.sample(interval, out);
+    }
+
+    fn is_finished(&self) -> bool {
+        self.inner.is_finished()
+    }
+
+    fn duration_remaining(&self) -> Option<f32> {
+        self.inner.duration_remaining()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Constant, SignalExt};
+    use alloc::vec;
+
+    fn points(curve: Curve) -> Automation {
+        Automation::new(vec![
+            Breakpoint {
+                time: 1.0,
+                value: 1.0,
+                curve,
+            },
+            Breakpoint {
+                time: 3.0,
+                value: 4.0,
+                curve: Curve::Hold,
+            },
+        ])
+    }
+
+    #[test]
+    fn curves() {
+        let mut out = [0.0; 5];
+        let mut linear = points(Curve::Linear);
+        linear.sample(1.0, &mut out);
+        assert_eq!(out, [1.0, 1.0, 2.5, 4.0, 4.0]);
+        assert!(linear.is_finished());
+
+        let mut exponential = points(Curve::Exponential);
+        exponential.sample(1.0, &mut out);
+        assert_eq!(out, [1.0, 1.0, 2.0, 4.0, 4.0]);
+
+        let mut hold = points(Curve::Hold);
+        hold.sample(0.5, &mut out);
+        assert_eq!(out, [1.0, 1.0, 1.0, 1.0, 1.0]);
+        assert!(!hold.is_finished());
+        hold.seek(-2.0);
+        hold.sample(1.0, &mut out);
+        assert_eq!(out, [1.0, 1.0, 1.0, 4.0, 4.0]);
+    }
+
+    #[test]
+    fn automate() {
+        let (mut control, signal) = Constant(1.0).gain();
+        control.set_smoothing(0.0);
+        let mut signal = Automate::new(signal, points(Curve::Linear), move |x| {
+            control.set_amplitude_ratio(x)
+        });
+        let mut out = [0.0; 2];
+        signal.sample(1.0, &mut out);
+        assert_eq!(out, [1.0; 2]);
+        signal.sample(0.5, &mut out);
+        assert_eq!(out, [2.5; 2]);
+    }
+}
@@ -0,0 +1,156 @@
+use core::f32::consts::TAU;
+
+use crate::{math::Float, Frame};
+
+/// Coefficients of a second-order IIR filter, normalized such that `a0 = 1`
+#[derive(Debug, Copy, Clone)]
+pub(crate) struct Coefficients {
+    b0: f32,
+    b1: f32,
+    b2: f32,
+    a1: f32,
+    a2: f32,
+}
+
+impl Coefficients {
+    /// Identity filter, which passes its input through unchanged
+    pub(crate) const IDENTITY: Self = Self {
+        b0: 1.0,
+        b1: 0.0,
+        b2: 0.0,
+        a1: 0.0,
+        a2: 0.0,
+    };
+
+    /// Normalize unnormalized coefficients
+    fn new(b0: f32, b1: f32, b2: f32, a0: f32, a1: f32, a2: f32) -> Self {
+        Self {
+            b0: b0 / a0,
+            b1: b1 / a0,
+            b2: b2 / a0,
+            a1: a1 / a0,
+            a2: a2 / a0,
+        }
+    }
+
+    /// Low-pass filter attenuating frequencies above `cutoff`, given in cycles per sample
+    pub(crate) fn low_pass(cutoff: f32, q: f32) -> Self {
+        let (cos, alpha) = angular(cutoff, q);
+        let b1 = 1.0 - cos;
+        Self::new(b1 / 2.0, b1, b1 / 2.0, 1.0 + alpha, -2.0 * cos, 1.0 - alpha)
+    }
+
+    /// High-pass filter attenuating frequencies below `cutoff`, given in cycles per sample
+    pub(crate) fn high_pass(cutoff: f32, q: f32) -> Self {
+        let (cos, alpha) = angular(cutoff, q);
+        let b1 = 1.0 + cos;
+        Self::new(
+            b1 / 2.0,
+            -b1,
+            b1 / 2.0,
+            1.0 + alpha,
+            -2.0 * cos,
+            1.0 - alpha,
+        )
+    }
+
+    /// All-pass filter shifting the phase of frequencies around `center`, given in cycles per
+    /// sample, without affecting their amplitude
+    pub(crate) fn all_pass(center: f32, q: f32) -> Self {
+        let (cos, alpha) = angular(center, q);
+        Self::new(
+            1.0 - alpha,
+            -2.0 * cos,
+            1.0 + alpha,
+            1.0 + alpha,
+            -2.0 * cos,
+            1.0 - alpha,
+        )
+    }
+
+    /// Bell-shaped boost or cut of `gain` decibels around `center`, given in cycles per sample
+    pub(crate) fn peaking(center: f32, gain: f32, q: f32) -> Self {
+        let (cos, alpha) = angular(center, q);
+        let a = 10.0f32.powf(gain / 40.0);
+        Self::new(
+            1.0 + alpha * a,
+            -2.0 * cos,
+            1.0 - alpha * a,
+            1.0 + alpha / a,
+            -2.0 * cos,
+            1.0 - alpha / a,
+        )
+    }
+
+    /// Boost or cut of `gain` decibels below `corner`, given in cycles per sample
+    pub(crate) fn low_shelf(corner: f32, gain: f32, q: f32) -> Self {
+        let (cos, alpha) = angular(corner, q);
+        let a = 10.0f32.powf(gain / 40.0);
+        let k = 2.0 * a.sqrt() * alpha;
+        Self::new(
+            a * ((a + 1.0) - (a - 1.0) * cos + k),
+            2.0 * a * ((a - 1.0) - (a + 1.0) * cos),
+            a * ((a + 1.0) - (a - 1.0) * cos - k),
+            (a + 1.0) + (a - 1.0) * cos + k,
+            -2.0 * ((a - 1.0) + (a + 1.0) * cos),
+            (a + 1.0) + (a - 1.0) * cos - k,
+        )
+    }
+
+    /// Boost or cut of `gain` decibels above `corner`, given in cycles per sample
+    pub(crate) fn high_shelf(corner: f32, gain: f32, q: f32) -> Self {
+        let (cos, alpha) = angular(corner, q);
+        let a = 10.0f32.powf(gain / 40.0);
+        let k = 2.0 * a.sqrt() * alpha;
+        Self::new(
+            a * ((a + 1.0) + (a - 1.0) * cos + k),
+            -2.0 * a * ((a - 1.0) + (a + 1.0) * cos),
+            a * ((a + 1.0) + (a - 1.0) * cos - k),
+            (a + 1.0) - (a - 1.0) * cos + k,
+            2.0 * ((a - 1.0) - (a + 1.0) * cos),
+            (a + 1.0) - (a - 1.0) * cos - k,
+        )
+    }
+}
+
+/// Compute the cosine of the angular frequency and the bandwidth term shared by all filter shapes
+fn angular(frequency: f32, q: f32) -> (f32, f32) {
+    let w0 = TAU * frequency;
+    (w0.cos(), w0.sin() / (2.0 * q))
+}
+
+/// History of a second-order IIR filter
+#[derive(Debug, Copy, Clone)]
+pub(crate) struct State<T> {
+    x1: T,
+    x2: T,
+    y1: T,
+    y2: T,
+}
+
+impl<T: Frame + Copy> State<T> {
+    pub(crate) const ZERO: Self = Self {
+        x1: T::ZERO,
+        x2: T::ZERO,
+        y1: T::ZERO,
+        y2: T::ZERO,
+    };
+
+    /// Filter a single frame
+    #[inline]
+    pub(crate) fn process(&mut self, c: &Coefficients, x: &T) -> T {
+        let mut y = T::ZERO;
+        for (i, o) in y.channels_mut().iter_mut().enumerate() {
+            *o = c.b0 * x.channels()[i]
+                + c.b1 * self.x1.channels()[i]
+                + c.b2 * self.x2.channels()[i]
+                - c.a1 * self.y1.channels()[i]
+                - c.a2 * self.y2.channels()[i];
+        }
+        self.x2 = self.x1;
+        self.x1 = *x;
+        self.y2 = self.y1;
+        self.y1 = y;
+        y
+    }
+}
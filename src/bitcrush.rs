@@ -0,0 +1,161 @@
+use alloc::sync::Arc;
+use core::sync::atomic::{AtomicU32, AtomicU8, Ordering};
+
+use crate::{math::Float, Frame, Reset, Seek, Signal};
+
+/// Reduces the resolution of a signal, for retro or lo-fi effects
+///
+/// Each sample is quantized to a fixed number of bits, like an integer sample of that width, and the effective sample rate can
+/// additionally be reduced by holding each sample for several output samples. Below about 4 bits,
+/// the quantization noise tends to overwhelm the original signal.
+pub struct Bitcrush<T: Signal + ?Sized> {
+    shared: Arc<Shared>,
+    /// Most recently latched frame
+    held: T::Frame,
+    /// Progress towards latching the next frame, in units of output samples
+    phase: f32,
+    inner: T,
+}
+
+impl<T: Signal> Bitcrush<T>
+where
+    T::Frame: Frame,
+{
+    /// Quantize `signal` to `bits` bits, clamped between 1 and 24, without rate reduction
+    pub fn new(signal: T, bits: u8) -> (BitcrushControl, Self) {
+        let shared = Arc::new(Shared {
+            bits: AtomicU8::new(clamp_bits(bits)),
+            rate_reduction: AtomicU32::new(1.0f32.to_bits()),
+        });
+        let signal = Self {
+            shared: shared.clone(),
+            held: T::Frame::ZERO,
+            phase: 0.0,
+            inner: signal,
+        };
+        (BitcrushControl(shared), signal)
+    }
+}
+
+impl<T: Signal> Signal for Bitcrush<T>
+where
+    T::Frame: Frame,
+{
+    type Frame = T::Frame;
+
+    fn sample(&mut self, interval: f32, out: &mut [T::Frame]) {
+        self.inner.sample(interval, out);
+        let bits = self.shared.bits.load(Ordering::Relaxed);
+        // Like a two's complement integer, one bit represents the sign, so there are `levels` steps
+        // below zero and one fewer above
+        let levels = 2.0f32.powi(i32::from(bits) - 1);
+        let max = (levels - 1.0) / levels;
+        let factor = f32::from_bits(self.shared.rate_reduction.load(Ordering::Relaxed));
+        for x in out {
+            self.phase -= 1.0;
+            if self.phase < 0.0 {
+                self.phase += factor;
+                for (held, &s) in self.held.channels_mut().iter_mut().zip(x.channels()) {
+                    *held = ((s * levels).round() / levels).clamp(-1.0, max);
+                }
+            }
+            x.channels_mut().copy_from_slice(self.held.channels());
+        }
+        // Keep the phase bounded if the factor was reduced
+        self.phase = self.phase.min(factor);
+    }
+
+    fn is_finished(&self) -> bool {
+        self.inner.is_finished()
+    }
+
+    fn duration_remaining(&self) -> Option<f32> {
+        self.inner.duration_remaining()
+    }
+}
+
+impl<T: Seek> Seek for Bitcrush<T>
+where
+    T::Frame: Frame,
+{
+    fn seek(&mut self, seconds: f32) {
+        self.inner.seek(seconds);
+    }
+}
+
+impl<T: Reset> Reset for Bitcrush<T>
+where
+    T::Frame: Frame,
+{
+    fn reset(&mut self) {
+        self.held = T::Frame::ZERO;
+        self.phase = 0.0;
+        self.inner.reset();
+    }
+}
+
+/// Thread-safe control for a [`Bitcrush`]
+pub struct BitcrushControl(Arc<Shared>);
+
+impl BitcrushControl {
+    /// Get the number of bits samples are quantized to
+    pub fn bits(&self) -> u8 {
+        self.0.bits.load(Ordering::Relaxed)
+    }
+
+    /// Set the number of bits samples are quantized to, clamped between 1 and 24
+    pub fn set_bits(&mut self, bits: u8) {
+        self.0.bits.store(clamp_bits(bits), Ordering::Relaxed);
+    }
+
+    /// Get the factor by which the effective sample rate is reduced
+    pub fn rate_reduction(&self) -> f32 {
+        f32::from_bits(self.0.rate_reduction.load(Ordering::Relaxed))
+    }
+
+    /// Set the factor by which the effective sample rate is reduced, at least 1
+    ///
+    /// Each latched sample is held for `factor` output samples on average.
+    pub fn set_rate_reduction(&mut self, factor: f32) {
+        self.0
+            .rate_reduction
+            .store(factor.max(1.0).to_bits(), Ordering::Relaxed);
+    }
+}
+
+struct Shared {
+    bits: AtomicU8,
+    rate_reduction: AtomicU32,
+}
+
+/// Restrict `bits` to a range that can be represented precisely
+fn clamp_bits(bits: u8) -> u8 {
+    bits.clamp(1, 24)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Sine;
+
+    #[test]
+    fn quantize() {
+        let (mut c, mut s) = Bitcrush::new(Sine::new(0.0, 3.0), 3);
+        let mut out = [0.0; 100];
+        s.sample(0.01, &mut out);
+        for x in out {
+            assert!([-1.0, -0.75, -0.5, -0.25, 0.0, 0.25, 0.5, 0.75].contains(&x));
+        }
+        assert!(out.contains(&-1.0) && out.contains(&0.75));
+
+        c.set_bits(200);
+        assert_eq!(c.bits(), 24);
+        c.set_bits(3);
+
+        c.set_rate_reduction(4.0);
+        s.sample(0.01, &mut out);
+        for chunk in out.chunks(4) {
+            assert!(chunk.iter().all(|&x| x == chunk[0]));
+        }
+    }
+}
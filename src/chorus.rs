@@ -0,0 +1,265 @@
+use alloc::{boxed::Box, sync::Arc, vec};
+use core::{
+    f32::consts::TAU,
+    slice,
+    sync::atomic::{AtomicU32, Ordering},
+};
+
+use crate::{
+    math::Float,
+    tempo::{NoteValue, Tempo, TempoSync},
+    DelayLine, Sample, Signal,
+};
+
+/// Thickens a mono signal by mixing in several slowly modulated delayed copies
+///
+/// Each voice reads the signal from a delay line at a delay oscillating around `delay`, with its
+/// own LFO rate and phase. Voices are spread across the stereo field from left to right.
+///
+/// The wet signal lags the dry signal by `delay` seconds on average. Delays are measured in samples
+/// at the `rate` passed to [`new`](Self::new); sampling at a different rate scales them
+/// accordingly.
+pub struct Chorus<T: ?Sized> {
+    shared: Arc<Shared>,
+    rate: u32,
+    delay: f32,
+    /// LFO phase of each voice, in [0, 1)
+    phases: Box<[f32]>,
+    /// Tempo to which the LFOs are synced, if any
+    sync: Option<TempoSync>,
+    /// Seconds since `inner` finished
+    tail: f32,
+    line: DelayLine,
+    buffer: Box<[Sample]>,
+    inner: T,
+}
+
+impl<T> Chorus<T> {
+    /// Apply chorus to `signal`, tuned for sample rate `rate`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `options.voices` is zero.
+    pub fn new(signal: T, rate: u32, options: ChorusOptions) -> (ChorusControl, Self) {
+        assert!(options.voices > 0, "chorus must have at least one voice");
+        let shared = Arc::new(Shared {
+            depth: AtomicU32::new(options.depth.clamp(0.0, options.delay).to_bits()),
+            rate: AtomicU32::new(options.rate.to_bits()),
+            mix: AtomicU32::new(options.mix.clamp(0.0, 1.0).to_bits()),
+        });
+        let voices = options.voices;
+        let signal = Self {
+            shared: shared.clone(),
+            rate,
+            delay: options.delay,
+            phases: (0..voices).map(|i| i as f32 / voices as f32).collect(),
+            sync: None,
+            tail: 0.0,
+            line: DelayLine::new(
+                2.0 * options.delay + (MAX_CHUNK + 1) as f32 / rate as f32,
+                rate,
+            ),
+            buffer: vec![0.0; MAX_CHUNK].into(),
+            inner: signal,
+        };
+        (ChorusControl(shared), signal)
+    }
+
+    /// Modulate the voices once per `note` at `tempo`, rather than at a rate in Hz
+    ///
+    /// See [`Tempo`] for details.
+    pub fn with_tempo(mut self, tempo: Tempo, note: NoteValue) -> Self {
+        self.sync = Some(TempoSync { tempo, note });
+        self
+    }
+
+    fn max_delay(&self) -> f32 {
+        2.0 * self.delay
+    }
+}
+
+impl<T: Signal<Frame = Sample>> Signal for Chorus<T> {
+    type Frame = [Sample; 2];
+
+    fn sample(&mut self, interval: f32, out: &mut [[Sample; 2]]) {
+        // `depth` is clamped to `delay` on write
+        let depth = f32::from_bits(self.shared.depth.load(Ordering::Relaxed)).min(self.delay);
+        let lfo_rate = match self.sync {
+            Some(ref sync) => sync.hz(),
+            None => f32::from_bits(self.shared.rate.load(Ordering::Relaxed)),
+        };
+        let mix = f32::from_bits(self.shared.mix.load(Ordering::Relaxed));
+        let voices = self.phases.len();
+        // Normalize so each channel receives unit total weight from the voices
+        let norm = if voices == 1 {
+            1.0
+        } else {
+            2.0 / voices as f32
+        };
+        let sample_period = 1.0 / self.rate as f32;
+
+        for chunk in out.chunks_mut(self.buffer.len()) {
+            let dry = &mut self.buffer[..chunk.len()];
+            self.inner.sample(interval, dry);
+            self.line.push_block(dry);
+            for (o, &x) in chunk.iter_mut().zip(dry.iter()) {
+                *o = [x * (1.0 - mix); 2];
+            }
+
+            let len = chunk.len();
+            for (voice, phase) in self.phases.iter_mut().enumerate() {
+                let pan = if voices == 1 {
+                    0.5
+                } else {
+                    voice as f32 / (voices - 1) as f32
+                };
+                let gains = [(1.0 - pan) * norm * mix, pan * norm * mix];
+                let dphase = lfo_rate * (1.0 + VOICE_RATE_SPREAD * voice as f32) * interval;
+                for (i, o) in chunk.iter_mut().enumerate() {
+                    let delay = self.delay + depth * (TAU * *phase).sin();
+                    // Offset by the position of this sample relative to the end of the chunk
+                    let age = (len - 1 - i) as f32 * sample_period;
+                    let mut x = 0.0;
+                    self.line.tap(delay + age, slice::from_mut(&mut x));
+                    o[0] += x * gains[0];
+                    o[1] += x * gains[1];
+                    *phase = (*phase + dphase) % 1.0;
+                }
+            }
+        }
+
+        if self.inner.is_finished() {
+            self.tail += interval * out.len() as f32;
+        }
+    }
+
+    fn is_finished(&self) -> bool {
+        self.inner.is_finished() && self.tail > self.max_delay()
+    }
+
+    fn duration_remaining(&self) -> Option<f32> {
+        let tail = (self.max_delay() - self.tail).max(0.0);
+        Some(self.inner.duration_remaining()? + tail)
+    }
+}
+
+/// Configuration for a [`Chorus`], passed to [`Chorus::new`]
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ChorusOptions {
+    /// Number of delayed copies of the signal
+    pub voices: usize,
+    /// Average delay of each voice in seconds
+    pub delay: f32,
+    /// Amount by which each voice's delay oscillates in seconds, at most `delay`
+    pub depth: f32,
+    /// Frequency of the oscillation in Hz
+    pub rate: f32,
+    /// Proportion of delayed signal in the output, from 0 (dry) to 1 (wet)
+    pub mix: f32,
+}
+
+impl Default for ChorusOptions {
+    fn default() -> Self {
+        Self {
+            voices: 3,
+            delay: 0.02,
+            depth: 0.003,
+            rate: 0.8,
+            mix: 0.5,
+        }
+    }
+}
+
+/// Thread-safe control for a [`Chorus`]
+pub struct ChorusControl(Arc<Shared>);
+
+impl ChorusControl {
+    /// Get the amount by which each voice's delay oscillates in seconds
+    pub fn depth(&self) -> f32 {
+        f32::from_bits(self.0.depth.load(Ordering::Relaxed))
+    }
+
+    /// Set the amount by which each voice's delay oscillates in seconds
+    ///
+    /// Values larger than the [`ChorusOptions::delay`] the chorus was constructed with are
+    /// treated as equal to it.
+    pub fn set_depth(&mut self, seconds: f32) {
+        self.0
+            .depth
+            .store(seconds.max(0.0).to_bits(), Ordering::Relaxed);
+    }
+
+    /// Get the frequency of the oscillation in Hz
+    pub fn rate(&self) -> f32 {
+        f32::from_bits(self.0.rate.load(Ordering::Relaxed))
+    }
+
+    /// Set the frequency of the oscillation in Hz
+    pub fn set_rate(&mut self, hz: f32) {
+        self.0.rate.store(hz.to_bits(), Ordering::Relaxed);
+    }
+
+    /// Get the proportion of delayed signal in the output
+    pub fn mix(&self) -> f32 {
+        f32::from_bits(self.0.mix.load(Ordering::Relaxed))
+    }
+
+    /// Set the proportion of delayed signal in the output, from 0 (dry) to 1 (wet)
+    pub fn set_mix(&mut self, mix: f32) {
+        self.0
+            .mix
+            .store(mix.clamp(0.0, 1.0).to_bits(), Ordering::Relaxed);
+    }
+}
+
+struct Shared {
+    depth: AtomicU32,
+    rate: AtomicU32,
+    mix: AtomicU32,
+}
+
+/// Fractional increase in LFO rate of each successive voice, to keep them from moving in lockstep
+const VOICE_RATE_SPREAD: f32 = 0.13;
+
+/// Maximum number of samples written to the delay line at once
+const MAX_CHUNK: usize = 256;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_util::zero_crossings, Sine};
+
+    const RATE: u32 = 8000;
+
+    #[test]
+    fn modulated() {
+        let options = ChorusOptions {
+            voices: 1,
+            depth: 0.01,
+            mix: 1.0,
+            rate: 2.5,
+            ..ChorusOptions::default()
+        };
+        let (_, mut chorus) = Chorus::new(Sine::new(0.0, 440.0), RATE, options);
+        let mut out = [[0.0; 2]; RATE as usize];
+        chorus.sample(1.0 / RATE as f32, &mut out);
+        // The moving delay bends the pitch up and down
+        let counts = out
+            .chunks(RATE as usize / 10)
+            .skip(1)
+            .map(zero_crossings)
+            .collect::<alloc::vec::Vec<_>>();
+        let lo = *counts.iter().min().unwrap();
+        let hi = *counts.iter().max().unwrap();
+        assert!(hi - lo >= 6, "{:?}", counts);
+    }
+
+    #[test]
+    fn stereo() {
+        let (_, mut chorus) = Chorus::new(Sine::new(0.0, 440.0), RATE, ChorusOptions::default());
+        let mut out = [[0.0; 2]; 1024];
+        chorus.sample(1.0 / RATE as f32, &mut out);
+        assert!(out.iter().any(|x| (x[0] - x[1]).abs() > 0.1));
+    }
+}
@@ -0,0 +1,220 @@
+use alloc::sync::Arc;
+use core::sync::atomic::{AtomicU32, Ordering};
+
+use crate::{Frame, Reset, Seek, Signal};
+
+/// Distorts a signal by amplifying it, then clipping it to [-1, 1]
+///
+/// The harshest form of distortion. See [`SoftClip`] for a gentler alternative.
+pub struct HardClip<T: ?Sized> {
+    shared: Arc<Shared>,
+    inner: T,
+}
+
+impl<T> HardClip<T> {
+    /// Amplify `signal` by `drive`, then clip it
+    pub fn new(signal: T, drive: f32) -> (ClipControl, Self) {
+        let (control, shared) = Shared::new(drive);
+        (
+            control,
+            Self {
+                shared,
+                inner: signal,
+            },
+        )
+    }
+}
+
+impl<T: Signal> Signal for HardClip<T>
+where
+    T::Frame: Frame,
+{
+    type Frame = T::Frame;
+
+    fn sample(&mut self, interval: f32, out: &mut [T::Frame]) {
+        self.inner.sample(interval, out);
+        self.shared.apply(out, |x| x.clamp(-1.0, 1.0));
+    }
+
+    fn is_finished(&self) -> bool {
+        self.inner.is_finished()
+    }
+
+    fn duration_remaining(&self) -> Option<f32> {
+        self.inner.duration_remaining()
+    }
+}
+
+impl<T: Seek> Seek for HardClip<T>
+where
+    T::Frame: Frame,
+{
+    fn seek(&mut self, seconds: f32) {
+        self.inner.seek(seconds);
+    }
+}
+
+impl<T: Reset> Reset for HardClip<T>
+where
+    T::Frame: Frame,
+{
+    fn reset(&mut self) {
+        self.inner.reset();
+    }
+}
+
+/// Distorts a signal by amplifying it, then smoothly clipping it to [-1, 1]
+///
+/// Uses the cubic curve `1.5 * (x - x³ / 3)`, which reaches ±1 at ±1 with zero slope, so that
+/// loud sounds are rounded off rather than sharply truncated like by [`HardClip`].
+pub struct SoftClip<T: ?Sized> {
+    shared: Arc<Shared>,
+    inner: T,
+}
+
+impl<T> SoftClip<T> {
+    /// Amplify `signal` by `drive`, then smoothly clip it
+    pub fn new(signal: T, drive: f32) -> (ClipControl, Self) {
+        let (control, shared) = Shared::new(drive);
+        (
+            control,
+            Self {
+                shared,
+                inner: signal,
+            },
+        )
+    }
+}
+
+impl<T: Signal> Signal for SoftClip<T>
+where
+    T::Frame: Frame,
+{
+    type Frame = T::Frame;
+
+    fn sample(&mut self, interval: f32, out: &mut [T::Frame]) {
+        self.inner.sample(interval, out);
+        self.shared.apply(out, |x| {
+            let x = x.clamp(-1.0, 1.0);
+            1.5 * (x - x * x * x / 3.0)
+        });
+    }
+
+    fn is_finished(&self) -> bool {
+        self.inner.is_finished()
+    }
+
+    fn duration_remaining(&self) -> Option<f32> {
+        self.inner.duration_remaining()
+    }
+}
+
+impl<T: Seek> Seek for SoftClip<T>
+where
+    T::Frame: Frame,
+{
+    fn seek(&mut self, seconds: f32) {
+        self.inner.seek(seconds);
+    }
+}
+
+impl<T: Reset> Reset for SoftClip<T>
+where
+    T::Frame: Frame,
+{
+    fn reset(&mut self) {
+        self.inner.reset();
+    }
+}
+
+/// Thread-safe control for a [`HardClip`] or [`SoftClip`]
+pub struct ClipControl(Arc<Shared>);
+
+impl ClipControl {
+    /// Get the linear gain applied before clipping
+    pub fn drive(&self) -> f32 {
+        f32::from_bits(self.0.drive.load(Ordering::Relaxed))
+    }
+
+    /// Set the linear gain applied before clipping, up to 1000
+    pub fn set_drive(&mut self, drive: f32) {
+        self.0
+            .drive
+            .store(drive.clamp(0.0, MAX_DRIVE).to_bits(), Ordering::Relaxed);
+    }
+
+    /// Get the linear gain applied after clipping
+    pub fn makeup(&self) -> f32 {
+        f32::from_bits(self.0.makeup.load(Ordering::Relaxed))
+    }
+
+    /// Set the linear gain applied after clipping, initially 1
+    ///
+    /// Heavily distorted signals sound much louder than the original, so this is typically reduced
+    /// as the drive increases.
+    pub fn set_makeup(&mut self, gain: f32) {
+        self.0.makeup.store(gain.to_bits(), Ordering::Relaxed);
+    }
+}
+
+struct Shared {
+    drive: AtomicU32,
+    makeup: AtomicU32,
+}
+
+impl Shared {
+    fn new(drive: f32) -> (ClipControl, Arc<Self>) {
+        let shared = Arc::new(Self {
+            drive: AtomicU32::new(drive.clamp(0.0, MAX_DRIVE).to_bits()),
+            makeup: AtomicU32::new(1.0f32.to_bits()),
+        });
+        (ClipControl(shared.clone()), shared)
+    }
+
+    #[inline]
+    fn apply<T: Frame>(&self, out: &mut [T], curve: impl Fn(f32) -> f32) {
+        let drive = f32::from_bits(self.drive.load(Ordering::Relaxed));
+        let makeup = f32::from_bits(self.makeup.load(Ordering::Relaxed));
+        for x in out {
+            for s in x.channels_mut() {
+                *s = curve(*s * drive) * makeup;
+            }
+        }
+    }
+}
+
+/// Largest accepted drive, beyond which any further distortion is inaudible
+const MAX_DRIVE: f32 = 1000.0;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Constant;
+
+    #[test]
+    fn hard() {
+        let (mut c, mut s) = HardClip::new(Constant([0.25, -0.5]), 1.0);
+        let mut out = [[0.0; 2]];
+        s.sample(1.0, &mut out);
+        assert_eq!(out, [[0.25, -0.5]]);
+        c.set_drive(4.0);
+        s.sample(1.0, &mut out);
+        assert_eq!(out, [[1.0, -1.0]]);
+        c.set_makeup(0.5);
+        s.sample(1.0, &mut out);
+        assert_eq!(out, [[0.5, -0.5]]);
+    }
+
+    #[test]
+    fn soft() {
+        let (mut c, mut s) = SoftClip::new(Constant(0.5), 1.0);
+        let mut out = [0.0];
+        s.sample(1.0, &mut out);
+        assert!(out[0] > 0.5 && out[0] < 1.0);
+        c.set_drive(10.0);
+        s.sample(1.0, &mut out);
+        assert!((out[0] - 1.0).abs() < 1e-6);
+        c.set_drive(1e9);
+        assert_eq!(c.drive(), MAX_DRIVE);
+    }
+}
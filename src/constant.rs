@@ -1,6 +1,14 @@
-use crate::{Seek, Signal};
+use alloc::sync::Arc;
+use core::{
+    marker::PhantomData,
+    sync::atomic::{AtomicU32, Ordering},
+};
+
+use crate::{Frame, Reset, Seek, Signal};
 
 /// A constant signal, useful for testing
+///
+/// See [`DynamicConstant`] for a constant that can be changed from another thread.
 pub struct Constant<T>(pub T);
 
 impl<T> Constant<T> {
@@ -21,3 +29,95 @@ impl<T: Clone> Signal for Constant<T> {
 impl<T: Clone> Seek for Constant<T> {
     fn seek(&mut self, _: f32) {}
 }
+
+impl<T: Clone> Reset for Constant<T> {
+    fn reset(&mut self) {}
+}
+
+/// A signal that always emits the value most recently set by its [`DynamicConstantControl`]
+///
+/// Useful as a source of parameters for other signals, or as a DC offset.
+pub struct DynamicConstant<T> {
+    channels: Arc<[AtomicU32]>,
+    _frame: PhantomData<T>,
+}
+
+impl<T: Frame> DynamicConstant<T> {
+    /// Construct a signal that initially emits `frame`
+    pub fn new(frame: T) -> (DynamicConstantControl<T>, Self) {
+        let channels = frame
+            .channels()
+            .iter()
+            .map(|x| AtomicU32::new(x.to_bits()))
+            .collect::<Arc<[_]>>();
+        let control = DynamicConstantControl {
+            channels: channels.clone(),
+            _frame: PhantomData,
+        };
+        let signal = Self {
+            channels,
+            _frame: PhantomData,
+        };
+        (control, signal)
+    }
+}
+
+impl<T: Frame + Copy> Signal for DynamicConstant<T> {
+    type Frame = T;
+
+    fn sample(&mut self, _interval: f32, out: &mut [T]) {
+        out.fill(load(&self.channels));
+    }
+}
+
+impl<T: Frame + Copy> Seek for DynamicConstant<T> {
+    fn seek(&mut self, _: f32) {}
+}
+
+/// Thread-safe control for a [`DynamicConstant`]
+pub struct DynamicConstantControl<T> {
+    channels: Arc<[AtomicU32]>,
+    _frame: PhantomData<T>,
+}
+
+impl<T: Frame> DynamicConstantControl<T> {
+    /// Get the current value
+    pub fn get(&self) -> T {
+        load(&self.channels)
+    }
+
+    /// Change the value emitted by the signal
+    ///
+    /// Each channel is updated independently, so a multichannel signal may briefly emit a mixture
+    /// of old and new values.
+    pub fn set(&mut self, frame: T) {
+        for (x, c) in frame.channels().iter().zip(&*self.channels) {
+            c.store(x.to_bits(), Ordering::Relaxed);
+        }
+    }
+}
+
+fn load<T: Frame>(channels: &[AtomicU32]) -> T {
+    let mut frame = T::ZERO;
+    for (x, c) in frame.channels_mut().iter_mut().zip(channels) {
+        *x = f32::from_bits(c.load(Ordering::Relaxed));
+    }
+    frame
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dynamic() {
+        let (mut c, mut s) = DynamicConstant::new([1.0, 2.0]);
+        let mut buf = [[0.0; 2]; 2];
+        s.sample(1.0, &mut buf);
+        assert_eq!(buf, [[1.0, 2.0]; 2]);
+        c.set([3.0, 4.0]);
+        assert_eq!(c.get(), [3.0, 4.0]);
+        s.sample(1.0, &mut buf);
+        assert_eq!(buf, [[3.0, 4.0]; 2]);
+    }
+}
@@ -0,0 +1,174 @@
+use alloc::{boxed::Box, sync::Arc};
+use core::{
+    f32::consts::FRAC_PI_2,
+    sync::atomic::{AtomicU32, Ordering},
+};
+
+use crate::{frame, math::Float, Frame, Reset, Seek, Signal, Smoothed};
+
+/// Continuously blends two signals according to a dynamically-adjustable position
+///
+/// Unlike [`Fader`](crate::Fader), which replaces one signal with another, both signals play
+/// indefinitely, and the blend can be moved back and forth at will, e.g. between the layers of an
+/// adaptive music track. Uses constant-power blending, suitable for uncorrelated signals.
+pub struct Crossfade<A: Signal + ?Sized, B> {
+    shared: Arc<Shared>,
+    position: Smoothed<f32>,
+    /// Staging buffer for `b`
+    buffer: Box<[A::Frame]>,
+    b: B,
+    a: A,
+}
+
+impl<A: Signal, B: Signal<Frame = A::Frame>> Crossfade<A, B>
+where
+    A::Frame: Frame,
+{
+    /// Blend `a` and `b`, initially playing only `a`
+    pub fn new(a: A, b: B) -> (CrossfadeControl, Self) {
+        let signal = Self {
+            shared: Arc::new(Shared {
+                position: AtomicU32::new(0.0f32.to_bits()),
+                smoothing: AtomicU32::new(DEFAULT_SMOOTHING_PERIOD.to_bits()),
+            }),
+            position: Smoothed::new(0.0),
+            buffer: (0..BUFFER_SIZE).map(|_| A::Frame::ZERO).collect(),
+            b,
+            a,
+        };
+        let control = CrossfadeControl(signal.shared.clone());
+        (control, signal)
+    }
+}
+
+impl<A: Signal + ?Sized, B: Signal<Frame = A::Frame>> Signal for Crossfade<A, B>
+where
+    A::Frame: Frame,
+{
+    type Frame = A::Frame;
+
+    #[allow(clippy::float_cmp)]
+    fn sample(&mut self, interval: f32, mut out: &mut [A::Frame]) {
+        let target = f32::from_bits(self.shared.position.load(Ordering::Relaxed));
+        let smoothing = f32::from_bits(self.shared.smoothing.load(Ordering::Relaxed));
+        if self.position.target() != &target {
+            self.position.set(target);
+            if smoothing <= 0.0 {
+                self.position.advance(1.0);
+            }
+        }
+        while !out.is_empty() {
+            let n = self.buffer.len().min(out.len());
+            self.a.sample(interval, &mut out[..n]);
+            self.b.sample(interval, &mut self.buffer[..n]);
+            for (o, x) in out.iter_mut().zip(&*self.buffer) {
+                let angle = self.position.get() * FRAC_PI_2;
+                *o = frame::mix(&frame::scale(o, angle.cos()), &frame::scale(x, angle.sin()));
+                if smoothing > 0.0 {
+                    self.position.advance(interval / smoothing);
+                }
+            }
+            out = &mut out[n..];
+        }
+    }
+
+    fn is_finished(&self) -> bool {
+        self.a.is_finished() && self.b.is_finished()
+    }
+
+    fn duration_remaining(&self) -> Option<f32> {
+        Some(
+            self.a
+                .duration_remaining()?
+                .max(self.b.duration_remaining()?),
+        )
+    }
+}
+
+impl<A: Seek + ?Sized, B: Seek<Frame = A::Frame>> Seek for Crossfade<A, B>
+where
+    A::Frame: Frame,
+{
+    fn seek(&mut self, seconds: f32) {
+        self.a.seek(seconds);
+        self.b.seek(seconds);
+    }
+}
+
+/// Any change in progress completes immediately.
+impl<A: Reset + ?Sized, B: Reset<Frame = A::Frame>> Reset for Crossfade<A, B>
+where
+    A::Frame: Frame,
+{
+    fn reset(&mut self) {
+        let target = f32::from_bits(self.shared.position.load(Ordering::Relaxed));
+        self.position = Smoothed::new(target);
+        self.a.reset();
+        self.b.reset();
+    }
+}
+
+/// Thread-safe control for a [`Crossfade`]
+pub struct CrossfadeControl(Arc<Shared>);
+
+impl CrossfadeControl {
+    /// Get the position of the blend, from 0 for only the first signal to 1 for only the second
+    pub fn position(&self) -> f32 {
+        f32::from_bits(self.0.position.load(Ordering::Relaxed))
+    }
+
+    /// Move the blend to `position`, from 0 for only the first signal to 1 for only the second
+    pub fn set_position(&mut self, position: f32) {
+        self.0
+            .position
+            .store(position.clamp(0.0, 1.0).to_bits(), Ordering::Relaxed);
+    }
+
+    /// Get the number of seconds over which changes in position are smoothed
+    pub fn smoothing(&self) -> f32 {
+        f32::from_bits(self.0.smoothing.load(Ordering::Relaxed))
+    }
+
+    /// Set the number of seconds over which changes in position are smoothed
+    ///
+    /// Defaults to 0.1. Longer periods produce more gradual transitions, e.g. several seconds
+    /// between musical layers. Zero disables smoothing entirely.
+    pub fn set_smoothing(&mut self, seconds: f32) {
+        self.0.smoothing.store(seconds.to_bits(), Ordering::Relaxed);
+    }
+}
+
+struct Shared {
+    position: AtomicU32,
+    /// Seconds over which to smooth a change in position
+    smoothing: AtomicU32,
+}
+
+/// Default number of seconds over which to smooth a change in position
+const DEFAULT_SMOOTHING_PERIOD: f32 = 0.1;
+
+/// Number of frames of the second signal to sample at a time
+const BUFFER_SIZE: usize = 256;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Constant;
+
+    #[test]
+    fn blend() {
+        let (mut control, mut signal) = Crossfade::new(Constant(1.0), Constant(-1.0));
+        let mut out = [0.0; 4];
+        signal.sample(0.25, &mut out);
+        assert_eq!(out, [1.0; 4]);
+
+        control.set_smoothing(1.0);
+        control.set_position(1.0);
+        signal.sample(0.25, &mut out);
+        assert_eq!(out[0], 1.0);
+        // Constant power: halfway through, both signals are at 1/√2
+        assert!(out[2].abs() < 1e-6);
+        signal.sample(0.25, &mut out);
+        assert_eq!(out, [-1.0; 4]);
+    }
+}
@@ -0,0 +1,192 @@
+use alloc::boxed::Box;
+use core::f32::consts::FRAC_1_SQRT_2;
+
+use crate::{biquad, Reset, Sample, Signal};
+
+/// Splits a mono signal into frequency bands, for multiband processing
+///
+/// Each channel of the output is one band, lowest first. Bands are separated by 4th-order
+/// Linkwitz-Riley filters, and lower bands are phase-compensated for each higher split, so the sum
+/// of all bands has the same magnitude response as the input at every frequency.
+///
+/// The bands aren't separate signals, so effects that take a whole [`Signal`] can't be applied to
+/// just one of them. Instead, process the bands of each frame with
+/// [`SignalExt::map`](crate::SignalExt::map), whose closure may keep per-band state such as
+/// envelope levels, then sum them back together with [`Downmix`](crate::Downmix):
+///
+/// ```
+/// # use oddio::{Crossover, Downmix, SignalExt, Sine};
+/// // Attenuate everything above 1kHz by half
+/// let bands = Crossover::new(Sine::new(0.0, 440.0), 1000.0).map(|x| x[1] *= 0.5);
+/// let signal = Downmix::new(bands);
+/// ```
+pub struct Crossover<T: ?Sized, const B: usize> {
+    /// One split for each boundary between adjacent bands, in ascending order of frequency
+    splits: Box<[Split]>,
+    /// Interval for which coefficients were computed
+    interval: f32,
+    /// Staging buffer for the input
+    buffer: Box<[Sample]>,
+    inner: T,
+}
+
+impl<T> Crossover<T, 2> {
+    /// Split `signal` into frequencies below and above `frequency` Hz
+    pub fn new(signal: T, frequency: f32) -> Self {
+        Self::with_frequencies(signal, &[frequency])
+    }
+}
+
+impl<T> Crossover<T, 3> {
+    /// Split `signal` into frequencies below `low` Hz, between `low` and `high` Hz, and above `high`
+    /// Hz
+    pub fn new_three_band(signal: T, low: f32, high: f32) -> Self {
+        Self::with_frequencies(signal, &[low, high])
+    }
+}
+
+impl<T, const B: usize> Crossover<T, B> {
+    fn with_frequencies(signal: T, frequencies: &[f32]) -> Self {
+        debug_assert_eq!(frequencies.len() + 1, B);
+        Self {
+            splits: frequencies
+                .iter()
+                .enumerate()
+                .map(|(i, &frequency)| Split::new(frequency, i))
+                .collect(),
+            // Forces coefficients to be computed on first use
+            interval: f32::NAN,
+            buffer: alloc::vec![0.0; BUFFER_SIZE].into(),
+            inner: signal,
+        }
+    }
+}
+
+impl<T: Signal<Frame = Sample> + ?Sized, const B: usize> Signal for Crossover<T, B> {
+    type Frame = [Sample; B];
+
+    #[allow(clippy::float_cmp)]
+    fn sample(&mut self, interval: f32, out: &mut [[Sample; B]]) {
+        if interval != self.interval {
+            self.interval = interval;
+            for split in self.splits.iter_mut() {
+                split.update(interval);
+            }
+        }
+        for chunk in out.chunks_mut(self.buffer.len()) {
+            let buffer = &mut self.buffer[..chunk.len()];
+            self.inner.sample(interval, buffer);
+            for (o, &x) in chunk.iter_mut().zip(&*buffer) {
+                let mut rest = x;
+                for (i, split) in self.splits.iter_mut().enumerate() {
+                    // Keep lower bands in phase with the two halves of this split
+                    for (band, state) in o[..i].iter_mut().zip(&mut *split.all_pass) {
+                        *band = state.process(&split.coefficients[2], band);
+                    }
+                    o[i] = split.low.process(&split.coefficients[0], rest);
+                    rest = split.high.process(&split.coefficients[1], rest);
+                }
+                o[B - 1] = rest;
+            }
+        }
+    }
+
+    fn is_finished(&self) -> bool {
+        self.inner.is_finished()
+    }
+
+    fn duration_remaining(&self) -> Option<f32> {
+        self.inner.duration_remaining()
+    }
+}
+
+/// Clears the filters' memory
+impl<T: Reset<Frame = Sample> + ?Sized, const B: usize> Reset for Crossover<T, B> {
+    fn reset(&mut self) {
+        for split in self.splits.iter_mut() {
+            split.low = Lr4::default();
+            split.high = Lr4::default();
+            for state in split.all_pass.iter_mut() {
+                *state = biquad::State::ZERO;
+            }
+        }
+        self.inner.reset();
+    }
+}
+
+/// Boundary between two bands
+struct Split {
+    /// In Hz
+    frequency: f32,
+    /// Low-pass, high-pass, and all-pass coefficients
+    coefficients: [biquad::Coefficients; 3],
+    low: Lr4,
+    high: Lr4,
+    /// Phase compensation for each band below this split's low band
+    all_pass: Box<[biquad::State<Sample>]>,
+}
+
+impl Split {
+    fn new(frequency: f32, lower_bands: usize) -> Self {
+        Self {
+            frequency,
+            coefficients: [biquad::Coefficients::IDENTITY; 3],
+            low: Lr4::default(),
+            high: Lr4::default(),
+            all_pass: (0..lower_bands).map(|_| biquad::State::ZERO).collect(),
+        }
+    }
+
+    fn update(&mut self, interval: f32) {
+        let f = self.frequency * interval;
+        self.coefficients = [
+            biquad::Coefficients::low_pass(f, FRAC_1_SQRT_2),
+            biquad::Coefficients::high_pass(f, FRAC_1_SQRT_2),
+            // The sum of a Linkwitz-Riley low-pass and high-pass pair
+            biquad::Coefficients::all_pass(f, FRAC_1_SQRT_2),
+        ];
+    }
+}
+
+/// 4th-order Linkwitz-Riley filter state: two cascaded Butterworth biquads
+#[derive(Copy, Clone)]
+struct Lr4([biquad::State<Sample>; 2]);
+
+impl Lr4 {
+    fn process(&mut self, coefficients: &biquad::Coefficients, x: Sample) -> Sample {
+        let x = self.0[0].process(coefficients, &x);
+        self.0[1].process(coefficients, &x)
+    }
+}
+
+impl Default for Lr4 {
+    fn default() -> Self {
+        Self([biquad::State::ZERO; 2])
+    }
+}
+
+/// Number of input frames to sample at a time
+const BUFFER_SIZE: usize = 256;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{math::Float, Sine};
+
+    #[test]
+    fn flat_sum() {
+        const RATE: f32 = 44100.0;
+        for frequency in [50.0, 300.0, 1000.0, 3000.0, 12000.0] {
+            let mut signal = Crossover::new_three_band(Sine::new(0.0, frequency), 250.0, 4000.0);
+            let mut out = [[0.0; 3]; 4410];
+            // Allow the filters to settle
+            signal.sample(1.0 / RATE, &mut out);
+            signal.sample(1.0 / RATE, &mut out);
+            let peak = out
+                .iter()
+                .map(|x| x.iter().sum::<f32>().abs())
+                .fold(0.0f32, f32::max);
+            assert!((peak - 1.0).abs() < 0.01, "{} Hz: {}", frequency, peak);
+        }
+    }
+}
@@ -1,4 +1,5 @@
 use alloc::sync::Arc;
+use core::ops::Range;
 
 use crate::{frame, math::Float, Frame, Frames, Seek, Signal};
 
@@ -6,6 +7,10 @@ use crate::{frame, math::Float, Frame, Frames, Seek, Signal};
 pub struct Cycle<T> {
     /// Current playback time, in samples
     cursor: f64,
+    /// First frame of the looped region
+    start: usize,
+    /// Frame after the last frame of the looped region
+    end: usize,
     frames: Arc<Frames<T>>,
 }
 
@@ -15,9 +20,31 @@ impl<T> Cycle<T> {
     pub fn new(frames: Arc<Frames<T>>) -> Self {
         Self {
             cursor: 0.0,
+            start: 0,
+            end: frames.len(),
             frames,
         }
     }
+
+    /// Loop only the frames in `region`, after playing any frames that precede it once
+    ///
+    /// Suited to music with authored loop points, where an intro leads into a repeating section.
+    /// The last frame of `region` is followed directly by its first, and frames falling exactly on
+    /// a sample position are reproduced without interpolation, so a loop authored to be seamless at
+    /// the sample level plays back bit-exact when the sample rate matches the output's.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `region` is empty or extends past the end of the frames.
+    pub fn with_loop(mut self, region: Range<usize>) -> Self {
+        assert!(
+            region.start < region.end && region.end <= self.frames.len(),
+            "loop region out of bounds"
+        );
+        self.start = region.start;
+        self.end = region.end;
+        self
+    }
 }
 
 impl<T: Frame + Copy> Signal for Cycle<T> {
@@ -30,23 +57,19 @@ impl<T: Frame + Copy> Signal for Cycle<T> {
         for o in out {
             let trunc = unsafe { offset.to_int_unchecked::<usize>() };
             let fract = offset - trunc as f32;
-            let x = base + trunc;
-            let (a, b) = if x < self.frames.len() - 1 {
-                (self.frames[x], self.frames[x + 1])
-            } else if x < self.frames.len() {
-                (self.frames[x], self.frames[0])
-            } else {
+            let mut x = base + trunc;
+            if x >= self.end {
+                x = self.start + (x - self.start) % (self.end - self.start);
                 base = 0;
-                offset = (x % self.frames.len()) as f32 + fract;
-                let x = unsafe { offset.to_int_unchecked::<usize>() };
-                if x < self.frames.len() - 1 {
-                    (self.frames[x], self.frames[x + 1])
-                } else {
-                    (self.frames[x], self.frames[0])
-                }
+                offset = x as f32 + fract;
+            }
+            let a = self.frames[x];
+            *o = if fract == 0.0 {
+                a
+            } else {
+                let next = if x + 1 < self.end { x + 1 } else { self.start };
+                frame::lerp(&a, &self.frames[next], fract)
             };
-
-            *o = frame::lerp(&a, &b, fract);
             offset += ds;
         }
         self.cursor = base as f64 + offset as f64;
@@ -55,8 +78,13 @@ impl<T: Frame + Copy> Signal for Cycle<T> {
 
 impl<T: Frame + Copy> Seek for Cycle<T> {
     fn seek(&mut self, seconds: f32) {
-        self.cursor = (self.cursor + f64::from(seconds) * self.frames.rate() as f64)
-            .rem_euclid(self.frames.len() as f64);
+        let cursor = self.cursor + f64::from(seconds) * self.frames.rate() as f64;
+        let start = self.start as f64;
+        self.cursor = if cursor >= start || self.start == 0 {
+            start + (cursor - start).rem_euclid((self.end - self.start) as f64)
+        } else {
+            cursor.max(0.0)
+        };
     }
 }
 
@@ -120,4 +148,20 @@ mod tests {
         s.sample(10.0, &mut buf[2..]);
         assert_eq!(buf, [1.0, 2.0, 3.0]);
     }
+
+    #[test]
+    fn authored_loop() {
+        const LOOP: &[f32] = &[0.9, 0.1, 0.3, 0.7];
+        let mut s = Cycle::new(Frames::from_slice(1, LOOP)).with_loop(1..4);
+        let mut buf = [0.0; 8];
+        s.sample(1.0, &mut buf[..3]);
+        s.sample(1.0, &mut buf[3..]);
+        assert_eq!(buf, [0.9, 0.1, 0.3, 0.7, 0.1, 0.3, 0.7, 0.1]);
+
+        // Interpolation across the loop point blends with the start of the region
+        let mut s = Cycle::new(Frames::from_slice(1, FRAMES)).with_loop(1..3);
+        let mut buf = [0.0; 6];
+        s.sample(0.5, &mut buf);
+        assert_eq!(buf, [1.0, 1.5, 2.0, 2.5, 3.0, 2.5]);
+    }
 }
@@ -0,0 +1,212 @@
+use alloc::{boxed::Box, sync::Arc, vec::Vec};
+use core::{
+    sync::atomic::{AtomicBool, Ordering},
+    time::Duration,
+};
+use std::{io, thread};
+
+use symphonia::core::{
+    audio::SampleBuffer,
+    codecs::DecoderOptions,
+    errors::Error,
+    formats::{FormatOptions, FormatReader},
+    io::{MediaSource, MediaSourceStream},
+    meta::MetadataOptions,
+    probe::Hint,
+};
+
+use crate::{Sample, Stream, StreamControl};
+
+impl Stream<[Sample; 2]> {
+    /// Stream compressed audio from `source`, decoding it on a background thread
+    ///
+    /// `hint` may describe e.g. the file extension to speed up format detection. Up to
+    /// `buffer_seconds` of decoded audio is held in memory at once, and decoding pauses whenever
+    /// the buffer is full. The stream plays at the source's own sample rate, so it's resampled to
+    /// the output rate as it's played. Mono audio is played through both channels, and channels
+    /// beyond the first two are discarded.
+    ///
+    /// Errors in the format of `source` are reported immediately. If the audio later turns out to
+    /// be corrupt, the stream plays what could be decoded and then finishes.
+    pub fn decode(
+        source: Box<dyn MediaSource>,
+        hint: &Hint,
+        buffer_seconds: f32,
+    ) -> Result<(DecodeHandle, Self), Error> {
+        let source = MediaSourceStream::new(source, Default::default());
+        let format = symphonia::default::get_probe()
+            .format(
+                hint,
+                source,
+                &FormatOptions::default(),
+                &MetadataOptions::default(),
+            )?
+            .format;
+        let track = format
+            .default_track()
+            .ok_or(Error::Unsupported("no audio track"))?;
+        let rate = track
+            .codec_params
+            .sample_rate
+            .ok_or(Error::Unsupported("unknown sample rate"))?;
+        let decoder = symphonia::default::get_codecs()
+            .make(&track.codec_params, &DecoderOptions::default())?;
+        let size = ((buffer_seconds * rate as f32) as usize).max(1);
+        let (control, signal) = Stream::new(rate, size);
+        let stop = Arc::new(AtomicBool::new(false));
+        let decoder = Decoder {
+            track: track.id,
+            format,
+            decoder,
+            control,
+            stop: stop.clone(),
+            // Poll often enough that the buffer can't run dry between refills
+            poll: Duration::from_secs_f32(buffer_seconds / 4.0),
+        };
+        let thread = thread::spawn(move || decoder.run());
+        Ok((
+            DecodeHandle {
+                stop,
+                thread: Some(thread),
+            },
+            signal,
+        ))
+    }
+}
+
+/// Handle to the background thread of a [`Stream::decode`]
+///
+/// Dropping the handle stops decoding, leaving the stream to finish once it has played what was
+/// already decoded.
+pub struct DecodeHandle {
+    stop: Arc<AtomicBool>,
+    thread: Option<thread::JoinHandle<()>>,
+}
+
+impl DecodeHandle {
+    /// Whether the whole source has been decoded, or decoding was otherwise abandoned
+    pub fn is_finished(&self) -> bool {
+        match self.thread {
+            Some(ref x) => x.is_finished(),
+            None => true,
+        }
+    }
+
+    /// Stop decoding, and wait for the background thread to exit
+    pub fn stop(mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(thread) = self.thread.take() {
+            // Decoding errors end the stream rather than propagating, so the thread can't panic
+            let _ = thread.join();
+        }
+    }
+}
+
+impl Drop for DecodeHandle {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
+
+struct Decoder {
+    track: u32,
+    format: Box<dyn FormatReader>,
+    decoder: Box<dyn symphonia::core::codecs::Decoder>,
+    control: StreamControl<[Sample; 2]>,
+    stop: Arc<AtomicBool>,
+    poll: Duration,
+}
+
+impl Decoder {
+    fn run(mut self) {
+        let mut samples: Option<SampleBuffer<Sample>> = None;
+        let mut frames = Vec::new();
+        while !self.stop.load(Ordering::Relaxed) {
+            let packet = match self.format.next_packet() {
+                Ok(x) => x,
+                // The end of the source, or an error we can't recover from
+                Err(_) => return,
+            };
+            if packet.track_id() != self.track {
+                continue;
+            }
+            let decoded = match self.decoder.decode(&packet) {
+                Ok(x) => x,
+                // A corrupt packet can be skipped
+                Err(Error::DecodeError(_)) => continue,
+                Err(_) => return,
+            };
+            let channels = decoded.spec().channels.count();
+            let samples = samples.get_or_insert_with(|| {
+                SampleBuffer::new(decoded.capacity() as u64, *decoded.spec())
+            });
+            if samples.capacity() < decoded.capacity() * channels {
+                *samples = SampleBuffer::new(decoded.capacity() as u64, *decoded.spec());
+            }
+            samples.copy_interleaved_ref(decoded);
+            frames.clear();
+            frames.extend(samples.samples().chunks_exact(channels).map(|x| match *x {
+                [mono] => [mono; 2],
+                [l, r, ..] => [l, r],
+                [] => unreachable!(),
+            }));
+            if !self.write(&frames) {
+                return;
+            }
+        }
+    }
+
+    /// Send `frames` to the stream as space becomes available
+    ///
+    /// Returns `false` if decoding should stop.
+    fn write(&mut self, mut frames: &[[Sample; 2]]) -> bool {
+        while !frames.is_empty() {
+            if self.stop.load(Ordering::Relaxed) || self.control.is_closed() {
+                return false;
+            }
+            let n = self.control.write(frames);
+            frames = &frames[n..];
+            if n == 0 {
+                thread::sleep(self.poll);
+            }
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Signal;
+
+    #[test]
+    fn decode_wav() {
+        const DATA: [i16; 4] = [0, 8192, 16384, -16384];
+        let mut bytes = io::Cursor::new(Vec::new());
+        let spec = hound::WavSpec {
+            channels: 1,
+            sample_rate: 4,
+            bits_per_sample: 16,
+            sample_format: hound::SampleFormat::Int,
+        };
+        let mut writer = hound::WavWriter::new(&mut bytes, spec).unwrap();
+        for &x in &DATA {
+            writer.write_sample(x).unwrap();
+        }
+        writer.finalize().unwrap();
+
+        let mut hint = Hint::new();
+        hint.with_extension("wav");
+        let (handle, mut stream) =
+            Stream::decode(Box::new(io::Cursor::new(bytes.into_inner())), &hint, 1.0).unwrap();
+        while !handle.is_finished() {
+            thread::yield_now();
+        }
+        let mut out = [[0.0; 2]; 4];
+        stream.sample(0.25, &mut out);
+        assert_eq!(out, [[0.0; 2], [0.25; 2], [0.5; 2], [-0.5; 2]]);
+        let mut out = [[0.0; 2]; 1];
+        stream.sample(0.25, &mut out);
+        assert!(stream.is_finished());
+    }
+}
@@ -0,0 +1,104 @@
+use crate::{math::Float, ring::Ring, NoteValue, Sample, Tempo};
+
+/// A record of the recent past of a mono signal, for building echoes, choruses, comb filters, and
+/// other delay-based effects
+///
+/// Data is appended with [`push_block`](Self::push_block), and read back with a fractional delay
+/// using [`tap`](Self::tap), which linearly interpolates between recorded samples.
+pub struct DelayLine {
+    ring: Ring,
+    rate: u32,
+    max_delay: f32,
+}
+
+impl DelayLine {
+    /// Construct a delay line able to record `max_seconds` of audio sampled at `rate`
+    pub fn new(max_seconds: f32, rate: u32) -> Self {
+        Self {
+            ring: Ring::new((max_seconds * rate as f32).ceil() as usize + 1),
+            rate,
+            max_delay: max_seconds,
+        }
+    }
+
+    /// Sample rate of the recorded signal
+    pub fn rate(&self) -> u32 {
+        self.rate
+    }
+
+    /// Record `block`, which follows any previously recorded data
+    pub fn push_block(&mut self, block: &[Sample]) {
+        self.ring.push(block);
+    }
+
+    /// Read the most recent `out.len()` samples, delayed by `delay_seconds`
+    ///
+    /// `out[i]` is the signal `delay_seconds` before the `i`th of the last `out.len()` samples
+    /// recorded. A delay of 0 therefore reproduces the most recent input. The sum of `delay_seconds`
+    /// and the duration of `out` must not exceed the `max_seconds` passed to
+    /// [`new`](Self::new); the delay is clamped to satisfy this.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the duration of `out` alone exceeds `max_seconds`.
+    pub fn tap(&self, delay_seconds: f32, out: &mut [Sample]) {
+        if out.is_empty() {
+            return;
+        }
+        let duration = out.len() as f32 / self.rate as f32;
+        assert!(
+            duration <= self.max_delay,
+            "output must not span more than the delay line's length"
+        );
+        let t = -(delay_seconds.max(0.0) + duration).min(self.max_delay);
+        self.ring.sample(self.rate, t, 1.0 / self.rate as f32, out);
+    }
+
+    /// Like [`tap`](Self::tap), but delayed by `note` at `tempo`, e.g. for an echo on the beat
+    ///
+    /// The delay follows changes to `tempo` immediately, so a change in tempo while the delayed
+    /// signal is audible causes a discontinuity.
+    pub fn tap_note(&self, tempo: &Tempo, note: NoteValue, out: &mut [Sample]) {
+        self.tap(note.seconds(tempo.bpm()), out);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tap() {
+        let mut d = DelayLine::new(4.0, 1);
+        d.push_block(&[1.0, 2.0, 3.0]);
+        let mut out = [0.0; 2];
+        d.tap(0.0, &mut out);
+        assert_eq!(out, [2.0, 3.0]);
+        d.tap(0.5, &mut out);
+        assert_eq!(out, [1.5, 2.5]);
+
+        d.push_block(&[4.0, 5.0, 6.0]);
+        d.tap(2.0, &mut out);
+        assert_eq!(out, [3.0, 4.0]);
+    }
+
+    #[test]
+    fn tap_note() {
+        let mut d = DelayLine::new(4.0, 1);
+        d.push_block(&[1.0, 2.0, 3.0, 4.0]);
+        let mut out = [0.0];
+        let mut tempo = Tempo::new(60.0);
+        d.tap_note(&tempo, NoteValue::QUARTER, &mut out);
+        assert_eq!(out, [3.0]);
+        tempo.set_bpm(30.0);
+        d.tap_note(&tempo, NoteValue::QUARTER, &mut out);
+        assert_eq!(out, [2.0]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn tap_too_long() {
+        let d = DelayLine::new(4.0, 1);
+        d.tap(0.0, &mut [0.0; 5]);
+    }
+}
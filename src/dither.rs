@@ -0,0 +1,137 @@
+//! Conversion of floating-point frames to integer samples
+//!
+//! Rounding samples to a lower bit depth introduces quantization error. For loud signals this is
+//! inaudible, but on quiet passages and fades the error correlates with the signal, producing
+//! audible distortion. Dithering adds a small amount of noise before rounding, which decorrelates
+//! the error and turns it into a constant, benign noise floor. Use [`to_i16`] when writing 16-bit
+//! audio, e.g. for WAV export, and [`to_i16_undithered`] where speed matters more than quality.
+
+use alloc::vec::Vec;
+
+use crate::{math::Float, Frame, Rng};
+
+/// State carried between calls to [`to_i16`]
+///
+/// Use one state per stream of audio, so that noise shaping remains continuous across blocks.
+pub struct DitherState {
+    /// Source of the dither noise
+    rng: Rng,
+    noise_shaping: bool,
+    /// Quantization error of the previous sample in each channel
+    error: Vec<f32>,
+}
+
+impl DitherState {
+    /// Construct state for triangular probability density dither without noise shaping
+    pub fn new() -> Self {
+        Self {
+            rng: Rng::default(),
+            noise_shaping: false,
+            error: Vec::new(),
+        }
+    }
+
+    /// Draw dither noise from `rng`, e.g. to vary the noise between otherwise identical renders
+    ///
+    /// The default generator has a fixed seed, so output is reproducible either way. One value is
+    /// drawn per sample, so it's independent of how frames are divided into calls to [`to_i16`].
+    pub fn with_rng(mut self, rng: Rng) -> Self {
+        self.rng = rng;
+        self
+    }
+
+    /// Shape the dither noise towards high frequencies, where it's less audible
+    ///
+    /// Feeds each sample's quantization error back into the next, lowering the perceived noise
+    /// floor at the cost of more total noise energy.
+    pub fn with_noise_shaping(mut self) -> Self {
+        self.noise_shaping = true;
+        self
+    }
+
+    /// Uniformly distributed random value in [-0.5, 0.5)
+    fn uniform(&mut self) -> f32 {
+        self.rng.next_f32() - 0.5
+    }
+}
+
+impl Default for DitherState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Convert `frames` to interleaved 16-bit samples in `out`, with dithering
+///
+/// Samples outside [-1, 1] are clipped.
+///
+/// # Panics
+///
+/// Panics if `out` does not hold exactly one sample per channel of each frame.
+pub fn to_i16<T: Frame>(frames: &[T], state: &mut DitherState, out: &mut [i16]) {
+    let channels = T::ZERO.channels().len();
+    assert_eq!(out.len(), frames.len() * channels, "output size mismatch");
+    state.error.resize(channels, 0.0);
+    for (frame, out) in frames.iter().zip(out.chunks_exact_mut(channels)) {
+        for (channel, (&x, o)) in frame.channels().iter().zip(out).enumerate() {
+            let mut x = x * SCALE;
+            if state.noise_shaping {
+                x -= state.error[channel];
+            }
+            // Sum of two uniform distributions has a triangular distribution spanning ±1 LSB
+            let noise = state.uniform() + state.uniform();
+            let quantized = (x + noise + 0.5)
+                .floor()
+                .clamp(i16::MIN.into(), i16::MAX.into());
+            state.error[channel] = quantized - x;
+            *o = quantized as i16;
+        }
+    }
+}
+
+/// Convert `frames` to interleaved 16-bit samples in `out` by rounding, without dithering
+///
+/// Faster than [`to_i16`], but quiet signals suffer audible quantization distortion. Samples
+/// outside [-1, 1] are clipped.
+///
+/// # Panics
+///
+/// Panics if `out` does not hold exactly one sample per channel of each frame.
+pub fn to_i16_undithered<T: Frame>(frames: &[T], out: &mut [i16]) {
+    let channels = T::ZERO.channels().len();
+    assert_eq!(out.len(), frames.len() * channels, "output size mismatch");
+    for (frame, out) in frames.iter().zip(out.chunks_exact_mut(channels)) {
+        for (&x, o) in frame.channels().iter().zip(out) {
+            // Float to int casts saturate
+            *o = (x * SCALE + 0.5).floor() as i16;
+        }
+    }
+}
+
+/// Amplitude of a full-scale 16-bit sample
+const SCALE: f32 = i16::MAX as f32;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn undithered() {
+        let mut out = [0; 4];
+        to_i16_undithered(&[[0.0, 1.0], [-2.0, 0.5]], &mut out);
+        assert_eq!(out, [0, i16::MAX, i16::MIN, 16384]);
+    }
+
+    #[test]
+    fn dithered() {
+        // A constant below one LSB is lost by rounding, but survives dithering on average
+        let frames = [0.25 / SCALE; 4096];
+        let mut out = [0; 4096];
+        for mut state in [DitherState::new(), DitherState::new().with_noise_shaping()] {
+            to_i16(&frames, &mut state, &mut out);
+            assert!(out.iter().all(|&x| (-2..=2).contains(&x)));
+            let mean = out.iter().map(|&x| f32::from(x)).sum::<f32>() / out.len() as f32;
+            assert!((mean - 0.25).abs() < 0.05, "mean {}", mean);
+        }
+    }
+}
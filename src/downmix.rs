@@ -1,16 +1,74 @@
-use crate::{Frame, Sample, Seek, Signal};
+use alloc::boxed::Box;
+use core::f32::consts::FRAC_1_SQRT_2;
+
+use crate::{Frame, Reset, Sample, Seek, Signal};
 
 /// Sums all channels together
 ///
 /// Beware that downmixing produces a maximum amplitude equal to the sum of the maximum amplitudes
 /// of its inputs. However, scaling the mixed signal back down by that proportion will usually
 /// produce a quieter signal than the inputs.
-pub struct Downmix<T: ?Sized>(T);
+pub struct Downmix<T: Signal + ?Sized> {
+    /// Per-channel weights, or `None` for an equal sum
+    weights: Option<Box<[f32]>>,
+    /// Staging buffer for frames from `inner`
+    buffer: Box<[T::Frame]>,
+    inner: T,
+}
 
-impl<T> Downmix<T> {
+impl<T: Signal> Downmix<T>
+where
+    T::Frame: Frame,
+{
     /// Sum together `signal`'s channels
     pub fn new(signal: T) -> Self {
-        Self(signal)
+        Self {
+            weights: None,
+            buffer: staging(DEFAULT_BUFFER_SIZE),
+            inner: signal,
+        }
+    }
+
+    /// Sum together `signal`'s channels, each scaled by the corresponding element of `weights`
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of `weights` differs from the number of channels in `signal`.
+    pub fn with_weights(signal: T, weights: &[f32]) -> Self {
+        assert_eq!(
+            weights.len(),
+            T::Frame::ZERO.channels().len(),
+            "one weight is required per channel"
+        );
+        Self {
+            weights: Some(weights.into()),
+            buffer: staging(DEFAULT_BUFFER_SIZE),
+            inner: signal,
+        }
+    }
+
+    /// Sample the inner signal `frames` at a time
+    ///
+    /// Larger buffers improve throughput when sampling large blocks, while smaller ones reduce
+    /// memory use. The default is 256 frames.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `frames` is zero.
+    pub fn with_buffer_size(mut self, frames: usize) -> Self {
+        assert!(frames > 0, "buffer size must be nonzero");
+        self.buffer = staging(frames);
+        self
+    }
+}
+
+impl<T: Signal<Frame = [Sample; 2]>> Downmix<T> {
+    /// Fold stereo `signal` down to mono using the ITU-R BS.775 coefficients
+    ///
+    /// Each channel is attenuated by 3dB before summing, which avoids exaggerating sounds panned
+    /// to the center.
+    pub fn itu_stereo(signal: T) -> Self {
+        Self::with_weights(signal, &[FRAC_1_SQRT_2; 2])
     }
 }
 
@@ -21,19 +79,35 @@ where
     type Frame = Sample;
 
     fn sample(&mut self, interval: f32, out: &mut [Sample]) {
-        const CHUNK_SIZE: usize = 256;
-
-        let mut buf = [Frame::ZERO; CHUNK_SIZE];
-        for chunk in out.chunks_mut(CHUNK_SIZE) {
-            self.0.sample(interval, &mut buf);
-            for (i, o) in buf.iter_mut().zip(chunk) {
-                *o = i.channels().iter().copied().sum();
+        for chunk in out.chunks_mut(self.buffer.len()) {
+            let buf = &mut self.buffer[..chunk.len()];
+            self.inner.sample(interval, buf);
+            match self.weights {
+                None => {
+                    for (i, o) in buf.iter_mut().zip(chunk) {
+                        *o = i.channels().iter().copied().sum();
+                    }
+                }
+                Some(ref weights) => {
+                    for (i, o) in buf.iter_mut().zip(chunk) {
+                        *o = i
+                            .channels()
+                            .iter()
+                            .zip(&**weights)
+                            .map(|(x, w)| x * w)
+                            .sum();
+                    }
+                }
             }
         }
     }
 
     fn is_finished(&self) -> bool {
-        self.0.is_finished()
+        self.inner.is_finished()
+    }
+
+    fn duration_remaining(&self) -> Option<f32> {
+        self.inner.duration_remaining()
     }
 }
 
@@ -42,14 +116,30 @@ where
     T::Frame: Frame,
 {
     fn seek(&mut self, seconds: f32) {
-        self.0.seek(seconds);
+        self.inner.seek(seconds);
     }
 }
 
+impl<T: Reset + Signal + ?Sized> Reset for Downmix<T>
+where
+    T::Frame: Frame,
+{
+    fn reset(&mut self) {
+        self.inner.reset();
+    }
+}
+
+fn staging<T: Frame>(frames: usize) -> Box<[T]> {
+    (0..frames).map(|_| T::ZERO).collect()
+}
+
+/// Number of frames [`Downmix`] samples its inner signal at a time by default
+const DEFAULT_BUFFER_SIZE: usize = 256;
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::Constant;
+    use crate::{Constant, Frames, FramesSignal};
 
     #[test]
     fn smoke() {
@@ -58,4 +148,44 @@ mod tests {
         signal.sample(1.0, &mut out);
         assert_eq!(out, [3.0; 384]);
     }
+
+    #[test]
+    fn buffer_size() {
+        let mut signal = Downmix::new(Constant::new([1.0, 2.0])).with_buffer_size(5);
+        let mut out = [0.0; 12];
+        signal.sample(1.0, &mut out);
+        assert_eq!(out, [3.0; 12]);
+    }
+
+    #[test]
+    fn weighted() {
+        let mut signal = Downmix::with_weights(Constant::new([1.0, 2.0, 4.0]), &[1.0, 0.5, 0.25]);
+        let mut out = [0.0; 2];
+        signal.sample(1.0, &mut out);
+        assert_eq!(out, [3.0; 2]);
+
+        let mut signal = Downmix::itu_stereo(Constant::new([1.0, 1.0]));
+        signal.sample(1.0, &mut out);
+        assert!((out[0] - 2.0f32.sqrt()).abs() < 1e-6);
+    }
+
+    #[test]
+    #[should_panic]
+    fn weights_mismatch() {
+        Downmix::with_weights(Constant::new([1.0, 2.0]), &[1.0]);
+    }
+
+    #[test]
+    fn seek() {
+        const DATA: &[[f32; 2]] = &[[1.0, 0.0], [2.0, 1.0], [3.0, 2.0], [4.0, 3.0]];
+        let mut signal = Downmix::new(FramesSignal::from(Frames::from_slice(1, DATA)));
+        let mut inner = FramesSignal::from(Frames::from_slice(1, DATA));
+        signal.seek(1.5);
+        inner.seek(1.5);
+        let mut out = [0.0; 2];
+        signal.sample(1.0, &mut out);
+        let mut expected = [[0.0; 2]; 2];
+        inner.sample(1.0, &mut expected);
+        assert_eq!(out, expected.map(|x| x[0] + x[1]));
+    }
 }
@@ -0,0 +1,128 @@
+use crate::{math::Float, Frame, MeterControl, Signal};
+
+/// Lowers the volume of a signal while another signal is loud
+///
+/// The level of the other signal, called the key, is obtained from a [`Meter`](crate::Meter). For
+/// example, music can be ducked under dialogue by metering the dialogue and passing its
+/// [`MeterControl`] here.
+pub struct Duck<T: ?Sized> {
+    key: MeterControl,
+    options: DuckOptions,
+    gain: f32,
+    inner: T,
+}
+
+impl<T> Duck<T> {
+    /// Duck `signal` while the RMS level of the channels metered by `key` exceeds the threshold
+    pub fn new(signal: T, key: MeterControl, options: DuckOptions) -> Self {
+        Self {
+            key,
+            options,
+            gain: 1.0,
+            inner: signal,
+        }
+    }
+}
+
+/// Configuration for a [`Duck`] filter, passed to [`Duck::new`]
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DuckOptions {
+    /// RMS level of the key above which the signal is ducked
+    pub threshold: f32,
+    /// Linear gain applied to the signal while ducked, e.g. 0.25 to reduce amplitude to a quarter
+    pub gain: f32,
+    /// Time constant in seconds with which gain falls after the key exceeds the threshold
+    pub attack: f32,
+    /// Time constant in seconds with which gain recovers after the key falls below the threshold
+    pub release: f32,
+}
+
+impl Default for DuckOptions {
+    fn default() -> Self {
+        Self {
+            threshold: 0.05,
+            gain: 0.25,
+            attack: 0.05,
+            release: 0.5,
+        }
+    }
+}
+
+impl<T: Signal> Signal for Duck<T>
+where
+    T::Frame: Frame,
+{
+    type Frame = T::Frame;
+
+    fn sample(&mut self, interval: f32, out: &mut [T::Frame]) {
+        self.inner.sample(interval, out);
+        let (target, tau) = if self.key.max_rms() > self.options.threshold {
+            (self.options.gain, self.options.attack)
+        } else {
+            (1.0, self.options.release)
+        };
+        let alpha = 1.0 - (-interval / tau).exp();
+        for x in out {
+            self.gain += (target - self.gain) * alpha;
+            for s in x.channels_mut() {
+                *s *= self.gain;
+            }
+        }
+    }
+
+    fn is_finished(&self) -> bool {
+        self.inner.is_finished()
+    }
+
+    fn duration_remaining(&self) -> Option<f32> {
+        self.inner.duration_remaining()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Constant, DynamicConstant, Meter};
+
+    #[test]
+    fn smoke() {
+        let (mut level, key_signal) = DynamicConstant::new(0.0);
+        let (key, mut key_signal) = Meter::new(key_signal, 0.01, 0.01);
+        let mut duck = Duck::new(
+            Constant(1.0),
+            key,
+            DuckOptions {
+                threshold: 0.5,
+                gain: 0.25,
+                attack: 0.1,
+                release: 0.2,
+            },
+        );
+        let mut key_out = [0.0; 100];
+        let mut out = [0.0; 100];
+
+        // Quiet key
+        key_signal.sample(0.01, &mut key_out);
+        duck.sample(0.01, &mut out);
+        assert_eq!(out, [1.0; 100]);
+
+        // Loud key
+        level.set(1.0);
+        key_signal.sample(0.01, &mut key_out);
+        duck.sample(0.01, &mut out);
+        for w in out.windows(2) {
+            assert!(w[0] > w[1]);
+        }
+        assert!((out[99] - 0.25).abs() < 1e-3);
+
+        // Quiet again
+        level.set(0.0);
+        key_signal.sample(0.01, &mut key_out);
+        duck.sample(0.01, &mut out);
+        for w in out.windows(2) {
+            assert!(w[0] < w[1]);
+        }
+        assert!(out[99] < 1.0);
+    }
+}
@@ -0,0 +1,100 @@
+//! Gain computation for custom dynamics processors
+//!
+//! Compressors, limiters, expanders, and gates all decide how much to attenuate a signal by
+//! comparing its level to a threshold. The functions here implement that static curve, including
+//! an optional soft knee that eases the transition across the threshold. All levels and gains are
+//! in decibels; feed them a level from e.g. an [`EnvelopeFollower`](crate::EnvelopeFollower), and
+//! smooth the resulting gain with attack and release times before applying it.
+//!
+//! The built-in [`Gate`](crate::Gate) and [`Duck`](crate::Duck) don't use these curves: they switch
+//! between two fixed gains at a hard threshold, which a soft knee can't express.
+
+/// Gain in dB that a compressor applies to a signal at `level` dB
+///
+/// Levels above `threshold` are reduced so that each dB of input beyond it yields `1 / ratio` dB of
+/// output. A `ratio` of [`f32::INFINITY`] makes a limiter. Within `width` dB centered on the
+/// threshold, the ratio ramps smoothly from 1, giving a soft knee; a `width` of 0 gives a hard
+/// knee. The result is never positive.
+pub fn knee(level: f32, threshold: f32, ratio: f32, width: f32) -> f32 {
+    let over = level - threshold;
+    if 2.0 * over <= -width {
+        0.0
+    } else if 2.0 * over < width {
+        let x = over + width / 2.0;
+        (1.0 / ratio - 1.0) * x * x / (2.0 * width)
+    } else {
+        over / ratio - over
+    }
+}
+
+/// Gain in dB that a downward expander applies to a signal at `level` dB
+///
+/// The counterpart of [`knee`] for quiet signals: levels below `threshold` are reduced so that
+/// each dB of input beneath it yields `ratio` dB of output. A `ratio` of [`f32::INFINITY`] makes
+/// a gate. `width` gives a soft knee as in `knee`. The result is never positive.
+pub fn expander_knee(level: f32, threshold: f32, ratio: f32, width: f32) -> f32 {
+    let over = level - threshold;
+    if 2.0 * over >= width {
+        0.0
+    } else if 2.0 * over > -width {
+        let x = over - width / 2.0;
+        (1.0 - ratio) * x * x / (2.0 * width)
+    } else {
+        over * ratio - over
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compressor() {
+        // (level, knee width, gain)
+        const CASES: &[(f32, f32, f32)] = &[
+            (-30.0, 0.0, 0.0),
+            (-20.0, 0.0, 0.0),
+            (-10.0, 0.0, -7.5),
+            (0.0, 0.0, -15.0),
+            (-25.0, 10.0, 0.0),
+            (-20.0, 10.0, -0.9375),
+            (-15.0, 10.0, -3.75),
+            (-10.0, 10.0, -7.5),
+        ];
+        for &(level, width, gain) in CASES {
+            let actual = knee(level, -20.0, 4.0, width);
+            assert!(
+                (actual - gain).abs() < 1e-4,
+                "{} dB, {} dB knee: {}",
+                level,
+                width,
+                actual
+            );
+        }
+        assert_eq!(knee(0.0, -20.0, f32::INFINITY, 0.0), -20.0);
+    }
+
+    #[test]
+    fn expander() {
+        // (level, knee width, gain)
+        const CASES: &[(f32, f32, f32)] = &[
+            (-30.0, 0.0, 0.0),
+            (-40.0, 0.0, 0.0),
+            (-50.0, 0.0, -10.0),
+            (-35.0, 10.0, 0.0),
+            (-40.0, 10.0, -1.25),
+            (-45.0, 10.0, -5.0),
+            (-50.0, 10.0, -10.0),
+        ];
+        for &(level, width, gain) in CASES {
+            let actual = expander_knee(level, -40.0, 2.0, width);
+            assert!(
+                (actual - gain).abs() < 1e-4,
+                "{} dB, {} dB knee: {}",
+                level,
+                width,
+                actual
+            );
+        }
+    }
+}
@@ -0,0 +1,210 @@
+use alloc::sync::Arc;
+use core::sync::atomic::{AtomicU32, Ordering};
+
+use crate::{math::Float, Frame, Reset, Seek, Signal};
+
+/// Tracks the amplitude envelope of a signal without modifying it
+///
+/// The envelope rises towards louder input with the attack time constant and falls towards quieter
+/// input with the release time constant, and can be read from another thread through an
+/// [`EnvelopeControl`], e.g. to drive gameplay or visuals from audio. Unlike a [`Meter`](crate::Meter),
+/// which reports separate per-channel levels for display, this produces a single level across all
+/// channels.
+pub struct EnvelopeFollower<T: ?Sized> {
+    shared: Arc<Shared>,
+    detection: EnvelopeDetection,
+    /// Current envelope, as a mean square for [`EnvelopeDetection::Rms`]
+    state: f32,
+    inner: T,
+}
+
+impl<T> EnvelopeFollower<T> {
+    /// Follow the envelope of `signal`
+    pub fn new(signal: T, options: EnvelopeOptions) -> (EnvelopeControl, Self) {
+        let shared = Arc::new(Shared {
+            level: AtomicU32::new(0.0f32.to_bits()),
+            attack: AtomicU32::new(options.attack.to_bits()),
+            release: AtomicU32::new(options.release.to_bits()),
+        });
+        let signal = Self {
+            shared: shared.clone(),
+            detection: options.detection,
+            state: 0.0,
+            inner: signal,
+        };
+        (EnvelopeControl(shared), signal)
+    }
+}
+
+impl<T: Signal> Signal for EnvelopeFollower<T>
+where
+    T::Frame: Frame,
+{
+    type Frame = T::Frame;
+
+    fn sample(&mut self, interval: f32, out: &mut [T::Frame]) {
+        self.inner.sample(interval, out);
+        let attack = 1.0 - (-interval / load(&self.shared.attack)).exp();
+        let release = 1.0 - (-interval / load(&self.shared.release)).exp();
+        for x in out.iter() {
+            let channels = x.channels();
+            let input = match self.detection {
+                EnvelopeDetection::Peak => channels.iter().fold(0.0f32, |acc, x| acc.max(x.abs())),
+                EnvelopeDetection::Rms => {
+                    channels.iter().map(|x| x * x).sum::<f32>() / channels.len() as f32
+                }
+            };
+            let alpha = if input > self.state { attack } else { release };
+            self.state += (input - self.state) * alpha;
+        }
+        let level = match self.detection {
+            EnvelopeDetection::Peak => self.state,
+            EnvelopeDetection::Rms => self.state.sqrt(),
+        };
+        self.shared.level.store(level.to_bits(), Ordering::Relaxed);
+    }
+
+    fn is_finished(&self) -> bool {
+        self.inner.is_finished()
+    }
+
+    fn duration_remaining(&self) -> Option<f32> {
+        self.inner.duration_remaining()
+    }
+}
+
+impl<T: Seek> Seek for EnvelopeFollower<T>
+where
+    T::Frame: Frame,
+{
+    fn seek(&mut self, seconds: f32) {
+        self.inner.seek(seconds);
+    }
+}
+
+impl<T: Reset> Reset for EnvelopeFollower<T>
+where
+    T::Frame: Frame,
+{
+    fn reset(&mut self) {
+        self.state = 0.0;
+        self.shared.level.store(0.0f32.to_bits(), Ordering::Relaxed);
+        self.inner.reset();
+    }
+}
+
+/// Configuration for an [`EnvelopeFollower`], passed to [`EnvelopeFollower::new`]
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EnvelopeOptions {
+    /// How the level of each frame is measured
+    pub detection: EnvelopeDetection,
+    /// Time constant in seconds with which the envelope rises
+    pub attack: f32,
+    /// Time constant in seconds with which the envelope falls
+    pub release: f32,
+}
+
+impl Default for EnvelopeOptions {
+    fn default() -> Self {
+        Self {
+            detection: EnvelopeDetection::Peak,
+            attack: 0.01,
+            release: 0.3,
+        }
+    }
+}
+
+/// How an [`EnvelopeFollower`] measures the level of its input
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EnvelopeDetection {
+    /// Largest absolute value among the channels, responsive to transients
+    Peak,
+    /// Root mean square over the channels, which better reflects perceived loudness
+    Rms,
+}
+
+/// Thread-safe access to the envelope tracked by an [`EnvelopeFollower`]
+///
+/// Cloning yields another handle to the same follower.
+#[derive(Clone)]
+pub struct EnvelopeControl(Arc<Shared>);
+
+impl EnvelopeControl {
+    /// Linear amplitude of the envelope as of the most recently sampled block
+    pub fn level(&self) -> f32 {
+        load(&self.0.level)
+    }
+
+    /// Get the time constant in seconds with which the envelope rises
+    pub fn attack(&self) -> f32 {
+        load(&self.0.attack)
+    }
+
+    /// Set the time constant in seconds with which the envelope rises
+    pub fn set_attack(&mut self, seconds: f32) {
+        self.0.attack.store(seconds.to_bits(), Ordering::Relaxed);
+    }
+
+    /// Get the time constant in seconds with which the envelope falls
+    pub fn release(&self) -> f32 {
+        load(&self.0.release)
+    }
+
+    /// Set the time constant in seconds with which the envelope falls
+    pub fn set_release(&mut self, seconds: f32) {
+        self.0.release.store(seconds.to_bits(), Ordering::Relaxed);
+    }
+}
+
+struct Shared {
+    level: AtomicU32,
+    attack: AtomicU32,
+    release: AtomicU32,
+}
+
+fn load(x: &AtomicU32) -> f32 {
+    f32::from_bits(x.load(Ordering::Relaxed))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Constant, Sine};
+
+    #[test]
+    fn ballistics() {
+        let (c, mut s) = EnvelopeFollower::new(
+            Constant([0.5, -1.0]),
+            EnvelopeOptions {
+                detection: EnvelopeDetection::Peak,
+                attack: 0.01,
+                release: 1.0,
+            },
+        );
+        let mut out = [[0.0; 2]; 100];
+        s.sample(0.001, &mut out);
+        assert_eq!(out, [[0.5, -1.0]; 100], "passes through");
+        assert!((c.level() - 1.0).abs() < 1e-3, "fast attack");
+
+        s.inner.0 = [0.0; 2];
+        s.sample(0.001, &mut out);
+        assert!(c.level() > 0.85, "slow release");
+    }
+
+    #[test]
+    fn rms() {
+        let (c, mut s) = EnvelopeFollower::new(
+            Sine::new(0.0, 100.0),
+            EnvelopeOptions {
+                detection: EnvelopeDetection::Rms,
+                attack: 0.05,
+                release: 0.05,
+            },
+        );
+        let mut out = [0.0; 8000];
+        s.sample(1.0 / 8000.0, &mut out);
+        assert!((c.level() - 0.5f32.sqrt()).abs() < 0.05);
+    }
+}
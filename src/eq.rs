@@ -0,0 +1,337 @@
+use alloc::sync::Arc;
+use core::sync::atomic::{AtomicU32, Ordering};
+
+use crate::{biquad, Frame, Interpolate, Reset, Signal, Smoothed};
+
+/// Boosts or cuts frequencies near a center frequency
+///
+/// One band of a parametric equalizer. Chain several bands, along with [`LowShelf`] and
+/// [`HighShelf`], to shape the frequency response of e.g. a bus.
+pub struct PeakingEq<T: Signal + ?Sized>(Band<T>);
+
+impl<T: Signal> PeakingEq<T>
+where
+    T::Frame: Frame + Copy,
+{
+    /// Apply a boost of `gain` decibels centered on `frequency` Hz to `signal`
+    ///
+    /// `gain` may be negative to cut instead. Larger `q` values affect a narrower range of
+    /// frequencies; 0.7 is a good place to start.
+    pub fn new(signal: T, frequency: f32, gain: f32, q: f32) -> (EqControl, Self) {
+        let (control, band) = Band::new(signal, biquad::Coefficients::peaking, frequency, gain, q);
+        (control, Self(band))
+    }
+}
+
+impl<T: Signal> Signal for PeakingEq<T>
+where
+    T::Frame: Frame + Copy,
+{
+    type Frame = T::Frame;
+
+    fn sample(&mut self, interval: f32, out: &mut [T::Frame]) {
+        self.0.sample(interval, out);
+    }
+
+    fn is_finished(&self) -> bool {
+        self.0.inner.is_finished()
+    }
+
+    fn duration_remaining(&self) -> Option<f32> {
+        self.0.inner.duration_remaining()
+    }
+}
+
+impl<T: Reset> Reset for PeakingEq<T>
+where
+    T::Frame: Frame + Copy,
+{
+    fn reset(&mut self) {
+        self.0.reset();
+    }
+}
+
+/// Boosts or cuts frequencies below a corner frequency
+pub struct LowShelf<T: Signal + ?Sized>(Band<T>);
+
+impl<T: Signal> LowShelf<T>
+where
+    T::Frame: Frame + Copy,
+{
+    /// Apply a boost of `gain` decibels to frequencies of `signal` below `frequency` Hz
+    ///
+    /// `gain` may be negative to cut instead. `q` controls the steepness of the transition; 0.7
+    /// gives a smooth response.
+    pub fn new(signal: T, frequency: f32, gain: f32, q: f32) -> (EqControl, Self) {
+        let (control, band) =
+            Band::new(signal, biquad::Coefficients::low_shelf, frequency, gain, q);
+        (control, Self(band))
+    }
+}
+
+impl<T: Signal> Signal for LowShelf<T>
+where
+    T::Frame: Frame + Copy,
+{
+    type Frame = T::Frame;
+
+    fn sample(&mut self, interval: f32, out: &mut [T::Frame]) {
+        self.0.sample(interval, out);
+    }
+
+    fn is_finished(&self) -> bool {
+        self.0.inner.is_finished()
+    }
+
+    fn duration_remaining(&self) -> Option<f32> {
+        self.0.inner.duration_remaining()
+    }
+}
+
+impl<T: Reset> Reset for LowShelf<T>
+where
+    T::Frame: Frame + Copy,
+{
+    fn reset(&mut self) {
+        self.0.reset();
+    }
+}
+
+/// Boosts or cuts frequencies above a corner frequency
+pub struct HighShelf<T: Signal + ?Sized>(Band<T>);
+
+impl<T: Signal> HighShelf<T>
+where
+    T::Frame: Frame + Copy,
+{
+    /// Apply a boost of `gain` decibels to frequencies of `signal` above `frequency` Hz
+    ///
+    /// `gain` may be negative to cut instead. `q` controls the steepness of the transition; 0.7
+    /// gives a smooth response.
+    pub fn new(signal: T, frequency: f32, gain: f32, q: f32) -> (EqControl, Self) {
+        let (control, band) =
+            Band::new(signal, biquad::Coefficients::high_shelf, frequency, gain, q);
+        (control, Self(band))
+    }
+}
+
+impl<T: Signal> Signal for HighShelf<T>
+where
+    T::Frame: Frame + Copy,
+{
+    type Frame = T::Frame;
+
+    fn sample(&mut self, interval: f32, out: &mut [T::Frame]) {
+        self.0.sample(interval, out);
+    }
+
+    fn is_finished(&self) -> bool {
+        self.0.inner.is_finished()
+    }
+
+    fn duration_remaining(&self) -> Option<f32> {
+        self.0.inner.duration_remaining()
+    }
+}
+
+impl<T: Reset> Reset for HighShelf<T>
+where
+    T::Frame: Frame + Copy,
+{
+    fn reset(&mut self) {
+        self.0.reset();
+    }
+}
+
+/// Thread-safe control for a [`PeakingEq`], [`LowShelf`], or [`HighShelf`] filter
+///
+/// Changes take effect smoothly over a short period.
+pub struct EqControl(Arc<Shared>);
+
+impl EqControl {
+    /// Get the center or corner frequency in Hz
+    pub fn frequency(&self) -> f32 {
+        self.0.load().frequency
+    }
+
+    /// Set the center or corner frequency in Hz
+    pub fn set_frequency(&mut self, hz: f32) {
+        self.0.frequency.store(hz.to_bits(), Ordering::Relaxed);
+    }
+
+    /// Get the boost in decibels
+    pub fn gain(&self) -> f32 {
+        self.0.load().gain
+    }
+
+    /// Set the boost in decibels, which may be negative to cut instead
+    pub fn set_gain(&mut self, db: f32) {
+        self.0.gain.store(db.to_bits(), Ordering::Relaxed);
+    }
+
+    /// Get the quality factor
+    pub fn q(&self) -> f32 {
+        self.0.load().q
+    }
+
+    /// Set the quality factor
+    pub fn set_q(&mut self, q: f32) {
+        self.0.q.store(q.to_bits(), Ordering::Relaxed);
+    }
+}
+
+/// Filter implementation shared by all band shapes
+struct Band<T: Signal + ?Sized> {
+    shared: Arc<Shared>,
+    shape: fn(f32, f32, f32) -> biquad::Coefficients,
+    params: Smoothed<Params>,
+    coefficients: biquad::Coefficients,
+    /// Interval for which `coefficients` were computed
+    interval: f32,
+    state: biquad::State<T::Frame>,
+    inner: T,
+}
+
+impl<T: Signal> Band<T>
+where
+    T::Frame: Frame + Copy,
+{
+    fn new(
+        signal: T,
+        shape: fn(f32, f32, f32) -> biquad::Coefficients,
+        frequency: f32,
+        gain: f32,
+        q: f32,
+    ) -> (EqControl, Self) {
+        let params = Params { frequency, gain, q };
+        let shared = Arc::new(Shared {
+            frequency: AtomicU32::new(frequency.to_bits()),
+            gain: AtomicU32::new(gain.to_bits()),
+            q: AtomicU32::new(q.to_bits()),
+        });
+        let band = Self {
+            shared: shared.clone(),
+            shape,
+            params: Smoothed::new(params),
+            coefficients: biquad::Coefficients::IDENTITY,
+            // Forces coefficients to be computed on first use
+            interval: f32::NAN,
+            state: biquad::State::ZERO,
+            inner: signal,
+        };
+        (EqControl(shared), band)
+    }
+
+    #[allow(clippy::float_cmp)]
+    fn sample(&mut self, interval: f32, out: &mut [T::Frame]) {
+        self.inner.sample(interval, out);
+        let target = self.shared.load();
+        if *self.params.target() != target {
+            self.params.set(target);
+        }
+        if self.params.progress() >= 1.0 && interval == self.interval {
+            for x in out {
+                *x = self.state.process(&self.coefficients, x);
+            }
+            return;
+        }
+        // Recompute coefficients periodically while parameters change
+        self.interval = interval;
+        for chunk in out.chunks_mut(RAMP_CHUNK) {
+            self.params
+                .advance(chunk.len() as f32 * interval / SMOOTHING_PERIOD);
+            let params = self.params.get();
+            self.coefficients = (self.shape)(params.frequency * interval, params.gain, params.q);
+            for x in chunk {
+                *x = self.state.process(&self.coefficients, x);
+            }
+        }
+    }
+}
+
+impl<T: Reset> Band<T>
+where
+    T::Frame: Frame + Copy,
+{
+    /// Complete any parameter change and clear the filter's memory
+    fn reset(&mut self) {
+        self.params = Smoothed::new(self.shared.load());
+        self.interval = f32::NAN;
+        self.state = biquad::State::ZERO;
+        self.inner.reset();
+    }
+}
+
+struct Shared {
+    frequency: AtomicU32,
+    gain: AtomicU32,
+    q: AtomicU32,
+}
+
+impl Shared {
+    fn load(&self) -> Params {
+        Params {
+            frequency: f32::from_bits(self.frequency.load(Ordering::Relaxed)),
+            gain: f32::from_bits(self.gain.load(Ordering::Relaxed)),
+            q: f32::from_bits(self.q.load(Ordering::Relaxed)),
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq)]
+struct Params {
+    frequency: f32,
+    gain: f32,
+    q: f32,
+}
+
+impl Interpolate for Params {
+    fn interpolate(&self, other: &Self, t: f32) -> Self {
+        Self {
+            frequency: self.frequency.interpolate(&other.frequency, t),
+            gain: self.gain.interpolate(&other.gain, t),
+            q: self.q.interpolate(&other.q, t),
+        }
+    }
+}
+
+/// Number of seconds over which to smooth a change in parameters
+const SMOOTHING_PERIOD: f32 = 0.05;
+
+/// Number of frames between coefficient updates while parameters change
+const RAMP_CHUNK: usize = 32;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_util::peak, Sample, Sine};
+
+    const RATE: f32 = 44100.0;
+
+    #[test]
+    fn peaking() {
+        let gain = |frequency| {
+            let (_, mut eq) = PeakingEq::new(Sine::new(0.0, frequency), 1000.0, 6.0, 1.0);
+            peak(&mut eq, RATE)
+        };
+        assert!((gain(1000.0) - 10.0f32.powf(6.0 / 20.0)).abs() < 1e-2);
+        assert!(gain(100.0) < 1.1);
+        assert!(gain(10_000.0) < 1.1);
+    }
+
+    #[test]
+    fn shelves() {
+        let (_, mut low) = LowShelf::new(Sine::new(0.0, 50.0), 500.0, -12.0, 0.7);
+        assert!((peak(&mut low, RATE) - 10.0f32.powf(-12.0 / 20.0)).abs() < 1e-2);
+        let (_, mut high) = HighShelf::new(Sine::new(0.0, 50.0), 500.0, -12.0, 0.7);
+        assert!((peak(&mut high, RATE) - 1.0).abs() < 1e-2);
+    }
+
+    #[test]
+    fn smoothed() {
+        let (mut c, mut eq) = PeakingEq::new(Sine::new(0.0, 1000.0), 1000.0, 0.0, 1.0);
+        assert!((peak(&mut eq, RATE) - 1.0).abs() < 1e-2);
+        c.set_gain(-12.0);
+        assert!((peak(&mut eq, RATE) - 10.0f32.powf(-12.0 / 20.0)).abs() < 1e-2);
+    }
+}
@@ -0,0 +1,89 @@
+use crate::{frame, Frame, Reset, Seek, Signal};
+
+/// Fades out the end of a finite signal, avoiding a click if it ends abruptly
+///
+/// Over the final `duration` seconds before the inner signal finishes, its output is ramped down
+/// so that its last frame is silent. Relies on [`Signal::duration_remaining`], so has no effect on
+/// signals that don't report a finite remaining duration, such as those that loop forever.
+pub struct FadeOutTail<T: ?Sized> {
+    duration: f32,
+    inner: T,
+}
+
+impl<T> FadeOutTail<T> {
+    /// Fade out the last `duration` seconds of `signal`
+    pub fn new(signal: T, duration: f32) -> Self {
+        Self {
+            duration,
+            inner: signal,
+        }
+    }
+}
+
+impl<T: Signal + ?Sized> Signal for FadeOutTail<T>
+where
+    T::Frame: Frame,
+{
+    type Frame = T::Frame;
+
+    fn sample(&mut self, interval: f32, out: &mut [T::Frame]) {
+        let remaining = self.inner.duration_remaining();
+        self.inner.sample(interval, out);
+        let remaining = match remaining {
+            Some(x) if self.duration > 0.0 => x,
+            _ => return,
+        };
+        if remaining - interval * out.len() as f32 > self.duration {
+            // The fade hasn't begun
+            return;
+        }
+        for (i, x) in out.iter_mut().enumerate() {
+            let gain = ((remaining - i as f32 * interval) / self.duration).clamp(0.0, 1.0);
+            *x = frame::scale(x, gain);
+        }
+    }
+
+    fn is_finished(&self) -> bool {
+        self.inner.is_finished()
+    }
+
+    fn duration_remaining(&self) -> Option<f32> {
+        self.inner.duration_remaining()
+    }
+}
+
+impl<T: Seek + ?Sized> Seek for FadeOutTail<T>
+where
+    T::Frame: Frame,
+{
+    fn seek(&mut self, seconds: f32) {
+        self.inner.seek(seconds);
+    }
+}
+
+impl<T: Reset + ?Sized> Reset for FadeOutTail<T>
+where
+    T::Frame: Frame,
+{
+    fn reset(&mut self) {
+        self.inner.reset();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Frames, FramesSignal};
+
+    #[test]
+    fn tail() {
+        let frames = Frames::from_slice(1, &[1.0; 6]);
+        let mut signal = FadeOutTail::new(FramesSignal::from(frames), 2.0);
+        let mut out = [0.0; 4];
+        signal.sample(1.0, &mut out[..2]);
+        signal.sample(1.0, &mut out[2..]);
+        assert_eq!(out, [1.0, 1.0, 1.0, 1.0]);
+        signal.sample(1.0, &mut out[..2]);
+        assert_eq!(out[..2], [0.5, 0.0]);
+    }
+}
@@ -1,25 +1,31 @@
-use alloc::sync::Arc;
+use alloc::{boxed::Box, sync::Arc};
 use core::mem;
 
-use crate::{frame, math::Float, swap, Frame, Signal};
+use crate::{frame, math::Float, swap, Frame, Seek, Signal};
 
 /// Cross-fades smoothly between dynamically-supplied signals
 ///
 /// Uses constant-power fading, suitable for blending uncorrelated signals without distorting
 /// perceived loudness
-pub struct Fader<T> {
+pub struct Fader<T: Signal> {
     progress: f32,
     next: swap::Receiver<Option<Command<T>>>,
+    /// Staging buffer for the outgoing signal during a fade
+    buffer: Box<[T::Frame]>,
     inner: T,
 }
 
-impl<T: Send> Fader<T> {
+impl<T: Signal + Send> Fader<T>
+where
+    T::Frame: Frame,
+{
     /// Create a fader initially wrapping `inner`
     pub fn new(inner: T) -> (FaderControl<T>, Self) {
         let (send, recv) = swap::swap(|| None);
         let signal = Self {
             progress: 1.0,
             next: recv,
+            buffer: (0..BUFFER_SIZE).map(|_| T::Frame::ZERO).collect(),
             inner,
         };
         let control = FaderControl(send);
@@ -49,12 +55,11 @@ where
         let next = (*self.next.received()).as_mut().unwrap();
         let increment = interval / next.duration;
         while !out.is_empty() {
-            let mut buffer = [(); 1024].map(|()| T::Frame::ZERO);
-            let n = buffer.len().min(out.len());
-            self.inner.sample(interval, &mut buffer);
-            next.fade_to.sample(interval, out);
+            let n = self.buffer.len().min(out.len());
+            self.inner.sample(interval, &mut self.buffer[..n]);
+            next.fade_to.sample(interval, &mut out[..n]);
 
-            for (o, x) in out.iter_mut().zip(&buffer) {
+            for (o, x) in out.iter_mut().zip(&*self.buffer) {
                 let fade_out = (1.0 - self.progress).sqrt();
                 let fade_in = self.progress.sqrt();
                 *o = frame::mix(&frame::scale(x, fade_out), &frame::scale(o, fade_in));
@@ -70,9 +75,29 @@ where
         }
     }
 
+    /// Finished when no fade is in progress or pending and the current signal is finished
     #[inline]
     fn is_finished(&self) -> bool {
-        false
+        self.progress >= 1.0 && !self.next.is_fresh() && self.inner.is_finished()
+    }
+}
+
+/// Seeking is permitted even while a fade is in progress, in which case both the outgoing and
+/// incoming signals are seeked, keeping them aligned. The progress of the fade itself is
+/// unaffected. Refusing to seek mid-fade would make faders unusable where seeking is routine, e.g.
+/// in a [`SpatialScene`](crate::SpatialScene), which seeks its signals on every block to model
+/// propagation delay.
+impl<T: Seek> Seek for Fader<T>
+where
+    T::Frame: Frame,
+{
+    fn seek(&mut self, seconds: f32) {
+        self.inner.seek(seconds);
+        if self.progress < 1.0 {
+            if let Some(next) = self.next.received() {
+                next.fade_to.seek(seconds);
+            }
+        }
     }
 }
 
@@ -97,9 +122,12 @@ struct Command<T> {
     duration: f32,
 }
 
+/// Number of frames of the outgoing signal to sample at a time during a fade
+const BUFFER_SIZE: usize = 1024;
+
 #[cfg(test)]
 mod tests {
-    use crate::Constant;
+    use crate::{Constant, Frames, FramesSignal};
 
     use super::*;
 
@@ -115,4 +143,19 @@ mod tests {
         assert_eq!(buf[11], 0.0);
         assert!((buf[5] - 0.5f32.sqrt()).abs() < 1e-6);
     }
+
+    #[test]
+    fn finished() {
+        let frames = Frames::from_slice(1, &[1.0, 1.0]);
+        let (mut c, mut s) = Fader::new(FramesSignal::from(frames.clone()));
+        let mut buf = [0.0; 4];
+        s.sample(1.0, &mut buf);
+        assert!(s.is_finished());
+        c.fade_to(FramesSignal::from(frames), 2.0);
+        assert!(!s.is_finished(), "fade pending");
+        s.sample(0.25, &mut buf);
+        assert!(!s.is_finished(), "fade in progress");
+        s.sample(1.0, &mut buf);
+        assert!(s.is_finished(), "fade complete");
+    }
 }
@@ -0,0 +1,272 @@
+use alloc::sync::Arc;
+use core::{
+    f32::consts::TAU,
+    slice,
+    sync::atomic::{AtomicU32, Ordering},
+};
+
+use crate::{
+    math::Float,
+    tempo::{NoteValue, Tempo, TempoSync},
+    DelayLine, Sample, Signal,
+};
+
+/// Produces a sweeping "jet" effect by mixing a signal with a copy of itself under a short,
+/// slowly varying delay
+///
+/// The combination forms a comb filter whose notches move as the delay changes. Feeding the
+/// delayed signal back into the delay line deepens the effect. Delays are measured in samples at the
+/// `rate` passed to [`new`](Self::new); sampling at a different rate scales them accordingly.
+pub struct Flanger<T: ?Sized> {
+    shared: Arc<Shared>,
+    rate: u32,
+    /// LFO phase, in [0, 1)
+    phase: f32,
+    /// Tempo to which the LFO is synced, if any
+    sync: Option<TempoSync>,
+    /// Seconds since `inner` finished
+    tail: f32,
+    line: DelayLine,
+    inner: T,
+}
+
+impl<T> Flanger<T> {
+    /// Apply flanging to `signal`, tuned for sample rate `rate`
+    pub fn new(signal: T, rate: u32, options: FlangerOptions) -> (FlangerControl, Self) {
+        let shared = Arc::new(Shared {
+            min_delay: AtomicU32::new(0),
+            max_delay: AtomicU32::new(0),
+            rate: AtomicU32::new(options.rate.to_bits()),
+            feedback: AtomicU32::new(0),
+            mix: AtomicU32::new(0),
+        });
+        let mut control = FlangerControl(shared.clone());
+        control.set_delay_range(options.min_delay, options.max_delay);
+        control.set_feedback(options.feedback);
+        control.set_mix(options.mix);
+        let signal = Self {
+            shared,
+            rate,
+            phase: 0.0,
+            sync: None,
+            tail: 0.0,
+            line: DelayLine::new(MAX_DELAY, rate),
+            inner: signal,
+        };
+        (control, signal)
+    }
+
+    /// Sweep the delay once per `note` at `tempo`, rather than at a rate in Hz
+    ///
+    /// See [`Tempo`] for details.
+    pub fn with_tempo(mut self, tempo: Tempo, note: NoteValue) -> Self {
+        self.sync = Some(TempoSync { tempo, note });
+        self
+    }
+}
+
+impl<T: Signal<Frame = Sample>> Signal for Flanger<T> {
+    type Frame = Sample;
+
+    fn sample(&mut self, interval: f32, out: &mut [Sample]) {
+        let min_delay = load(&self.shared.min_delay);
+        let max_delay = load(&self.shared.max_delay);
+        let lfo_rate = match self.sync {
+            Some(ref sync) => sync.hz(),
+            None => load(&self.shared.rate),
+        };
+        let dphase = lfo_rate * interval;
+        let feedback = load(&self.shared.feedback);
+        let mix = load(&self.shared.mix);
+        let sample_period = 1.0 / self.rate as f32;
+
+        self.inner.sample(interval, out);
+        for x in out.iter_mut() {
+            let sweep = 0.5 - 0.5 * (TAU * self.phase).cos();
+            let delay = min_delay + (max_delay - min_delay) * sweep;
+            // The current sample hasn't been recorded yet, so the most recent one is already a
+            // sample old
+            let mut delayed = 0.0;
+            self.line
+                .tap(delay - sample_period, slice::from_mut(&mut delayed));
+            self.line.push_block(&[*x + feedback * delayed]);
+            *x = *x * (1.0 - mix) + delayed * mix;
+            self.phase = (self.phase + dphase) % 1.0;
+        }
+
+        if self.inner.is_finished() {
+            self.tail += interval * out.len() as f32;
+        }
+    }
+
+    fn is_finished(&self) -> bool {
+        self.inner.is_finished() && self.tail > self.tail_duration()
+    }
+
+    fn duration_remaining(&self) -> Option<f32> {
+        let tail = (self.tail_duration() - self.tail).max(0.0);
+        Some(self.inner.duration_remaining()? + tail)
+    }
+}
+
+impl<T: ?Sized> Flanger<T> {
+    /// Upper bound on how long the feedback remains audible after input stops
+    fn tail_duration(&self) -> f32 {
+        // Each trip through the delay line attenuates the signal by `feedback`
+        let feedback = load(&self.shared.feedback).abs();
+        let trips = (SILENCE.ln() / feedback.max(f32::MIN_POSITIVE).ln()).max(1.0);
+        trips * load(&self.shared.max_delay)
+    }
+}
+
+/// Configuration for a [`Flanger`], passed to [`Flanger::new`]
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FlangerOptions {
+    /// Shortest delay of the sweep in seconds
+    pub min_delay: f32,
+    /// Longest delay of the sweep in seconds, at most 20ms
+    pub max_delay: f32,
+    /// Frequency of the sweep in Hz
+    pub rate: f32,
+    /// Proportion of the delayed signal fed back into the delay line, in (-1, 1)
+    pub feedback: f32,
+    /// Proportion of delayed signal in the output, from 0 (dry) to 1 (wet)
+    pub mix: f32,
+}
+
+impl Default for FlangerOptions {
+    fn default() -> Self {
+        Self {
+            min_delay: 0.001,
+            max_delay: 0.005,
+            rate: 0.25,
+            feedback: 0.5,
+            mix: 0.5,
+        }
+    }
+}
+
+/// Thread-safe control for a [`Flanger`]
+pub struct FlangerControl(Arc<Shared>);
+
+impl FlangerControl {
+    /// Get the shortest and longest delays of the sweep in seconds
+    pub fn delay_range(&self) -> (f32, f32) {
+        (load(&self.0.min_delay), load(&self.0.max_delay))
+    }
+
+    /// Set the shortest and longest delays of the sweep in seconds
+    ///
+    /// Delays are clamped between 0 and 20ms. The current sample is mixed with previous ones only,
+    /// so delays shorter than one sample behave as a delay of one sample.
+    pub fn set_delay_range(&mut self, min: f32, max: f32) {
+        let max = max.clamp(0.0, MAX_DELAY);
+        let min = min.clamp(0.0, max);
+        store(&self.0.min_delay, min);
+        store(&self.0.max_delay, max);
+    }
+
+    /// Get the frequency of the sweep in Hz
+    pub fn rate(&self) -> f32 {
+        load(&self.0.rate)
+    }
+
+    /// Set the frequency of the sweep in Hz
+    pub fn set_rate(&mut self, hz: f32) {
+        store(&self.0.rate, hz);
+    }
+
+    /// Get the proportion of the delayed signal fed back into the delay line
+    pub fn feedback(&self) -> f32 {
+        load(&self.0.feedback)
+    }
+
+    /// Set the proportion of the delayed signal fed back into the delay line
+    ///
+    /// Magnitudes of 1 or more would grow without bound, so values are clamped to within ±0.95.
+    /// Negative values emphasize odd harmonics of the comb, giving a hollower sound. Large
+    /// magnitudes produce strong resonances that may clip.
+    pub fn set_feedback(&mut self, feedback: f32) {
+        store(
+            &self.0.feedback,
+            feedback.clamp(-MAX_FEEDBACK, MAX_FEEDBACK),
+        );
+    }
+
+    /// Get the proportion of delayed signal in the output
+    pub fn mix(&self) -> f32 {
+        load(&self.0.mix)
+    }
+
+    /// Set the proportion of delayed signal in the output, from 0 (dry) to 1 (wet)
+    ///
+    /// Notches are deepest at 0.5.
+    pub fn set_mix(&mut self, mix: f32) {
+        store(&self.0.mix, mix.clamp(0.0, 1.0));
+    }
+}
+
+struct Shared {
+    min_delay: AtomicU32,
+    max_delay: AtomicU32,
+    rate: AtomicU32,
+    feedback: AtomicU32,
+    mix: AtomicU32,
+}
+
+fn load(x: &AtomicU32) -> f32 {
+    f32::from_bits(x.load(Ordering::Relaxed))
+}
+
+fn store(x: &AtomicU32, value: f32) {
+    x.store(value.to_bits(), Ordering::Relaxed);
+}
+
+/// Longest supported delay in seconds
+const MAX_DELAY: f32 = 0.02;
+
+/// Largest magnitude of feedback, keeping the filter stable
+const MAX_FEEDBACK: f32 = 0.95;
+
+/// Amplitude below which the feedback tail is considered inaudible
+const SILENCE: f32 = 1e-3;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Sine;
+
+    const RATE: u32 = 48000;
+
+    #[test]
+    fn moving_notch() {
+        let options = FlangerOptions {
+            min_delay: 0.0005,
+            max_delay: 0.0015,
+            rate: 2.0,
+            feedback: 0.0,
+            mix: 0.5,
+        };
+        let (_, mut flanger) = Flanger::new(Sine::new(0.0, 1000.0), RATE, options);
+        let mut out = [0.0; RATE as usize / 2];
+        flanger.sample(1.0 / RATE as f32, &mut out);
+        // A 1kHz tone is cancelled at delays of 0.5ms and 1.5ms, and reinforced at 1ms
+        let peaks = out
+            .chunks(RATE as usize / 100)
+            .skip(1)
+            .map(|c| c.iter().fold(0.0f32, |acc, x| acc.max(x.abs())))
+            .collect::<alloc::vec::Vec<_>>();
+        let lo = peaks.iter().copied().fold(f32::INFINITY, f32::min);
+        let hi = peaks.iter().copied().fold(0.0, f32::max);
+        assert!(lo < 0.2, "{:?}", peaks);
+        assert!(hi > 0.9, "{:?}", peaks);
+    }
+
+    #[test]
+    fn feedback_clamped() {
+        let (mut c, _) = Flanger::new(Sine::new(0.0, 1.0), RATE, FlangerOptions::default());
+        c.set_feedback(2.0);
+        assert_eq!(c.feedback(), MAX_FEEDBACK);
+    }
+}
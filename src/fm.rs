@@ -0,0 +1,134 @@
+use alloc::sync::Arc;
+use core::{
+    f32::consts::TAU,
+    sync::atomic::{AtomicU32, Ordering},
+};
+
+use crate::{math::Float, Sample, Signal};
+
+/// A pair of sine waves where one modulates the frequency of the other, for simple FM synthesis
+///
+/// The carrier's instantaneous frequency deviates from its base frequency by up to `index` times the
+/// modulator's frequency, producing sidebands at the carrier frequency plus and minus multiples of
+/// the modulator frequency. Integer ratios between the two frequencies produce harmonic, tonal
+/// sounds, while other ratios produce clangorous, bell-like ones. Larger indices spread energy
+/// across more sidebands, brightening the sound.
+pub struct FmVoice {
+    shared: Arc<Shared>,
+    /// Carrier phase in radians
+    carrier: f32,
+    /// Modulator phase in radians
+    modulator: f32,
+}
+
+impl FmVoice {
+    /// Construct a voice with a carrier at `carrier_hz` modulated at `ratio` times that frequency
+    /// with modulation index `index`
+    pub fn new(carrier_hz: f32, ratio: f32, index: f32) -> (FmVoiceControl, Self) {
+        let shared = Arc::new(Shared {
+            frequency: AtomicU32::new(carrier_hz.to_bits()),
+            ratio: AtomicU32::new(ratio.to_bits()),
+            index: AtomicU32::new(index.to_bits()),
+        });
+        let signal = Self {
+            shared: shared.clone(),
+            carrier: 0.0,
+            modulator: 0.0,
+        };
+        (FmVoiceControl(shared), signal)
+    }
+}
+
+impl Signal for FmVoice {
+    type Frame = Sample;
+
+    fn sample(&mut self, interval: f32, out: &mut [Sample]) {
+        let carrier = TAU * load(&self.shared.frequency);
+        let modulator = carrier * load(&self.shared.ratio);
+        let deviation = modulator * load(&self.shared.index);
+        for x in out {
+            *x = self.carrier.sin();
+            let frequency = carrier + deviation * self.modulator.cos();
+            // Wrap for numerical stability no matter how long we play for
+            self.carrier = (self.carrier + frequency * interval).rem_euclid(TAU);
+            self.modulator = (self.modulator + modulator * interval) % TAU;
+        }
+    }
+}
+
+/// Thread-safe control for an [`FmVoice`]
+pub struct FmVoiceControl(Arc<Shared>);
+
+impl FmVoiceControl {
+    /// Get the carrier frequency in Hz
+    pub fn frequency(&self) -> f32 {
+        load(&self.0.frequency)
+    }
+
+    /// Set the carrier frequency in Hz
+    pub fn set_frequency(&mut self, hz: f32) {
+        self.0.frequency.store(hz.to_bits(), Ordering::Relaxed);
+    }
+
+    /// Get the ratio of the modulator frequency to the carrier frequency
+    pub fn ratio(&self) -> f32 {
+        load(&self.0.ratio)
+    }
+
+    /// Set the ratio of the modulator frequency to the carrier frequency
+    pub fn set_ratio(&mut self, ratio: f32) {
+        self.0.ratio.store(ratio.to_bits(), Ordering::Relaxed);
+    }
+
+    /// Get the modulation index
+    pub fn index(&self) -> f32 {
+        load(&self.0.index)
+    }
+
+    /// Set the modulation index, i.e. the peak frequency deviation relative to the modulator
+    /// frequency
+    pub fn set_index(&mut self, index: f32) {
+        self.0.index.store(index.to_bits(), Ordering::Relaxed);
+    }
+}
+
+struct Shared {
+    /// Carrier frequency in Hz
+    frequency: AtomicU32,
+    ratio: AtomicU32,
+    index: AtomicU32,
+}
+
+fn load(x: &AtomicU32) -> f32 {
+    f32::from_bits(x.load(Ordering::Relaxed))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::magnitude;
+
+    const RATE: u32 = 8000;
+
+    #[test]
+    fn sidebands() {
+        let (_, mut voice) = FmVoice::new(1000.0, 0.25, 1.0);
+        let mut out = [0.0; RATE as usize];
+        voice.sample(1.0 / RATE as f32, &mut out);
+        // Amplitudes are given by Bessel functions of the first kind: J0(1), J1(1), J2(1)
+        for &(hz, expected) in &[
+            (1000.0, 0.765),
+            (750.0, 0.440),
+            (1250.0, 0.440),
+            (500.0, 0.115),
+            (1500.0, 0.115),
+        ] {
+            let actual = magnitude(&out, hz, RATE as f32);
+            assert!((actual - expected).abs() < 0.02, "{}Hz: {}", hz, actual);
+        }
+        assert!(
+            magnitude(&out, 1125.0, RATE as f32) < 0.01,
+            "no energy between sidebands"
+        );
+    }
+}
@@ -40,6 +40,24 @@ pub(crate) fn lerp<T: Frame>(a: &T, b: &T, t: f32) -> T {
     bimap(a, b, |a, b| a + t * (b - a))
 }
 
+/// Catmull-Rom spline through `b` and `c`, using `a` and `d` as outer control points
+#[inline]
+pub(crate) fn cubic<T: Frame>(a: &T, b: &T, c: &T, d: &T, t: f32) -> T {
+    let mut out = T::ZERO;
+    for (i, o) in out.channels_mut().iter_mut().enumerate() {
+        let (a, b, c, d) = (
+            a.channels()[i],
+            b.channels()[i],
+            c.channels()[i],
+            d.channels()[i],
+        );
+        *o = b + 0.5
+            * t
+            * ((c - a) + t * ((2.0 * a - 5.0 * b + 4.0 * c - d) + t * (3.0 * (b - c) + d - a)));
+    }
+    out
+}
+
 #[inline]
 pub(crate) fn mix<T: Frame>(a: &T, b: &T) -> T {
     bimap(a, b, |a, b| a + b)
@@ -77,3 +95,52 @@ impl<const N: usize> Frame for [Sample; N] {
         self.as_mut()
     }
 }
+
+/// Integer frames which can be converted into frames of floating-point samples
+///
+/// Allows audio data to be stored in a more compact form, e.g. as `Frames<i16>`, and converted
+/// only when it's played by an [`IntoFloatSignal`](crate::IntoFloatSignal). Samples are scaled such
+/// that their full range maps to [-1, 1).
+pub trait IntoFloat: Copy {
+    /// Floating-point frame type, e.g. `[Sample; 2]` for stereo
+    type Frame: Frame + Copy;
+
+    /// Convert to floating point
+    fn into_float(self) -> Self::Frame;
+}
+
+impl IntoFloat for i16 {
+    type Frame = Sample;
+
+    #[inline]
+    fn into_float(self) -> Sample {
+        f32::from(self) / 32768.0
+    }
+}
+
+impl IntoFloat for u8 {
+    type Frame = Sample;
+
+    #[inline]
+    fn into_float(self) -> Sample {
+        (f32::from(self) - 128.0) / 128.0
+    }
+}
+
+impl<const N: usize> IntoFloat for [i16; N] {
+    type Frame = [Sample; N];
+
+    #[inline]
+    fn into_float(self) -> [Sample; N] {
+        self.map(IntoFloat::into_float)
+    }
+}
+
+impl<const N: usize> IntoFloat for [u8; N] {
+    type Frame = [Sample; N];
+
+    #[inline]
+    fn into_float(self) -> [Sample; N] {
+        self.map(IntoFloat::into_float)
+    }
+}
@@ -1,13 +1,14 @@
 use crate::alloc::{alloc, boxed::Box, sync::Arc};
 use core::{
     convert::TryFrom,
+    f32::consts::PI,
     mem,
     ops::{Deref, DerefMut},
     ptr,
-    sync::atomic::{AtomicIsize, Ordering},
+    sync::atomic::{AtomicBool, AtomicIsize, Ordering},
 };
 
-use crate::{frame, math::Float, Frame, Seek, Signal};
+use crate::{frame, math::Float, Frame, IntoFloat, Sample, Seek, Signal};
 
 /// A sequence of static audio frames at a particular sample rate
 ///
@@ -48,6 +49,14 @@ impl<T> Frames<T> {
 
     /// Generate samples from an iterator
     pub fn from_iter<I>(rate: u32, iter: I) -> Arc<Self>
+    where
+        I: IntoIterator<Item = T>,
+        I::IntoIter: ExactSizeIterator,
+    {
+        Self::boxed_from_iter(rate, iter).into()
+    }
+
+    fn boxed_from_iter<I>(rate: u32, iter: I) -> Box<Self>
     where
         I: IntoIterator<Item = T>,
         I::IntoIter: ExactSizeIterator,
@@ -72,10 +81,161 @@ impl<T> Frames<T> {
                 n += 1;
             }
             assert_eq!(n, len, "iterator returned incorrect length");
-            Box::from_raw(ptr::slice_from_raw_parts_mut(mem, len) as *mut Self).into()
+            Box::from_raw(ptr::slice_from_raw_parts_mut(mem, len) as *mut Self)
         }
     }
 
+    /// Interleave separate buffers for each channel, as produced by many decoders
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of buffers differs from the number of channels in `T`, or if the
+    /// buffers differ in length.
+    pub fn from_planar(rate: u32, channels: &[&[Sample]]) -> Arc<Self>
+    where
+        T: Frame,
+    {
+        assert_eq!(
+            channels.len(),
+            T::ZERO.channels().len(),
+            "wrong number of channels"
+        );
+        let len = channels.first().map_or(0, |x| x.len());
+        assert!(
+            channels.iter().all(|x| x.len() == len),
+            "channels differ in length"
+        );
+        Self::from_iter(
+            rate,
+            (0..len).map(|i| {
+                let mut x = T::ZERO;
+                for (out, channel) in x.channels_mut().iter_mut().zip(channels) {
+                    *out = channel[i];
+                }
+                x
+            }),
+        )
+    }
+
+    /// Resample `source` to `rate` using windowed sinc interpolation
+    ///
+    /// Higher quality than the interpolation performed during playback by [`FramesSignal`], and
+    /// avoids repeating that work each time the result is played at `rate`. When lowering the
+    /// sample rate, frequencies above the new Nyquist frequency are filtered out.
+    pub fn resampled(source: &Frames<T>, rate: u32) -> Arc<Self>
+    where
+        T: Frame + Copy,
+    {
+        let ratio = source.rate / f64::from(rate);
+        let len = (source.len() as f64 / ratio).ceil() as usize;
+        // Normalized cutoff frequency, lowered to avoid aliasing when downsampling
+        let cutoff = (1.0 / ratio).min(1.0) as f32;
+        // Half-width of the kernel in source samples
+        let width = SINC_ZERO_CROSSINGS as f32 / cutoff;
+        Self::from_iter(
+            rate,
+            (0..len).map(|i| {
+                let t = i as f64 * ratio;
+                let center = t as isize;
+                let mut out = T::ZERO;
+                let first = center - width as isize;
+                let last = center + width as isize + 1;
+                for j in first..=last {
+                    let x = match usize::try_from(j).ok().and_then(|j| source.get(j)) {
+                        Some(x) => x,
+                        None => continue,
+                    };
+                    let offset = (t - j as f64) as f32;
+                    if offset.abs() >= width {
+                        continue;
+                    }
+                    let window = 0.5 * (1.0 + (PI * offset / width).cos());
+                    let weight = cutoff * sinc(cutoff * offset) * window;
+                    out = frame::mix(&out, &frame::scale(x, weight));
+                }
+                out
+            }),
+        )
+    }
+
+    /// Largest absolute value of any sample in any channel
+    pub fn peak(&self) -> f32
+    where
+        T: Frame,
+    {
+        self.samples
+            .iter()
+            .flat_map(|x| x.channels())
+            .fold(0.0, |acc, x| acc.max(x.abs()))
+    }
+
+    /// Root mean square of all samples in all channels
+    pub fn rms(&self) -> f32
+    where
+        T: Frame,
+    {
+        let (sum, n) = self
+            .samples
+            .iter()
+            .flat_map(|x| x.channels())
+            .fold((0.0, 0usize), |(sum, n), x| (sum + x * x, n + 1));
+        if n == 0 {
+            return 0.0;
+        }
+        (sum / n as f32).sqrt()
+    }
+
+    /// Copy of `self` scaled so that its [`peak`](Self::peak) is `target_peak`
+    ///
+    /// Every channel is scaled by the same factor, preserving their balance. Silence is copied
+    /// unchanged.
+    pub fn normalized(&self, target_peak: f32) -> Arc<Self>
+    where
+        T: Frame + Copy,
+    {
+        let peak = self.peak();
+        let factor = if peak == 0.0 { 1.0 } else { target_peak / peak };
+        Self::from_iter(
+            self.rate(),
+            self.samples.iter().map(|x| frame::scale(x, factor)),
+        )
+    }
+
+    /// Iterate over every frame in order
+    pub fn frames(&self) -> core::slice::Iter<'_, T> {
+        self.samples.iter()
+    }
+
+    /// Iterate over windows of `size` consecutive frames, starting every `hop` frames
+    ///
+    /// Windows overlap when `hop` is less than `size`, as is typical for spectral analysis. Each
+    /// window borrows directly from the underlying frames without copying. Trailing frames that
+    /// don't fill a complete window are skipped.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` or `hop` is zero.
+    pub fn windows(&self, size: usize, hop: usize) -> impl Iterator<Item = &[T]> + '_ {
+        assert!(hop > 0, "hop must be nonzero");
+        self.samples.windows(size).step_by(hop)
+    }
+
+    /// Iterate over the samples of channel `index` of every frame
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is not less than the number of channels in `T`.
+    pub fn channel(&self, index: usize) -> impl Iterator<Item = Sample> + '_
+    where
+        T: Frame,
+    {
+        assert!(
+            index < T::ZERO.channels().len(),
+            "channel index out of bounds"
+        );
+        self.samples.iter().map(move |x| x.channels()[index])
+    }
+
     /// Number of samples per second
     pub fn rate(&self) -> u32 {
         self.rate as u32
@@ -97,32 +257,86 @@ impl<T> Frames<T> {
     {
         let x0 = s as isize;
         let fract = (s - x0 as f64) as f32;
-        let (a, b) = self.get_pair(x0);
+        let (a, b) = self.get_pair(x0, |x| x);
         frame::lerp(&a, &b, fract)
     }
 
+    /// Interpolate a frame for position `s` using a cubic spline
+    ///
+    /// Like [`interpolate`](Self::interpolate), but smoother at a slightly higher cost.
     #[inline]
-    fn get_pair(&self, sample: isize) -> (T, T)
+    pub fn interpolate_cubic(&self, s: f64) -> T
     where
         T: Frame + Copy,
+    {
+        let x0 = s as isize;
+        let fract = (s - x0 as f64) as f32;
+        let (a, b, c, d) = self.get_quad(x0, |x| x);
+        frame::cubic(&a, &b, &c, &d, fract)
+    }
+
+    /// Get the frame at `sample` converted by `load`, treating out-of-range frames as zero
+    #[inline]
+    fn get_frame<F: Frame>(&self, sample: isize, load: impl Fn(T) -> F) -> F
+    where
+        T: Copy,
+    {
+        usize::try_from(sample)
+            .ok()
+            .and_then(|i| self.samples.get(i))
+            .map_or(F::ZERO, |&x| load(x))
+    }
+
+    /// Get the frames at `sample - 1` through `sample + 2`, treating out-of-range frames as zero
+    #[inline]
+    fn get_quad<F: Frame + Copy>(&self, sample: isize, load: impl Fn(T) -> F) -> (F, F, F, F)
+    where
+        T: Copy,
+    {
+        let (b, c) = self.get_pair(sample, &load);
+        (
+            self.get_frame(sample - 1, &load),
+            b,
+            c,
+            self.get_frame(sample + 2, &load),
+        )
+    }
+
+    #[inline]
+    fn get_pair<F: Frame + Copy>(&self, sample: isize, load: impl Fn(T) -> F) -> (F, F)
+    where
+        T: Copy,
     {
         if sample >= 0 {
             let sample = sample as usize;
-            if sample < self.samples.len() - 1 {
-                (self.samples[sample], self.samples[sample + 1])
+            if sample + 1 < self.samples.len() {
+                (load(self.samples[sample]), load(self.samples[sample + 1]))
             } else if sample < self.samples.len() {
-                (self.samples[sample], T::ZERO)
+                (load(self.samples[sample]), F::ZERO)
             } else {
-                (T::ZERO, T::ZERO)
+                (F::ZERO, F::ZERO)
             }
-        } else if sample < -1 {
-            (T::ZERO, T::ZERO)
+        } else if sample < -1 || self.samples.is_empty() {
+            (F::ZERO, F::ZERO)
         } else {
-            (T::ZERO, self.samples[0])
+            (F::ZERO, load(self.samples[0]))
         }
     }
 }
 
+/// Number of zero crossings of the sinc function on either side of the center of the kernel used by
+/// [`Frames::resampled`]
+pub(crate) const SINC_ZERO_CROSSINGS: usize = 16;
+
+/// Normalized sinc function
+pub(crate) fn sinc(x: f32) -> f32 {
+    if x.abs() < 1e-6 {
+        return 1.0;
+    }
+    let x = PI * x;
+    x.sin() / x
+}
+
 impl<T> Deref for Frames<T> {
     type Target = [T];
     fn deref(&self) -> &[T] {
@@ -136,17 +350,75 @@ impl<T> DerefMut for Frames<T> {
     }
 }
 
+#[cfg(feature = "serde")]
+impl<T: serde::Serialize> serde::Serialize for Frames<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+        let mut s = serializer.serialize_struct("Frames", 2)?;
+        s.serialize_field("rate", &self.rate())?;
+        s.serialize_field("samples", &self.samples)?;
+        s.end()
+    }
+}
+
+/// Deserialized as a `Box` because `Arc` is foreign; enable serde's `rc` feature to deserialize
+/// an `Arc<Frames<T>>` directly
+#[cfg(feature = "serde")]
+impl<'de, T: serde::Deserialize<'de>> serde::Deserialize<'de> for Box<Frames<T>> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(serde::Deserialize)]
+        #[serde(rename = "Frames")]
+        struct Raw<T> {
+            rate: u32,
+            samples: ::alloc::vec::Vec<T>,
+        }
+        let raw = Raw::deserialize(deserializer)?;
+        if raw.rate == 0 {
+            return Err(serde::de::Error::invalid_value(
+                serde::de::Unexpected::Unsigned(0),
+                &"a nonzero sample rate",
+            ));
+        }
+        Ok(Frames::boxed_from_iter(raw.rate, raw.samples))
+    }
+}
+
+/// Method used to reconstruct a signal between samples
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Interpolation {
+    /// Straight lines between adjacent samples. Cheap, but introduces audible artifacts when
+    /// heavily pitched down.
+    #[default]
+    Linear,
+    /// Catmull-Rom spline through the four nearest samples
+    Cubic,
+}
+
 /// An audio signal backed by a static sequence of samples
+///
+/// To play integer samples, such as `Frames<i16>`, wrap the signal in an [`IntoFloatSignal`].
 #[derive(Debug)]
 pub struct FramesSignal<T> {
     /// Frames to play
     data: Arc<Frames<T>>,
+    interpolation: Interpolation,
     /// Playback position in seconds
     t: f64,
+    /// Whether playback proceeds from the end of `data` towards the start
+    reverse: bool,
     /// Approximation of t in samples, for reading from the control. We could store t's bits in an
     /// AtomicU64 here, but that would sacrifice portability to platforms that don't have it,
     /// e.g. mips32.
     sample_t: Arc<AtomicIsize>,
+    /// Set once playback passes the end of `data`, and cleared by the control
+    finished: Arc<AtomicBool>,
+    /// Whether `finished` has been set
+    finish_reported: bool,
+    /// Duration of the fade-in applied at the start of playback, in seconds
+    fade: f32,
+    /// Seconds of playback so far, while fading in
+    fade_elapsed: f32,
 }
 
 impl<T> FramesSignal<T> {
@@ -154,62 +426,190 @@ impl<T> FramesSignal<T> {
     ///
     /// `start_seconds` adjusts the initial playback position, and may be negative.
     pub fn new(data: Arc<Frames<T>>, start_seconds: f64) -> (FramesSignalControl, Self) {
+        Self::with_direction(data, start_seconds, false)
+    }
+
+    /// Create an audio signal from some samples that ramps up from silence over `fade_seconds`
+    ///
+    /// Avoids a click when `start_seconds` places the start of playback in the middle of a
+    /// waveform, where the first sample may be far from zero. The fade begins as soon as the signal
+    /// is first sampled.
+    pub fn new_faded(
+        data: Arc<Frames<T>>,
+        start_seconds: f64,
+        fade_seconds: f32,
+    ) -> (FramesSignalControl, Self) {
+        let (control, mut signal) = Self::new(data, start_seconds);
+        signal.fade = fade_seconds;
+        (control, signal)
+    }
+
+    /// Create an audio signal that plays some samples backwards, e.g. for a reversed cymbal
+    ///
+    /// Playback begins at the last sample. `start_seconds` adjusts the initial playback position
+    /// towards the start of `data`, and may be negative to delay playback.
+    ///
+    /// Only static data can be reversed like this. Arbitrary signals, which may be computed on the
+    /// fly, can't be played backwards.
+    pub fn new_reversed(data: Arc<Frames<T>>, start_seconds: f64) -> (FramesSignalControl, Self) {
+        let end = data.len().saturating_sub(1) as f64 / data.rate;
+        Self::with_direction(data, end - start_seconds, true)
+    }
+
+    fn with_direction(data: Arc<Frames<T>>, t: f64, reverse: bool) -> (FramesSignalControl, Self) {
         let samples = data.len();
         let signal = Self {
-            t: start_seconds,
-            sample_t: Arc::new(AtomicIsize::new((start_seconds * data.rate) as isize)),
+            interpolation: Interpolation::Linear,
+            t,
+            reverse,
+            sample_t: Arc::new(AtomicIsize::new((t * data.rate) as isize)),
+            finished: Arc::new(AtomicBool::new(false)),
+            finish_reported: false,
+            fade: 0.0,
+            fade_elapsed: 0.0,
             data,
         };
         let control = FramesSignalControl {
             samples,
             sample_position: signal.sample_t.clone(),
+            finished: signal.finished.clone(),
             rate: signal.data.rate,
+            reverse,
         };
         (control, signal)
     }
-}
 
-impl<T: Frame + Copy> Signal for FramesSignal<T> {
-    type Frame = T;
+    /// Signed number of seconds of `data` traversed per second of playback
+    #[inline]
+    fn direction(&self) -> f32 {
+        if self.reverse {
+            -1.0
+        } else {
+            1.0
+        }
+    }
+
+    /// Reconstruct the signal between samples using `interpolation`
+    ///
+    /// Defaults to [`Interpolation::Linear`].
+    pub fn with_interpolation(mut self, interpolation: Interpolation) -> Self {
+        self.interpolation = interpolation;
+        self
+    }
 
     #[inline]
-    fn sample(&mut self, interval: f32, out: &mut [T]) {
+    fn get<F: Frame + Copy>(&self, sample: isize, fract: f32, load: impl Fn(T) -> F) -> F
+    where
+        T: Copy,
+    {
+        match self.interpolation {
+            Interpolation::Linear => {
+                let (a, b) = self.data.get_pair(sample, load);
+                frame::lerp(&a, &b, fract)
+            }
+            Interpolation::Cubic => {
+                let (a, b, c, d) = self.data.get_quad(sample, load);
+                frame::cubic(&a, &b, &c, &d, fract)
+            }
+        }
+    }
+
+    /// Fill `out`, converting each stored frame with `load` before interpolating
+    #[inline]
+    fn sample_with<F: Frame + Copy>(
+        &mut self,
+        interval: f32,
+        out: &mut [F],
+        load: impl Fn(T) -> F + Copy,
+    ) where
+        T: Copy,
+    {
         let s0 = self.t * self.data.rate;
-        let ds = interval * self.data.rate as f32;
-        let base = s0 as isize;
+        let ds = self.direction() * interval * self.data.rate as f32;
+        let base = s0.floor() as isize;
         if (ds - 1.0).abs() <= f32::EPSILON {
             // This fast-path is important for Spatial::play_buffered where we sample the signal
             // into the Ring with the interval = 1 / rate.
             let fract = (s0 - base as f64) as f32;
             for (i, o) in out.iter_mut().enumerate() {
-                let (a, b) = self.data.get_pair(base + i as isize);
-                *o = frame::lerp(&a, &b, fract);
+                *o = self.get(base + i as isize, fract, load);
             }
         } else {
             let mut offset = (s0 - base as f64) as f32;
             for o in out.iter_mut() {
-                let trunc = unsafe { offset.to_int_unchecked::<isize>() };
-                let (a, b) = self.data.get_pair(base + trunc);
+                let trunc = unsafe { offset.floor().to_int_unchecked::<isize>() };
                 let fract = offset - trunc as f32;
-                *o = frame::lerp(&a, &b, fract);
+                *o = self.get(base + trunc, fract, load);
                 offset += ds;
             }
         }
-        self.t += f64::from(interval) * out.len() as f64;
+        if self.fade_elapsed < self.fade {
+            for o in out.iter_mut() {
+                if self.fade_elapsed >= self.fade {
+                    break;
+                }
+                *o = frame::scale(o, self.fade_elapsed / self.fade);
+                self.fade_elapsed += interval;
+            }
+        }
+        self.t += f64::from(self.direction() * interval) * out.len() as f64;
         self.sample_t
             .store((self.t * self.data.rate) as isize, Ordering::Relaxed);
+        if !self.finish_reported && self.finished_playing() {
+            self.finish_reported = true;
+            self.finished.store(true, Ordering::Relaxed);
+        }
+    }
+
+    #[inline]
+    fn seek_by(&mut self, seconds: f32) {
+        self.t += f64::from(self.direction() * seconds);
+    }
+
+    #[inline]
+    fn finished_playing(&self) -> bool {
+        if self.reverse {
+            // The first frame is still pending when the cursor rests on it
+            self.t < 0.0
+        } else {
+            self.t >= (self.data.samples.len() - 1) as f64 / self.data.rate
+        }
+    }
+
+    #[inline]
+    fn remaining(&self) -> f32 {
+        let remaining = if self.reverse {
+            self.t
+        } else {
+            self.data.samples.len().saturating_sub(1) as f64 / self.data.rate - self.t
+        };
+        remaining.max(0.0) as f32
+    }
+}
+
+impl<T: Frame + Copy> Signal for FramesSignal<T> {
+    type Frame = T;
+
+    #[inline]
+    fn sample(&mut self, interval: f32, out: &mut [T]) {
+        self.sample_with(interval, out, |x| x);
     }
 
     #[inline]
     fn is_finished(&self) -> bool {
-        self.t >= (self.data.samples.len() - 1) as f64 / self.data.rate
+        self.finished_playing()
+    }
+
+    #[inline]
+    fn duration_remaining(&self) -> Option<f32> {
+        Some(self.remaining())
     }
 }
 
 impl<T: Frame + Copy> Seek for FramesSignal<T> {
     #[inline]
     fn seek(&mut self, seconds: f32) {
-        self.t += f64::from(seconds);
+        self.seek_by(seconds);
     }
 }
 
@@ -219,11 +619,69 @@ impl<T> From<Arc<Frames<T>>> for FramesSignal<T> {
     }
 }
 
+/// A [`FramesSignal`] of integer samples, converted to floating point as they're sampled
+///
+/// Allows e.g. 16-bit audio data to be stored compactly as `Frames<i16>`, rather than converted
+/// up front. Interpolation is performed in floating point.
+///
+/// ```
+/// # use oddio::{Frames, FramesSignal, IntoFloatSignal, Signal};
+/// let frames = Frames::from_slice(44100, &[0i16, 16384, -16384]);
+/// let (_control, signal) = FramesSignal::new(frames, 0.0);
+/// let mut signal = IntoFloatSignal::new(signal);
+/// let mut out = [0.0; 3];
+/// signal.sample(1.0 / 44100.0, &mut out);
+/// assert_eq!(out, [0.0, 0.5, -0.5]);
+/// ```
+#[derive(Debug)]
+pub struct IntoFloatSignal<T>(FramesSignal<T>);
+
+impl<T: IntoFloat> IntoFloatSignal<T> {
+    /// Convert the samples of `signal` to floating point as it's played
+    pub fn new(signal: FramesSignal<T>) -> Self {
+        Self(signal)
+    }
+}
+
+impl<T: IntoFloat> Signal for IntoFloatSignal<T> {
+    type Frame = T::Frame;
+
+    #[inline]
+    fn sample(&mut self, interval: f32, out: &mut [T::Frame]) {
+        self.0.sample_with(interval, out, T::into_float);
+    }
+
+    #[inline]
+    fn is_finished(&self) -> bool {
+        self.0.finished_playing()
+    }
+
+    #[inline]
+    fn duration_remaining(&self) -> Option<f32> {
+        Some(self.0.remaining())
+    }
+}
+
+impl<T: IntoFloat> Seek for IntoFloatSignal<T> {
+    #[inline]
+    fn seek(&mut self, seconds: f32) {
+        self.0.seek_by(seconds);
+    }
+}
+
+impl<T: IntoFloat> From<Arc<Frames<T>>> for IntoFloatSignal<T> {
+    fn from(samples: Arc<Frames<T>>) -> Self {
+        Self::new(FramesSignal::from(samples))
+    }
+}
+
 /// Thread-safe control for a [`FramesSignal`], giving access to current playback location.
 pub struct FramesSignalControl {
     samples: usize,
     sample_position: Arc<AtomicIsize>,
+    finished: Arc<AtomicBool>,
     rate: f64,
+    reverse: bool,
 }
 
 impl FramesSignalControl {
@@ -242,8 +700,22 @@ impl FramesSignalControl {
     /// Whether the signal has finished playing
     #[inline]
     pub fn is_finished(&self) -> bool {
-        usize::try_from(self.sample_position.load(Ordering::Relaxed))
-            .map_or(false, |x| x >= self.samples)
+        let position = self.sample_position.load(Ordering::Relaxed);
+        if self.reverse {
+            // Truncation rounds positions within the first frame to 0
+            position < 0
+        } else {
+            usize::try_from(position).is_ok_and(|x| x >= self.samples)
+        }
+    }
+
+    /// Whether playback has passed the end of the signal since the last call
+    ///
+    /// Returns `true` at most once per signal, making it convenient to poll for the completion of
+    /// many one-shot sounds.
+    #[inline]
+    pub fn take_finished(&self) -> bool {
+        self.finished.swap(false, Ordering::Relaxed)
     }
 }
 
@@ -258,6 +730,39 @@ mod tests {
         assert_eq!(&output, expected);
     }
 
+    #[test]
+    fn from_planar() {
+        let planar = Frames::<[Sample; 2]>::from_planar(1, &[&[1.0, 2.0, 3.0], &[4.0, 5.0, 6.0]]);
+        let interleaved = Frames::from_slice(1, &[[1.0, 4.0], [2.0, 5.0], [3.0, 6.0]]);
+        assert_eq!(planar[..], interleaved[..]);
+    }
+
+    #[test]
+    fn analysis() {
+        let frames = Frames::from_slice(1, &[[1.0, -1.0], [2.0, -2.0], [3.0, -3.0], [4.0, -4.0]]);
+        assert_eq!(frames.frames().count(), 4);
+        assert!(frames.channel(1).eq([-1.0, -2.0, -3.0, -4.0]));
+        assert_eq!(frames.windows(2, 1).count(), 3);
+        assert_eq!(
+            frames.windows(2, 1).last().unwrap(),
+            [[3.0, -3.0], [4.0, -4.0]]
+        );
+        assert_eq!(frames.windows(3, 2).count(), 1);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde_round_trip() {
+        let frames = Frames::from_slice(44100, &[[1.0, -1.0], [0.5, -0.5]]);
+        let json = serde_json::to_string(&*frames).unwrap();
+        assert_eq!(json, r#"{"rate":44100,"samples":[[1.0,-1.0],[0.5,-0.5]]}"#);
+        let decoded = serde_json::from_str::<Box<Frames<[Sample; 2]>>>(&json).unwrap();
+        assert_eq!(decoded.rate(), 44100);
+        assert_eq!(decoded[..], frames[..]);
+
+        assert!(serde_json::from_str::<Box<Frames<Sample>>>(r#"{"rate":0,"samples":[]}"#).is_err());
+    }
+
     #[test]
     fn from_slice() {
         const DATA: &[f32] = &[1.0, 2.0, 3.0];
@@ -274,6 +779,161 @@ mod tests {
         assert_out(&mut signal, 1.0, &[1.5, 2.5, 3.5, 2.0, 0.0]);
     }
 
+    #[test]
+    fn duration_remaining() {
+        let frames = Frames::from_slice(2, &[1.0, 2.0, 3.0, 4.0, 5.0]);
+        let (_, mut signal) = FramesSignal::new(frames.clone(), -1.0);
+        assert_eq!(signal.duration_remaining(), Some(3.0));
+        signal.sample(0.5, &mut [0.0; 4]);
+        assert_eq!(signal.duration_remaining(), Some(1.0));
+        signal.sample(0.5, &mut [0.0; 4]);
+        assert_eq!(signal.duration_remaining(), Some(0.0));
+        assert!(signal.is_finished());
+
+        let (_, signal) = FramesSignal::new_reversed(frames, 1.5);
+        assert_eq!(signal.duration_remaining(), Some(0.5));
+    }
+
+    #[test]
+    fn resampled() {
+        use core::f32::consts::TAU;
+
+        const FREQUENCY: f32 = 1000.0;
+        let sine = |rate: u32, i: usize| (i as f32 * TAU * FREQUENCY / rate as f32).sin();
+        let source = Frames::from_iter(8000, (0..8000).map(|i| sine(8000, i)));
+        for &rate in &[11025, 6000] {
+            let resampled = Frames::resampled(&source, rate);
+            assert_eq!(resampled.rate(), rate);
+            assert_eq!(resampled.len(), rate as usize);
+            // Skip the edges, where the kernel runs off the end of the data
+            let margin = rate as usize / 10;
+            for i in margin..resampled.len() - margin {
+                let expected = sine(rate, i);
+                assert!(
+                    (resampled[i] - expected).abs() < 1e-2,
+                    "{} Hz sample {}: {} vs. {}",
+                    rate,
+                    i,
+                    resampled[i],
+                    expected
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn normalized() {
+        let frames = Frames::from_slice(1, &[[0.5, -0.25], [0.0, 0.25]]);
+        assert_eq!(frames.peak(), 0.5);
+        assert_eq!(frames.rms(), (0.375f32 / 4.0).sqrt());
+        let normalized = frames.normalized(1.0);
+        assert_eq!(normalized.rate(), 1);
+        assert_eq!(&normalized[..], &[[1.0, -0.5], [0.0, 0.5]]);
+
+        let silence = Frames::from_slice(1, &[0.0; 4]).normalized(1.0);
+        assert_eq!(&silence[..], &[0.0; 4]);
+    }
+
+    #[test]
+    fn faded() {
+        let frames = Frames::from_slice(1, &[1.0; 8]);
+        let (_, mut signal) = FramesSignal::new_faded(frames, 2.0, 4.0);
+        assert_out(&mut signal, 1.0, &[0.0, 0.25]);
+        assert_out(&mut signal, 1.0, &[0.5, 0.75, 1.0]);
+    }
+
+    #[test]
+    fn integer_frames() {
+        let frames = Frames::from_slice(1, &[[i16::MIN, 0], [0, 16384]]);
+        let mut signal = IntoFloatSignal::from(frames);
+        let mut out = [[0.0; 2]; 3];
+        signal.sample(0.5, &mut out);
+        assert_eq!(out, [[-1.0, 0.0], [-0.5, 0.25], [0.0, 0.5]]);
+
+        let frames = Frames::from_slice(1, &[0u8, 128, 192]);
+        let mut signal = IntoFloatSignal::from(frames);
+        let mut out = [0.0; 3];
+        signal.sample(1.0, &mut out);
+        assert_eq!(out, [-1.0, 0.0, 0.5]);
+    }
+
+    #[test]
+    fn cubic_interpolation() {
+        use core::f32::consts::TAU;
+
+        const RATE: u32 = 8;
+        let frames = Frames::from_iter(
+            RATE,
+            (0..4 * RATE).map(|i| (i as f32 * TAU / RATE as f32).sin()),
+        );
+        let error = |interpolation| {
+            let (_, signal) = FramesSignal::new(frames.clone(), 1.0);
+            let mut signal = signal.with_interpolation(interpolation);
+            let mut out = [0.0; 64];
+            signal.sample(1.0 / (4 * RATE) as f32, &mut out);
+            out.iter()
+                .enumerate()
+                .map(|(i, &x)| {
+                    let expected = (TAU * (1.0 + i as f32 / (4 * RATE) as f32)).sin();
+                    (x - expected).abs()
+                })
+                .fold(0.0f32, f32::max)
+        };
+        let linear = error(Interpolation::Linear);
+        let cubic = error(Interpolation::Cubic);
+        assert!(
+            cubic < linear / 2.0,
+            "cubic {} vs. linear {}",
+            cubic,
+            linear
+        );
+    }
+
+    #[test]
+    fn reversed() {
+        let (control, mut signal) =
+            FramesSignal::new_reversed(Frames::from_slice(1, &[1.0, 2.0, 3.0, 4.0]), -1.0);
+        assert_eq!(control.playback_position(), 4.0);
+
+        assert_out(&mut signal, 0.5, &[0.0, 2.0, 4.0, 3.5]);
+        assert!(!signal.is_finished());
+        assert_out(&mut signal, 1.0, &[3.0, 2.0, 1.0]);
+        assert!(signal.is_finished());
+        assert!(control.is_finished());
+        assert_out(&mut signal, 1.0, &[0.0, 0.0]);
+
+        signal.seek(-4.5);
+        assert_out(&mut signal, 0.25, &[2.5, 2.25]);
+    }
+
+    #[test]
+    fn reversed_finish() {
+        let (control, mut signal) =
+            FramesSignal::new_reversed(Frames::from_slice(1, &[1.0, 2.0]), 0.0);
+        assert_out(&mut signal, 0.5, &[2.0]);
+        assert!(!signal.is_finished());
+        assert!(!control.is_finished());
+        assert_out(&mut signal, 0.5, &[1.5]);
+        assert!(!signal.is_finished());
+        assert!(!control.is_finished());
+        assert_out(&mut signal, 0.5, &[1.0, 0.5]);
+        assert!(signal.is_finished());
+        assert!(control.is_finished());
+    }
+
+    #[test]
+    fn take_finished() {
+        let (control, mut signal) = FramesSignal::new(Frames::from_slice(1, &[1.0, 2.0]), 0.0);
+        let mut buf = [0.0; 1];
+        signal.sample(0.5, &mut buf);
+        assert!(!control.take_finished());
+        signal.sample(0.5, &mut buf);
+        assert!(control.take_finished());
+        assert!(!control.take_finished());
+        signal.sample(0.5, &mut buf);
+        assert!(!control.take_finished());
+    }
+
     #[test]
     fn playback_position() {
         let (control, mut signal) =
@@ -1,11 +1,11 @@
 use alloc::sync::Arc;
 use core::sync::atomic::{AtomicU32, Ordering};
 
-use crate::{frame, math::Float, Frame, Seek, Signal, Smoothed};
+use crate::{frame, math::Float, Frame, Reset, Seek, Signal, Smoothed};
 
 /// Amplifies a signal by a constant amount
 ///
-/// Unlike [`Gain`], this can implement [`Seek`].
+/// Cheaper than [`Gain`] when the amplification never needs to change.
 pub struct FixedGain<T: ?Sized> {
     gain: f32,
     inner: T,
@@ -39,6 +39,10 @@ where
     fn is_finished(&self) -> bool {
         self.inner.is_finished()
     }
+
+    fn duration_remaining(&self) -> Option<f32> {
+        self.inner.duration_remaining()
+    }
 }
 
 impl<T: Seek + ?Sized> Seek for FixedGain<T>
@@ -50,14 +54,25 @@ where
     }
 }
 
+impl<T: Reset + ?Sized> Reset for FixedGain<T>
+where
+    T::Frame: Frame,
+{
+    fn reset(&mut self) {
+        self.inner.reset()
+    }
+}
+
 /// Amplifies a signal dynamically
 ///
 /// To implement a volume control, place a gain combinator near the end of your pipeline where the
 /// input amplitude is initially in the range [0, 1] and pass decibels to [`GainControl::set_gain`],
 /// mapping the maximum volume to 0 decibels, and the minimum to e.g. -60.
 pub struct Gain<T: ?Sized> {
-    shared: Arc<AtomicU32>,
+    shared: Arc<Shared>,
     gain: Smoothed<f32>,
+    /// Seconds over which the change in progress takes place
+    fade: f32,
     inner: T,
 }
 
@@ -65,8 +80,13 @@ impl<T> Gain<T> {
     /// Apply dynamic amplification to `signal`
     pub fn new(signal: T) -> (GainControl, Self) {
         let signal = Gain {
-            shared: Arc::new(AtomicU32::new(1.0f32.to_bits())),
+            shared: Arc::new(Shared {
+                gain: AtomicU32::new(1.0f32.to_bits()),
+                smoothing: AtomicU32::new(DEFAULT_SMOOTHING_PERIOD.to_bits()),
+                fade: AtomicU32::new((-1.0f32).to_bits()),
+            }),
             gain: Smoothed::new(1.0),
+            fade: 0.0,
             inner: signal,
         };
         let handle = GainControl(signal.shared.clone());
@@ -88,9 +108,18 @@ impl<T> Gain<T> {
     /// `set_gain`. Unlike `set_gain`, this method allows a signal to be completely zeroed out if
     /// needed, or even have its phase inverted with a negative factor.
     pub fn set_amplitude_ratio(&mut self, factor: f32) {
-        self.shared.store(factor.to_bits(), Ordering::Relaxed);
+        self.shared.gain.store(factor.to_bits(), Ordering::Relaxed);
         self.gain = Smoothed::new(factor);
     }
+
+    /// Set the initial number of seconds over which changes in gain are smoothed
+    ///
+    /// Defaults to 0.1. See [`GainControl::set_smoothing`].
+    pub fn set_smoothing(&mut self, seconds: f32) {
+        self.shared
+            .smoothing
+            .store(seconds.to_bits(), Ordering::Relaxed);
+    }
 }
 
 impl<T: Signal> Signal for Gain<T>
@@ -102,9 +131,17 @@ where
     #[allow(clippy::float_cmp)]
     fn sample(&mut self, interval: f32, out: &mut [T::Frame]) {
         self.inner.sample(interval, out);
-        let shared = f32::from_bits(self.shared.load(Ordering::Relaxed));
+        // Synchronizes with the control, so `fade` is at least as new as the gain
+        let shared = f32::from_bits(self.shared.gain.load(Ordering::Acquire));
         if self.gain.target() != &shared {
             self.gain.set(shared);
+            self.fade = f32::from_bits(self.shared.fade.load(Ordering::Relaxed));
+            if self.fade < 0.0 {
+                self.fade = f32::from_bits(self.shared.smoothing.load(Ordering::Relaxed));
+            }
+            if self.fade <= 0.0 {
+                self.gain.advance(1.0);
+            }
         }
         if self.gain.progress() == 1.0 {
             let g = self.gain.get();
@@ -117,17 +154,43 @@ where
         }
         for x in out {
             *x = frame::scale(x, self.gain.get());
-            self.gain.advance(interval / SMOOTHING_PERIOD);
+            self.gain.advance(interval / self.fade);
         }
     }
 
     fn is_finished(&self) -> bool {
         self.inner.is_finished()
     }
+
+    fn duration_remaining(&self) -> Option<f32> {
+        self.inner.duration_remaining()
+    }
+}
+
+/// Gain is independent of time, so the smoothing state is left untouched by seeking.
+impl<T: Seek> Seek for Gain<T>
+where
+    T::Frame: Frame,
+{
+    fn seek(&mut self, seconds: f32) {
+        self.inner.seek(seconds)
+    }
+}
+
+/// Any change in progress completes immediately.
+impl<T: Reset> Reset for Gain<T>
+where
+    T::Frame: Frame,
+{
+    fn reset(&mut self) {
+        let target = f32::from_bits(self.shared.gain.load(Ordering::Relaxed));
+        self.gain = Smoothed::new(target);
+        self.inner.reset()
+    }
 }
 
 /// Thread-safe control for a [`Gain`] filter
-pub struct GainControl(Arc<AtomicU32>);
+pub struct GainControl(Arc<Shared>);
 
 impl GainControl {
     /// Get the current amplification in decibels
@@ -146,7 +209,7 @@ impl GainControl {
 
     /// Get the current amplitude scaling factor
     pub fn amplitude_ratio(&self) -> f32 {
-        f32::from_bits(self.0.load(Ordering::Relaxed))
+        f32::from_bits(self.0.gain.load(Ordering::Relaxed))
     }
 
     /// Scale the amplitude of the signal directly
@@ -155,17 +218,70 @@ impl GainControl {
     /// `set_gain`. Unlike `set_gain`, this method allows a signal to be completely zeroed out if
     /// needed, or even have its phase inverted with a negative factor.
     pub fn set_amplitude_ratio(&mut self, factor: f32) {
-        self.0.store(factor.to_bits(), Ordering::Relaxed);
+        self.fade_amplitude_ratio_to(factor, -1.0);
     }
+
+    /// Ramp the amplification to `db` decibels over `seconds`, rather than the smoothing period
+    ///
+    /// Suited to scripted fades. A new change issued while a fade is in progress continues from
+    /// the current amplification. To fade a signal out and then stop it, see
+    /// [`Mixed::stop_with_fade`](crate::Mixed::stop_with_fade).
+    pub fn fade_to(&mut self, db: f32, seconds: f32) {
+        self.fade_amplitude_ratio_to(10.0f32.powf(db / 20.0), seconds.max(0.0));
+    }
+
+    /// Like [`fade_to`](Self::fade_to), but for an amplitude scaling factor
+    ///
+    /// See [`set_amplitude_ratio`](Self::set_amplitude_ratio).
+    pub fn fade_amplitude_ratio_to(&mut self, factor: f32, seconds: f32) {
+        // Store the duration first and publish it with the gain, so it's in place when the audio
+        // thread notices the new gain
+        self.0.fade.store(seconds.to_bits(), Ordering::Relaxed);
+        self.0.gain.store(factor.to_bits(), Ordering::Release);
+    }
+
+    /// Get the number of seconds over which changes in gain are smoothed
+    pub fn smoothing(&self) -> f32 {
+        f32::from_bits(self.0.smoothing.load(Ordering::Relaxed))
+    }
+
+    /// Set the number of seconds over which changes in gain are smoothed
+    ///
+    /// Defaults to 0.1, which avoids audible artifacts when e.g. a volume slider is dragged.
+    /// Shorter periods allow faster modulation, such as a tremolo effect. Zero disables smoothing
+    /// entirely.
+    pub fn set_smoothing(&mut self, seconds: f32) {
+        self.0.smoothing.store(seconds.to_bits(), Ordering::Relaxed);
+    }
+}
+
+struct Shared {
+    gain: AtomicU32,
+    /// Seconds over which to smooth a change in gain
+    smoothing: AtomicU32,
+    /// Seconds over which to make the most recent change in gain, or negative to use `smoothing`
+    fade: AtomicU32,
 }
 
-/// Number of seconds over which to smooth a change in gain
-const SMOOTHING_PERIOD: f32 = 0.1;
+/// Default number of seconds over which to smooth a change in gain
+const DEFAULT_SMOOTHING_PERIOD: f32 = 0.1;
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::Constant;
+    use crate::{Constant, Frames, FramesSignal};
+
+    #[test]
+    fn duration_remaining() {
+        let frames = Frames::from_slice(10, &[0.0; 8]);
+        let (_, mut s) = Gain::new(FramesSignal::from(frames.clone()));
+        let mut bare = FramesSignal::from(frames);
+        let mut buf = [0.0; 3];
+        s.sample(0.1, &mut buf);
+        bare.sample(0.1, &mut buf);
+        assert_eq!(s.duration_remaining(), bare.duration_remaining());
+        assert!(s.duration_remaining().is_some());
+    }
 
     #[test]
     fn smoothing() {
@@ -177,4 +293,45 @@ mod tests {
         s.sample(0.025, &mut buf);
         assert_eq!(buf, [5.0; 6]);
     }
+
+    #[test]
+    fn custom_smoothing() {
+        let (mut c, mut s) = Gain::new(Constant(1.0));
+        let mut buf = [0.0; 3];
+        c.set_smoothing(0.02);
+        c.set_amplitude_ratio(5.0);
+        s.sample(0.01, &mut buf);
+        assert_eq!(buf, [1.0, 3.0, 5.0]);
+
+        c.set_smoothing(0.0);
+        c.set_amplitude_ratio(2.0);
+        s.sample(0.01, &mut buf);
+        assert_eq!(buf, [2.0; 3]);
+    }
+
+    #[test]
+    fn fade_to() {
+        let (mut c, mut s) = Gain::new(Constant(1.0));
+        let mut buf = [0.0; 2];
+        c.fade_amplitude_ratio_to(5.0, 1.0);
+        s.sample(0.25, &mut buf);
+        assert_eq!(buf, [1.0, 2.0]);
+
+        // Retarget from the current value
+        c.fade_amplitude_ratio_to(1.0, 0.5);
+        s.sample(0.25, &mut buf);
+        assert_eq!(buf, [3.0, 2.0]);
+        s.sample(0.25, &mut buf);
+        assert_eq!(buf, [1.0; 2]);
+    }
+
+    #[test]
+    fn seek() {
+        let (_, mut s) = Gain::new(FramesSignal::from(Frames::from_slice(1, &[1.0, 2.0, 3.0])));
+        s.set_amplitude_ratio(2.0);
+        s.seek(1.0);
+        let mut buf = [0.0; 2];
+        s.sample(1.0, &mut buf);
+        assert_eq!(buf, [4.0, 6.0]);
+    }
 }
@@ -0,0 +1,279 @@
+use alloc::sync::Arc;
+use core::sync::atomic::{AtomicU32, Ordering};
+
+use crate::{math::Float, Frame, Reset, Seek, Signal};
+
+/// Silences a signal while its level stays below a threshold
+///
+/// Useful for suppressing background noise between useful sounds, e.g. in microphone input. The
+/// gate opens as soon as the signal's peak level exceeds the threshold, and closes once the level
+/// has remained below the threshold, less hysteresis, for the hold time.
+pub struct Gate<T: ?Sized> {
+    shared: Arc<Shared>,
+    /// Peak level of the input
+    envelope: f32,
+    /// Whether the gate is open
+    open: bool,
+    /// Seconds the level has been below the closing threshold
+    quiet: f32,
+    gain: f32,
+    inner: T,
+}
+
+impl<T> Gate<T> {
+    /// Apply a noise gate to `signal`, initially closed
+    pub fn new(signal: T, options: GateOptions) -> (GateControl, Self) {
+        let shared = Arc::new(Shared {
+            threshold: AtomicU32::new(options.threshold.to_bits()),
+            hysteresis: AtomicU32::new(options.hysteresis.clamp(0.0, 1.0).to_bits()),
+            attack: AtomicU32::new(options.attack.to_bits()),
+            hold: AtomicU32::new(options.hold.to_bits()),
+            release: AtomicU32::new(options.release.to_bits()),
+        });
+        let signal = Self {
+            shared: shared.clone(),
+            envelope: 0.0,
+            open: false,
+            quiet: 0.0,
+            gain: 0.0,
+            inner: signal,
+        };
+        (GateControl(shared), signal)
+    }
+}
+
+/// Configuration for a [`Gate`], passed to [`Gate::new`]
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GateOptions {
+    /// Peak level above which the gate opens
+    pub threshold: f32,
+    /// Fraction of `threshold` below which the level must fall for the gate to close, from 0 to 1
+    ///
+    /// Smaller values prevent the gate from rapidly opening and closing ("chattering") when the
+    /// level hovers around the threshold.
+    pub hysteresis: f32,
+    /// Time constant in seconds with which the gate opens
+    pub attack: f32,
+    /// Seconds the level must remain low before the gate begins to close
+    pub hold: f32,
+    /// Time constant in seconds with which the gate closes
+    pub release: f32,
+}
+
+impl Default for GateOptions {
+    fn default() -> Self {
+        Self {
+            threshold: 0.05,
+            hysteresis: 0.5,
+            attack: 0.001,
+            hold: 0.05,
+            release: 0.05,
+        }
+    }
+}
+
+impl<T: Signal> Signal for Gate<T>
+where
+    T::Frame: Frame,
+{
+    type Frame = T::Frame;
+
+    fn sample(&mut self, interval: f32, out: &mut [T::Frame]) {
+        self.inner.sample(interval, out);
+        let threshold = load(&self.shared.threshold);
+        let close_threshold = threshold * load(&self.shared.hysteresis);
+        let hold = load(&self.shared.hold);
+        let attack = 1.0 - (-interval / load(&self.shared.attack)).exp();
+        let release = 1.0 - (-interval / load(&self.shared.release)).exp();
+        let decay = (-interval / ENVELOPE_TAU).exp();
+        for x in out {
+            let peak = x.channels().iter().fold(0.0f32, |acc, s| acc.max(s.abs()));
+            self.envelope = peak.max(self.envelope * decay);
+            if self.envelope > threshold {
+                self.open = true;
+            }
+            if self.envelope < close_threshold {
+                self.quiet += interval;
+                if self.quiet >= hold {
+                    self.open = false;
+                }
+            } else {
+                self.quiet = 0.0;
+            }
+            if self.open {
+                self.gain += (1.0 - self.gain) * attack;
+            } else {
+                self.gain -= self.gain * release;
+            }
+            for s in x.channels_mut() {
+                *s *= self.gain;
+            }
+        }
+    }
+
+    fn is_finished(&self) -> bool {
+        self.inner.is_finished()
+    }
+
+    fn duration_remaining(&self) -> Option<f32> {
+        self.inner.duration_remaining()
+    }
+}
+
+impl<T: Seek> Seek for Gate<T>
+where
+    T::Frame: Frame,
+{
+    fn seek(&mut self, seconds: f32) {
+        self.inner.seek(seconds);
+    }
+}
+
+impl<T: Reset> Reset for Gate<T>
+where
+    T::Frame: Frame,
+{
+    fn reset(&mut self) {
+        self.envelope = 0.0;
+        self.open = false;
+        self.quiet = 0.0;
+        self.gain = 0.0;
+        self.inner.reset();
+    }
+}
+
+/// Thread-safe control for a [`Gate`]
+pub struct GateControl(Arc<Shared>);
+
+impl GateControl {
+    /// Get the peak level above which the gate opens
+    pub fn threshold(&self) -> f32 {
+        load(&self.0.threshold)
+    }
+
+    /// Set the peak level above which the gate opens
+    pub fn set_threshold(&mut self, threshold: f32) {
+        self.0
+            .threshold
+            .store(threshold.to_bits(), Ordering::Relaxed);
+    }
+
+    /// Get the fraction of the threshold below which the level must fall for the gate to close
+    pub fn hysteresis(&self) -> f32 {
+        load(&self.0.hysteresis)
+    }
+
+    /// Set the fraction of the threshold below which the level must fall for the gate to close,
+    /// clamped between 0 and 1
+    pub fn set_hysteresis(&mut self, hysteresis: f32) {
+        self.0
+            .hysteresis
+            .store(hysteresis.clamp(0.0, 1.0).to_bits(), Ordering::Relaxed);
+    }
+
+    /// Get the time constant in seconds with which the gate opens
+    pub fn attack(&self) -> f32 {
+        load(&self.0.attack)
+    }
+
+    /// Set the time constant in seconds with which the gate opens
+    pub fn set_attack(&mut self, seconds: f32) {
+        self.0.attack.store(seconds.to_bits(), Ordering::Relaxed);
+    }
+
+    /// Get the seconds the level must remain low before the gate begins to close
+    pub fn hold(&self) -> f32 {
+        load(&self.0.hold)
+    }
+
+    /// Set the seconds the level must remain low before the gate begins to close
+    pub fn set_hold(&mut self, seconds: f32) {
+        self.0.hold.store(seconds.to_bits(), Ordering::Relaxed);
+    }
+
+    /// Get the time constant in seconds with which the gate closes
+    pub fn release(&self) -> f32 {
+        load(&self.0.release)
+    }
+
+    /// Set the time constant in seconds with which the gate closes
+    pub fn set_release(&mut self, seconds: f32) {
+        self.0.release.store(seconds.to_bits(), Ordering::Relaxed);
+    }
+}
+
+struct Shared {
+    threshold: AtomicU32,
+    hysteresis: AtomicU32,
+    attack: AtomicU32,
+    hold: AtomicU32,
+    release: AtomicU32,
+}
+
+fn load(x: &AtomicU32) -> f32 {
+    f32::from_bits(x.load(Ordering::Relaxed))
+}
+
+/// Time constant in seconds with which the envelope follower falls
+const ENVELOPE_TAU: f32 = 0.01;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Frames, FramesSignal};
+    use alloc::vec::Vec;
+    use core::f32::consts::TAU;
+
+    const RATE: u32 = 8000;
+
+    fn peak(out: &[f32]) -> f32 {
+        out.iter().fold(0.0f32, |acc, x| acc.max(x.abs()))
+    }
+
+    #[test]
+    fn burst() {
+        // A burst of tone between stretches of quiet noise
+        let input = (0..4000)
+            .map(|i| {
+                let amplitude = if (800..1600).contains(&i) { 1.0 } else { 0.01 };
+                amplitude * (i as f32 * 440.0 * TAU / RATE as f32).sin()
+            })
+            .collect::<Vec<_>>();
+        let (_, mut gate) = Gate::new(
+            FramesSignal::from(Frames::from_slice(RATE, &input)),
+            GateOptions::default(),
+        );
+        let mut out = [0.0; 800];
+
+        gate.sample(1.0 / RATE as f32, &mut out);
+        assert!(peak(&out) < 1e-3, "closed below threshold");
+
+        gate.sample(1.0 / RATE as f32, &mut out);
+        assert!(peak(&out[400..]) > 0.99, "opens above threshold");
+
+        gate.sample(1.0 / RATE as f32, &mut out);
+        assert!(peak(&out[..80]) > 0.009, "held open");
+        gate.sample(1.0 / RATE as f32, &mut out);
+        gate.sample(1.0 / RATE as f32, &mut out);
+        assert!(peak(&out[400..]) < 1e-3, "closes after hold");
+    }
+
+    #[test]
+    fn hysteresis() {
+        let (mut c, mut gate) = Gate::new(crate::Constant(0.04), GateOptions::default());
+        assert_eq!(c.hysteresis(), 0.5);
+        c.set_threshold(0.03);
+        let mut out = [0.0; 100];
+        gate.sample(1.0 / RATE as f32, &mut out);
+        assert!(out[99] > 0.03, "opens above threshold");
+        // Without hysteresis, the level is below the closing threshold, so the gate closes
+        c.set_hysteresis(2.0);
+        assert_eq!(c.hysteresis(), 1.0);
+        c.set_threshold(0.05);
+        for _ in 0..20 {
+            gate.sample(1.0 / RATE as f32, &mut out);
+        }
+        assert!(out[99] < 1e-3, "closes");
+    }
+}
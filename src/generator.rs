@@ -0,0 +1,91 @@
+use core::marker::PhantomData;
+
+use crate::Signal;
+
+/// A signal whose frames are produced by a closure
+///
+/// Handy for prototyping procedural audio and for tests, where a full [`Signal`] implementation
+/// would be boilerplate. The closure receives the same arguments as [`Signal::sample`]. It runs on
+/// the audio thread, so it must not wait, allocate, or free memory, and it must be `Send` for the
+/// generator to be played by e.g. a [`Mixer`](crate::Mixer).
+///
+/// ```
+/// # use oddio::{Generator, Signal};
+/// let mut t = 0.0;
+/// let mut ramp = Generator::new(move |interval: f32, out: &mut [f32]| {
+///     for x in out {
+///         *x = t;
+///         t += interval;
+///     }
+/// });
+/// let mut out = [0.0; 3];
+/// ramp.sample(0.5, &mut out);
+/// assert_eq!(out, [0.0, 0.5, 1.0]);
+/// ```
+pub struct Generator<T, F, G = fn() -> bool> {
+    generate: F,
+    is_finished: G,
+    _frame: PhantomData<fn() -> T>,
+}
+
+impl<T, F: FnMut(f32, &mut [T])> Generator<T, F> {
+    /// Construct a signal that fills each block of output by calling `generate`, forever
+    pub fn new(generate: F) -> Self {
+        Self {
+            generate,
+            is_finished: || false,
+            _frame: PhantomData,
+        }
+    }
+}
+
+impl<T, F: FnMut(f32, &mut [T]), G> Generator<T, F, G> {
+    /// Report the signal as finished whenever `is_finished` returns true
+    ///
+    /// Typically `is_finished` reads state shared with the generating closure, e.g. through an
+    /// atomic.
+    pub fn with_is_finished<H: Fn() -> bool>(self, is_finished: H) -> Generator<T, F, H> {
+        Generator {
+            generate: self.generate,
+            is_finished,
+            _frame: PhantomData,
+        }
+    }
+}
+
+impl<T, F: FnMut(f32, &mut [T]), G: Fn() -> bool> Signal for Generator<T, F, G> {
+    type Frame = T;
+
+    fn sample(&mut self, interval: f32, out: &mut [T]) {
+        (self.generate)(interval, out);
+    }
+
+    fn is_finished(&self) -> bool {
+        (self.is_finished)()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::sync::Arc;
+    use core::sync::atomic::{AtomicBool, Ordering};
+
+    #[test]
+    fn finish() {
+        let done = Arc::new(AtomicBool::new(false));
+        let mut signal = Generator::new({
+            let done = done.clone();
+            move |_, out: &mut [f32]| {
+                out.fill(1.0);
+                done.store(true, Ordering::Relaxed);
+            }
+        })
+        .with_is_finished(move || done.load(Ordering::Relaxed));
+        assert!(!signal.is_finished());
+        let mut out = [0.0; 2];
+        signal.sample(1.0, &mut out);
+        assert_eq!(out, [1.0; 2]);
+        assert!(signal.is_finished());
+    }
+}
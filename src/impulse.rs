@@ -0,0 +1,134 @@
+use core::f32::consts::PI;
+
+use crate::{
+    frames::{sinc, SINC_ZERO_CROSSINGS},
+    math::Float,
+    Sample, Seek, Signal,
+};
+
+/// A single click followed by silence, for measuring impulse responses
+///
+/// Feed through a filter, reverb, or spatial scene to observe its response to a unit impulse.
+pub struct Impulse {
+    /// Seconds since the start of the signal
+    t: f64,
+    /// Time of the impulse
+    time: f64,
+    amplitude: f32,
+    /// Seconds after the impulse to continue reporting the signal as unfinished
+    settle: f32,
+    band_limited: bool,
+    /// Most recent sampling interval, which determines the length of a band-limited pulse
+    interval: f32,
+}
+
+impl Impulse {
+    /// Construct a signal that emits a unit impulse at `time` seconds, and is otherwise silent
+    ///
+    /// The impulse falls on the first frame at or after `time`.
+    pub fn new(time: f32) -> Self {
+        Self {
+            t: 0.0,
+            time: time.into(),
+            amplitude: 1.0,
+            settle: 0.0,
+            band_limited: false,
+            interval: 0.0,
+        }
+    }
+
+    /// Scale the impulse by `amplitude`
+    pub fn with_amplitude(mut self, amplitude: f32) -> Self {
+        self.amplitude = amplitude;
+        self
+    }
+
+    /// Continue reporting the signal as unfinished until `seconds` after the impulse
+    ///
+    /// Allows e.g. a [`Mixer`](crate::Mixer) to capture the tail of a reverb the impulse is fed
+    /// into before discarding it.
+    pub fn with_settle_time(mut self, seconds: f32) -> Self {
+        self.settle = seconds;
+        self
+    }
+
+    /// Emit a windowed sinc pulse centered exactly on the impulse time, rather than a single frame
+    ///
+    /// Contains no frequencies above the Nyquist frequency of the output, so it's free of aliasing
+    /// and represents impulses that fall between frames precisely, at the cost of ringing spanning
+    /// 16 frames either side of the impulse.
+    pub fn band_limited(mut self) -> Self {
+        self.band_limited = true;
+        self
+    }
+
+    /// Seconds after `time` at which output ceases
+    fn tail(&self) -> f64 {
+        if self.band_limited {
+            f64::from(self.interval) * SINC_ZERO_CROSSINGS as f64
+        } else {
+            0.0
+        }
+    }
+}
+
+impl Signal for Impulse {
+    type Frame = Sample;
+
+    fn sample(&mut self, interval: f32, out: &mut [Sample]) {
+        self.interval = interval;
+        let width = SINC_ZERO_CROSSINGS as f32;
+        for (i, x) in out.iter_mut().enumerate() {
+            let t = self.t + f64::from(interval) * i as f64;
+            // Frames from the frame at `t` to the impulse
+            let offset = ((t - self.time) / f64::from(interval)) as f32;
+            *x = if self.band_limited {
+                if offset.abs() < width {
+                    let window = 0.5 * (1.0 + (PI * offset / width).cos());
+                    self.amplitude * sinc(offset) * window
+                } else {
+                    0.0
+                }
+            } else if (0.0..1.0).contains(&offset) {
+                self.amplitude
+            } else {
+                0.0
+            };
+        }
+        self.t += f64::from(interval) * out.len() as f64;
+    }
+
+    fn is_finished(&self) -> bool {
+        self.t > self.time + self.tail() + f64::from(self.settle)
+    }
+
+    fn duration_remaining(&self) -> Option<f32> {
+        Some((self.time + self.tail() + f64::from(self.settle) - self.t).max(0.0) as f32)
+    }
+}
+
+impl Seek for Impulse {
+    fn seek(&mut self, seconds: f32) {
+        self.t += f64::from(seconds);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn impulse() {
+        let mut signal = Impulse::new(1.5).with_amplitude(0.5);
+        let mut out = [0.0; 4];
+        signal.sample(1.0, &mut out);
+        assert_eq!(out, [0.0, 0.0, 0.5, 0.0]);
+        assert!(signal.is_finished());
+
+        let mut signal = Impulse::new(2.0).band_limited().with_settle_time(1.0);
+        signal.sample(1.0, &mut out);
+        assert!(out[0].abs() < 1e-6 && out[1].abs() < 1e-6);
+        assert_eq!(out[2], 1.0);
+        assert!(!signal.is_finished());
+    }
+}
@@ -0,0 +1,106 @@
+//! Variants of the spatial API taking `glam` and `nalgebra` types directly
+//!
+//! The canonical methods take `mint` types, which both libraries convert to with `.into()`. These
+//! save the conversions at each call site, without making the canonical methods generic and
+//! thereby breaking type inference for callers who construct `mint` values with `.into()`.
+
+use crate::{Spatial, SpatialOptions, SpatialSceneControl};
+
+#[cfg(feature = "glam")]
+impl Spatial {
+    /// [`set_motion`](Self::set_motion) taking `glam` vectors
+    pub fn set_motion_glam(
+        &mut self,
+        position: glam::Vec3,
+        velocity: glam::Vec3,
+        discontinuity: bool,
+    ) {
+        self.set_motion(position.into(), velocity.into(), discontinuity);
+    }
+
+    /// [`set_direction`](Self::set_direction) taking a `glam` vector
+    pub fn set_direction_glam(&mut self, direction: glam::Vec3) {
+        self.set_direction(direction.into());
+    }
+}
+
+#[cfg(feature = "glam")]
+impl SpatialSceneControl {
+    /// [`set_listener_rotation`](Self::set_listener_rotation) taking a `glam` quaternion
+    pub fn set_listener_rotation_glam(&mut self, rotation: glam::Quat) {
+        self.set_listener_rotation(rotation.into());
+    }
+}
+
+#[cfg(feature = "glam")]
+impl SpatialOptions {
+    /// Set [`position`](Self::position) and [`velocity`](Self::velocity) from `glam` vectors
+    pub fn with_motion_glam(mut self, position: glam::Vec3, velocity: glam::Vec3) -> Self {
+        self.position = position.into();
+        self.velocity = velocity.into();
+        self
+    }
+}
+
+#[cfg(feature = "nalgebra")]
+impl Spatial {
+    /// [`set_motion`](Self::set_motion) taking `nalgebra` types
+    pub fn set_motion_nalgebra(
+        &mut self,
+        position: nalgebra::Point3<f32>,
+        velocity: nalgebra::Vector3<f32>,
+        discontinuity: bool,
+    ) {
+        self.set_motion(position.into(), velocity.into(), discontinuity);
+    }
+
+    /// [`set_direction`](Self::set_direction) taking a `nalgebra` vector
+    pub fn set_direction_nalgebra(&mut self, direction: nalgebra::Vector3<f32>) {
+        self.set_direction(direction.into());
+    }
+}
+
+#[cfg(feature = "nalgebra")]
+impl SpatialSceneControl {
+    /// [`set_listener_rotation`](Self::set_listener_rotation) taking a `nalgebra` rotation
+    pub fn set_listener_rotation_nalgebra(&mut self, rotation: nalgebra::UnitQuaternion<f32>) {
+        self.set_listener_rotation(rotation.into());
+    }
+}
+
+#[cfg(feature = "nalgebra")]
+impl SpatialOptions {
+    /// Set [`position`](Self::position) and [`velocity`](Self::velocity) from `nalgebra` types
+    pub fn with_motion_nalgebra(
+        mut self,
+        position: nalgebra::Point3<f32>,
+        velocity: nalgebra::Vector3<f32>,
+    ) -> Self {
+        self.position = position.into();
+        self.velocity = velocity.into();
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(feature = "glam")]
+    fn glam_options() {
+        let options = SpatialOptions::default()
+            .with_motion_glam(glam::Vec3::new(1.0, 2.0, 3.0), glam::Vec3::X);
+        assert_eq!(options.position, [1.0, 2.0, 3.0].into());
+        assert_eq!(options.velocity, [1.0, 0.0, 0.0].into());
+    }
+
+    #[test]
+    #[cfg(feature = "nalgebra")]
+    fn nalgebra_options() {
+        let options = SpatialOptions::default()
+            .with_motion_nalgebra(nalgebra::Point3::new(1.0, 2.0, 3.0), nalgebra::Vector3::x());
+        assert_eq!(options.position, [1.0, 2.0, 3.0].into());
+        assert_eq!(options.velocity, [1.0, 0.0, 0.0].into());
+    }
+}
@@ -0,0 +1,206 @@
+use alloc::{boxed::Box, sync::Arc, vec::Vec};
+use core::sync::atomic::{AtomicU32, Ordering};
+
+use crate::{frame, Frame, Reset, Seek, Signal, Smoothed};
+
+/// Plays several synchronized stems at once, each with its own dynamically-adjustable gain
+///
+/// Suited to "vertical remixing" of adaptive music, where layers such as drums or strings are
+/// brought in and out as the game's state changes. Every stem is sampled with the same interval
+/// whether or not it's audible, so stems stay locked together. For looping music, construct each
+/// stem with [`Cycle`](crate::Cycle) from [`Frames`](crate::Frames) of exactly the same length
+/// and rate, or the stems will drift apart after the first loop.
+pub struct Layers<T: Signal> {
+    shared: Arc<[Shared]>,
+    gains: Box<[Smoothed<f32>]>,
+    /// Seconds over which each layer's current gain change takes place
+    fades: Box<[f32]>,
+    /// Staging buffer for each stem's output
+    buffer: Box<[T::Frame]>,
+    stems: Box<[T]>,
+}
+
+impl<T: Signal> Layers<T>
+where
+    T::Frame: Frame,
+{
+    /// Play `stems` together, each initially at full volume
+    pub fn new(stems: impl IntoIterator<Item = T>) -> (LayersControl, Self) {
+        let stems = stems.into_iter().collect::<Box<[T]>>();
+        let shared = stems
+            .iter()
+            .map(|_| Shared {
+                gain: AtomicU32::new(1.0f32.to_bits()),
+                fade: AtomicU32::new(0.0f32.to_bits()),
+            })
+            .collect::<Vec<_>>();
+        let shared = Arc::<[Shared]>::from(shared);
+        let signal = Self {
+            shared: shared.clone(),
+            gains: stems.iter().map(|_| Smoothed::new(1.0)).collect(),
+            fades: stems.iter().map(|_| 0.0).collect(),
+            buffer: (0..BUFFER_SIZE).map(|_| T::Frame::ZERO).collect(),
+            stems,
+        };
+        (LayersControl(shared), signal)
+    }
+}
+
+impl<T: Signal> Signal for Layers<T>
+where
+    T::Frame: Frame,
+{
+    type Frame = T::Frame;
+
+    #[allow(clippy::float_cmp)]
+    fn sample(&mut self, interval: f32, out: &mut [T::Frame]) {
+        for o in out.iter_mut() {
+            *o = T::Frame::ZERO;
+        }
+        for (i, stem) in self.stems.iter_mut().enumerate() {
+            let gain = &mut self.gains[i];
+            let target = f32::from_bits(self.shared[i].gain.load(Ordering::Acquire));
+            if gain.target() != &target {
+                gain.set(target);
+                self.fades[i] = f32::from_bits(self.shared[i].fade.load(Ordering::Relaxed));
+                if self.fades[i] <= 0.0 {
+                    gain.advance(1.0);
+                }
+            }
+            for chunk in out.chunks_mut(self.buffer.len()) {
+                let buffer = &mut self.buffer[..chunk.len()];
+                stem.sample(interval, buffer);
+                for (o, x) in chunk.iter_mut().zip(&*buffer) {
+                    *o = frame::mix(o, &frame::scale(x, gain.get()));
+                    if gain.progress() < 1.0 {
+                        gain.advance(interval / self.fades[i]);
+                    }
+                }
+            }
+        }
+    }
+
+    fn is_finished(&self) -> bool {
+        self.stems.iter().all(|x| x.is_finished())
+    }
+
+    fn duration_remaining(&self) -> Option<f32> {
+        self.stems
+            .iter()
+            .try_fold(0.0f32, |acc, x| Some(acc.max(x.duration_remaining()?)))
+    }
+}
+
+impl<T: Seek> Seek for Layers<T>
+where
+    T::Frame: Frame,
+{
+    fn seek(&mut self, seconds: f32) {
+        for stem in self.stems.iter_mut() {
+            stem.seek(seconds);
+        }
+    }
+}
+
+/// Any gain changes in progress complete immediately.
+impl<T: Reset> Reset for Layers<T>
+where
+    T::Frame: Frame,
+{
+    fn reset(&mut self) {
+        for ((stem, gain), shared) in self
+            .stems
+            .iter_mut()
+            .zip(&mut *self.gains)
+            .zip(&*self.shared)
+        {
+            stem.reset();
+            *gain = Smoothed::new(f32::from_bits(shared.gain.load(Ordering::Relaxed)));
+        }
+    }
+}
+
+/// Thread-safe control for [`Layers`]
+pub struct LayersControl(Arc<[Shared]>);
+
+impl LayersControl {
+    /// Number of stems being played
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Whether no stems are being played
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Get the amplitude scaling most recently requested for stem `layer`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `layer` is out of bounds.
+    pub fn gain(&self, layer: usize) -> f32 {
+        f32::from_bits(self.0[layer].gain.load(Ordering::Relaxed))
+    }
+
+    /// Scale the amplitude of stem `layer` by `gain`, smoothed over a short period
+    ///
+    /// # Panics
+    ///
+    /// Panics if `layer` is out of bounds.
+    pub fn set_gain(&mut self, layer: usize, gain: f32) {
+        self.fade(layer, gain, DEFAULT_SMOOTHING_PERIOD);
+    }
+
+    /// Ramp the amplitude scaling of stem `layer` to `gain` over `seconds`
+    ///
+    /// To fade over a musical duration, convert it to seconds at the music's tempo, e.g.
+    /// `beats * 60.0 / bpm`. Zero changes the gain immediately.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `layer` is out of bounds.
+    pub fn fade(&mut self, layer: usize, gain: f32, seconds: f32) {
+        let shared = &self.0[layer];
+        shared.fade.store(seconds.to_bits(), Ordering::Relaxed);
+        // Releases `fade` to the audio thread along with the gain that it applies to
+        shared.gain.store(gain.to_bits(), Ordering::Release);
+    }
+}
+
+struct Shared {
+    /// Target amplitude scaling
+    gain: AtomicU32,
+    /// Seconds over which to reach `gain`
+    fade: AtomicU32,
+}
+
+/// Seconds over which [`LayersControl::set_gain`] smooths changes
+const DEFAULT_SMOOTHING_PERIOD: f32 = 0.1;
+
+/// Number of frames of each stem to sample at a time
+const BUFFER_SIZE: usize = 256;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Cycle, Frames};
+
+    #[test]
+    fn fade_layer() {
+        let stem = |x: f32| Cycle::new(Frames::from_slice(4, &[x, 0.0, 0.0, 0.0]));
+        let (mut control, mut signal) = Layers::new([stem(1.0), stem(2.0)]);
+        assert_eq!(control.len(), 2);
+        let mut out = [0.0; 4];
+        signal.sample(0.25, &mut out);
+        assert_eq!(out, [3.0, 0.0, 0.0, 0.0]);
+
+        control.fade(1, 0.0, 2.0);
+        signal.sample(0.25, &mut out);
+        assert_eq!(out, [3.0, 0.0, 0.0, 0.0]);
+        signal.sample(0.25, &mut out);
+        assert_eq!(out, [2.0, 0.0, 0.0, 0.0], "stems remain in sync");
+        signal.sample(0.25, &mut out);
+        assert_eq!(out, [1.0, 0.0, 0.0, 0.0]);
+    }
+}
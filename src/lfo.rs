@@ -0,0 +1,156 @@
+use core::f32::consts::TAU;
+
+use crate::{
+    math::Float,
+    tempo::{NoteValue, Tempo, TempoSync},
+    Sample, Seek, Signal,
+};
+
+/// A low-frequency oscillator, for modulating parameters
+///
+/// Cycles through a [`Waveform`] forever, by default between -1 and 1. Combine with
+/// [`Automate`](crate::Automate) to drive a control, or embed in custom effects.
+pub struct Lfo {
+    waveform: Waveform,
+    /// Cycles per second
+    frequency: f32,
+    /// Position within the current cycle, in [0, 1)
+    phase: f32,
+    /// Tempo to which `frequency` is synced, if any
+    sync: Option<TempoSync>,
+    min: f32,
+    max: f32,
+}
+
+impl Lfo {
+    /// Construct an oscillator that cycles through `waveform` `frequency_hz` times per second
+    pub fn new(waveform: Waveform, frequency_hz: f32) -> Self {
+        Self {
+            waveform,
+            frequency: frequency_hz,
+            phase: 0.0,
+            sync: None,
+            min: -1.0,
+            max: 1.0,
+        }
+    }
+
+    /// Begin `cycles` of the way through the waveform, e.g. 0.25 for a quarter cycle
+    pub fn with_phase(mut self, cycles: f32) -> Self {
+        self.phase = cycles.rem_euclid(1.0);
+        self
+    }
+
+    /// Output values between `min` and `max` rather than -1 and 1
+    ///
+    /// For example, `with_range(0.0, 1.0)` produces a unipolar signal suitable for a depth
+    /// parameter.
+    pub fn with_range(mut self, min: f32, max: f32) -> Self {
+        self.min = min;
+        self.max = max;
+        self
+    }
+
+    /// Cycle once per `note` at `tempo`, rather than at a fixed frequency
+    ///
+    /// See [`Tempo`] for details.
+    pub fn with_tempo(mut self, tempo: Tempo, note: NoteValue) -> Self {
+        self.sync = Some(TempoSync { tempo, note });
+        self
+    }
+
+    fn seek_to(&mut self, t: f32) {
+        // Wrap for numerical stability no matter how long we play for
+        self.phase = (self.phase + t * self.frequency).rem_euclid(1.0);
+    }
+}
+
+impl Signal for Lfo {
+    type Frame = Sample;
+
+    fn sample(&mut self, interval: f32, out: &mut [Sample]) {
+        if let Some(ref sync) = self.sync {
+            self.frequency = sync.hz();
+        }
+        for (i, x) in out.iter_mut().enumerate() {
+            let phase = (self.phase + interval * i as f32 * self.frequency).fract();
+            let unit = match self.waveform {
+                Waveform::Sine => 0.5 + 0.5 * (TAU * phase).sin(),
+                Waveform::Triangle => 1.0 - (2.0 * phase - 1.0).abs(),
+                Waveform::Saw => phase,
+                Waveform::Square => {
+                    if phase < 0.5 {
+                        1.0
+                    } else {
+                        0.0
+                    }
+                }
+            };
+            *x = self.min + (self.max - self.min) * unit;
+        }
+        self.seek_to(interval * out.len() as f32);
+    }
+}
+
+impl Seek for Lfo {
+    fn seek(&mut self, seconds: f32) {
+        self.seek_to(seconds);
+    }
+}
+
+/// Shape of an [`Lfo`]'s cycle
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Waveform {
+    /// Smooth oscillation, starting at the middle of the range and rising
+    Sine,
+    /// Linear rise from the bottom of the range to the top and back
+    Triangle,
+    /// Linear rise from the bottom of the range to the top, then an abrupt drop
+    Saw,
+    /// Alternation between the top and bottom of the range
+    Square,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn waveforms() {
+        let mut out = [0.0; 5];
+        Lfo::new(Waveform::Triangle, 1.0).sample(0.25, &mut out);
+        assert_eq!(out, [-1.0, 0.0, 1.0, 0.0, -1.0]);
+        Lfo::new(Waveform::Saw, 1.0)
+            .with_range(0.0, 1.0)
+            .sample(0.25, &mut out);
+        assert_eq!(out, [0.0, 0.25, 0.5, 0.75, 0.0]);
+        Lfo::new(Waveform::Square, 1.0)
+            .with_phase(0.5)
+            .sample(0.25, &mut out);
+        assert_eq!(out, [-1.0, -1.0, 1.0, 1.0, -1.0]);
+    }
+
+    #[test]
+    fn tempo() {
+        let mut tempo = Tempo::new(120.0);
+        let mut lfo = Lfo::new(Waveform::Saw, 1.0)
+            .with_range(0.0, 1.0)
+            .with_tempo(tempo.clone(), NoteValue::WHOLE);
+        let mut out = [0.0; 2];
+        lfo.sample(0.5, &mut out);
+        assert_eq!(out, [0.0, 0.25]);
+        tempo.set_bpm(240.0);
+        lfo.sample(0.5, &mut out);
+        assert_eq!(out, [0.5, 0.0]);
+    }
+
+    #[test]
+    fn seek() {
+        let mut lfo = Lfo::new(Waveform::Saw, 2.0).with_range(0.0, 1.0);
+        lfo.seek(0.125);
+        let mut out = [0.0];
+        lfo.sample(1.0, &mut out);
+        assert_eq!(out, [0.25]);
+    }
+}
@@ -30,56 +30,147 @@
 //! - [`Mixer`] allows multiple signals to be played concurrently and controlled during playback
 //! - [`SpatialScene`] is a mixer that spatializes its signals
 //! - [`run`] writes frames from a [`Signal`] into an output buffer
+//! - [`Offline`] renders a [`Signal`] to memory, e.g. for tests or baking assets
 
 #![allow(unused_imports)]
 #![warn(missing_docs)]
 #![no_std]
 
 extern crate alloc;
-#[cfg(not(feature = "no_std"))]
+// Decoding requires `std::io` regardless
+#[cfg(any(not(feature = "no_std"), feature = "wav", feature = "symphonia"))]
 extern crate std;
 
 mod adapt;
+mod automation;
+mod biquad;
+mod bitcrush;
+mod chorus;
+mod clip;
 mod constant;
+mod crossfade;
+mod crossover;
 mod cycle;
+#[cfg(feature = "symphonia")]
+mod decode;
+mod delay_line;
+pub mod dither;
 mod downmix;
+mod duck;
+pub mod dynamics;
+mod envelope;
+mod eq;
+mod fade_out_tail;
 mod fader;
+mod flanger;
+mod fm;
 mod frame;
 mod frames;
 mod gain;
+mod gate;
+mod generator;
+mod impulse;
+#[cfg(any(feature = "glam", feature = "nalgebra"))]
+mod interop;
+mod layers;
+mod lfo;
+mod map;
 mod math;
+mod meter;
 mod mixer;
+mod offline;
+mod one_pole;
+mod oversample;
+mod owned_handle;
+mod phaser;
+mod pitch_shift;
+mod recording;
 mod reinhard;
+mod resample;
+mod reverb;
 mod ring;
+mod ring_mod;
+mod rng;
+mod scope;
 mod set;
 mod signal;
 mod sine;
 mod smooth;
 mod spatial;
 mod speed;
-mod spsc;
+pub mod spsc;
+mod stereo_width;
 mod stream;
-mod swap;
+pub mod swap;
 mod tanh;
+mod tempo;
+#[cfg(test)]
+mod test_util;
+mod to_mono;
+mod upmix;
+#[cfg(feature = "wav")]
+mod wav;
+mod wavetable;
 
-pub use adapt::{Adapt, AdaptOptions};
-pub use constant::Constant;
+pub use adapt::{Adapt, AdaptControl, AdaptOptions};
+pub use automation::{Automate, Automation, Breakpoint, Curve};
+pub use bitcrush::{Bitcrush, BitcrushControl};
+pub use chorus::{Chorus, ChorusControl, ChorusOptions};
+pub use clip::{ClipControl, HardClip, SoftClip};
+pub use constant::{Constant, DynamicConstant, DynamicConstantControl};
+pub use crossfade::{Crossfade, CrossfadeControl};
+pub use crossover::Crossover;
 pub use cycle::Cycle;
+#[cfg(feature = "symphonia")]
+pub use decode::DecodeHandle;
+pub use delay_line::DelayLine;
 pub use downmix::Downmix;
+pub use duck::{Duck, DuckOptions};
+pub use envelope::{EnvelopeControl, EnvelopeDetection, EnvelopeFollower, EnvelopeOptions};
+pub use eq::{EqControl, HighShelf, LowShelf, PeakingEq};
+pub use fade_out_tail::FadeOutTail;
 pub use fader::{Fader, FaderControl};
-pub use frame::Frame;
+pub use flanger::{Flanger, FlangerControl, FlangerOptions};
+pub use fm::{FmVoice, FmVoiceControl};
+pub use frame::{Frame, IntoFloat};
 pub use frames::*;
 pub use gain::{FixedGain, Gain, GainControl};
+pub use gate::{Gate, GateControl, GateOptions};
+pub use generator::Generator;
+pub use impulse::Impulse;
+pub use layers::{Layers, LayersControl};
+pub use lfo::{Lfo, Waveform};
+pub use map::Map;
+pub use meter::{Meter, MeterControl};
 pub use mixer::*;
-pub use reinhard::Reinhard;
-use set::*;
+pub use offline::Offline;
+pub use one_pole::{OnePole, OnePoleControl};
+pub use oversample::Oversample;
+pub use owned_handle::OwnedHandle;
+pub use phaser::{Phaser, PhaserControl, PhaserOptions};
+pub use pitch_shift::{PitchShift, PitchShiftControl};
+pub use recording::{Recording, RecordingControl};
+pub use reinhard::{Reinhard, ReinhardControl};
+pub use resample::Resample;
+pub use reverb::{Reverb, ReverbControl, ReverbOptions};
+pub use ring_mod::{RingMod, RingModControl};
+pub use rng::Rng;
+pub use scope::{Scope, ScopeControl};
+pub use set::{set, Set, SetHandle};
 pub use signal::*;
 pub use sine::*;
 pub use smooth::{Interpolate, Smoothed};
 pub use spatial::*;
 pub use speed::{Speed, SpeedControl};
+pub use stereo_width::{StereoWidth, StereoWidthControl};
 pub use stream::{Stream, StreamControl};
-pub use tanh::Tanh;
+pub use tanh::{Tanh, TanhControl};
+pub use tempo::{NoteValue, Tempo};
+pub use to_mono::ToMono;
+pub use upmix::Upmix;
+#[cfg(feature = "wav")]
+pub use wav::{WavError, WavFrames};
+pub use wavetable::{Wavetable, WavetableControl};
 
 /// Unitless instantaneous sound wave amplitude measurement
 pub type Sample = f32;
@@ -94,9 +185,30 @@ pub fn run<S: Signal + ?Sized>(signal: &mut S, sample_rate: u32, out: &mut [S::F
 
 /// Convert a slice of interleaved stereo data into a slice of stereo frames
 ///
-/// Useful for adapting output buffers obtained externally.
+/// Useful for adapting output buffers obtained externally. If `xs` has an odd length, its last
+/// sample is left out.
 pub fn frame_stereo(xs: &mut [Sample]) -> &mut [[Sample; 2]] {
-    unsafe { core::slice::from_raw_parts_mut(xs.as_mut_ptr() as _, xs.len() / 2) }
+    let len = xs.len() / 2 * 2;
+    frame_multi(&mut xs[..len])
+}
+
+/// Convert a slice of interleaved `N`-channel data into a slice of `N`-channel frames
+///
+/// Useful for adapting output buffers obtained externally, e.g. for surround sound.
+///
+/// # Panics
+///
+/// Panics if the length of `xs` is not a multiple of `N`.
+// `usize::is_multiple_of` would require Rust 1.87
+#[allow(clippy::manual_is_multiple_of)]
+pub fn frame_multi<const N: usize>(xs: &mut [Sample]) -> &mut [[Sample; N]] {
+    assert!(
+        N != 0 && xs.len() % N == 0,
+        "length must be a multiple of the channel count"
+    );
+    // Sound because `[Sample; N]` has the same alignment as `Sample` and exactly `N` times its
+    // size, and the resulting slice covers exactly the memory of `xs`, which it mutably borrows.
+    unsafe { core::slice::from_raw_parts_mut(xs.as_mut_ptr() as _, xs.len() / N) }
 }
 
 fn flatten_stereo(xs: &mut [[Sample; 2]]) -> &mut [Sample] {
@@ -0,0 +1,65 @@
+use crate::{Reset, Seek, Signal};
+
+/// Applies a closure to each frame of a signal
+///
+/// Constructed by [`SignalExt::map`](crate::SignalExt::map). Useful for one-off effects like
+/// custom waveshaping or channel manipulation. The closure runs on the audio thread, so like
+/// [`Signal::sample`] it must not wait, allocate, or free memory.
+pub struct Map<T: ?Sized, F> {
+    f: F,
+    inner: T,
+}
+
+impl<T, F> Map<T, F> {
+    /// Apply `f` to each frame produced by `signal`
+    pub fn new(signal: T, f: F) -> Self {
+        Self { f, inner: signal }
+    }
+}
+
+impl<T: Signal + ?Sized, F: FnMut(&mut T::Frame)> Signal for Map<T, F> {
+    type Frame = T::Frame;
+
+    fn sample(&mut self, interval: f32, out: &mut [T::Frame]) {
+        self.inner.sample(interval, out);
+        for x in out {
+            (self.f)(x);
+        }
+    }
+
+    fn is_finished(&self) -> bool {
+        self.inner.is_finished()
+    }
+
+    fn duration_remaining(&self) -> Option<f32> {
+        self.inner.duration_remaining()
+    }
+}
+
+/// Seeking is only meaningful when the closure has no internal state, so that each output frame
+/// depends only on the corresponding input frame.
+impl<T: Seek + ?Sized, F: FnMut(&mut T::Frame)> Seek for Map<T, F> {
+    fn seek(&mut self, seconds: f32) {
+        self.inner.seek(seconds);
+    }
+}
+
+/// The closure's own state, if any, is unaffected.
+impl<T: Reset + ?Sized, F: FnMut(&mut T::Frame)> Reset for Map<T, F> {
+    fn reset(&mut self) {
+        self.inner.reset();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Constant, Signal, SignalExt};
+
+    #[test]
+    fn swap_channels() {
+        let mut signal = Constant::new([1.0, 2.0]).map(|x| x.swap(0, 1));
+        let mut out = [[0.0; 2]; 2];
+        signal.sample(1.0, &mut out);
+        assert_eq!(out, [[2.0, 1.0]; 2]);
+    }
+}
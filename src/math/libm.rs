@@ -17,10 +17,18 @@ impl Float for f32 {
         libm::ceilf(self)
     }
 
+    fn floor(self) -> Self {
+        libm::floorf(self)
+    }
+
     fn trunc(self) -> Self {
         libm::truncf(self)
     }
 
+    fn round(self) -> Self {
+        libm::roundf(self)
+    }
+
     fn fract(self) -> Self {
         self - self.trunc()
     }
@@ -29,6 +37,10 @@ impl Float for f32 {
         libm::log10f(self)
     }
 
+    fn ln(self) -> Self {
+        libm::logf(self)
+    }
+
     fn powf(self, n: Self) -> Self {
         libm::powf(self, n)
     }
@@ -62,6 +74,18 @@ impl Float for f32 {
         libm::sinf(self)
     }
 
+    fn cos(self) -> Self {
+        libm::cosf(self)
+    }
+
+    fn tan(self) -> Self {
+        libm::tanf(self)
+    }
+
+    fn acos(self) -> Self {
+        libm::acosf(self)
+    }
+
     fn rem_euclid(self, rhs: Self) -> Self {
         let r = self % rhs;
         if r < 0.0 {
@@ -93,10 +117,18 @@ impl Float for f64 {
         libm::ceil(self)
     }
 
+    fn floor(self) -> Self {
+        libm::floor(self)
+    }
+
     fn trunc(self) -> Self {
         libm::trunc(self)
     }
 
+    fn round(self) -> Self {
+        libm::round(self)
+    }
+
     fn fract(self) -> Self {
         self - self.trunc()
     }
@@ -105,6 +137,10 @@ impl Float for f64 {
         libm::log10(self)
     }
 
+    fn ln(self) -> Self {
+        libm::log(self)
+    }
+
     fn powf(self, n: Self) -> Self {
         libm::pow(self, n)
     }
@@ -138,6 +174,18 @@ impl Float for f64 {
         libm::sin(self)
     }
 
+    fn cos(self) -> Self {
+        libm::cos(self)
+    }
+
+    fn tan(self) -> Self {
+        libm::tan(self)
+    }
+
+    fn acos(self) -> Self {
+        libm::acos(self)
+    }
+
     fn rem_euclid(self, rhs: Self) -> Self {
         let r = self % rhs;
         if r < 0.0 {
@@ -4,7 +4,22 @@ mod std;
 #[cfg(feature = "no_std")]
 mod libm;
 
+// Whenever std is linked, the inherent float methods shadow these, leaving the trait unused. Besides
+// features that need std, this happens when our dev-dependencies enable the std features of
+// dependencies that optional features share with them.
+#[cfg_attr(
+    any(
+        not(feature = "no_std"),
+        feature = "wav",
+        feature = "symphonia",
+        feature = "nalgebra",
+        feature = "serde"
+    ),
+    allow(dead_code)
+)]
 pub(crate) trait Float {
+    // Inherent to `f32` in core since Rust 1.85, which takes precedence where available
+    #[allow(dead_code)]
     fn abs(self) -> Self;
 
     fn sqrt(self) -> Self;
@@ -13,18 +28,30 @@ pub(crate) trait Float {
 
     fn ceil(self) -> Self;
 
+    fn floor(self) -> Self;
+
     fn trunc(self) -> Self;
 
+    fn round(self) -> Self;
+
     fn fract(self) -> Self;
 
     fn log10(self) -> Self;
 
+    fn ln(self) -> Self;
+
     fn powf(self, n: Self) -> Self;
 
     fn powi(self, n: i32) -> Self;
 
     fn sin(self) -> Self;
 
+    fn cos(self) -> Self;
+
+    fn tan(self) -> Self;
+
+    fn acos(self) -> Self;
+
     fn rem_euclid(self, rhs: Self) -> Self;
 
     fn tanh(self) -> Self;
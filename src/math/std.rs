@@ -17,10 +17,18 @@ impl Float for f32 {
         Self::ceil(self)
     }
 
+    fn floor(self) -> Self {
+        Self::floor(self)
+    }
+
     fn trunc(self) -> Self {
         Self::trunc(self)
     }
 
+    fn round(self) -> Self {
+        Self::round(self)
+    }
+
     fn fract(self) -> Self {
         Self::fract(self)
     }
@@ -29,6 +37,10 @@ impl Float for f32 {
         Self::log10(self)
     }
 
+    fn ln(self) -> Self {
+        Self::ln(self)
+    }
+
     fn powf(self, n: Self) -> Self {
         Self::powf(self, n)
     }
@@ -41,6 +53,18 @@ impl Float for f32 {
         Self::sin(self)
     }
 
+    fn cos(self) -> Self {
+        Self::cos(self)
+    }
+
+    fn tan(self) -> Self {
+        Self::tan(self)
+    }
+
+    fn acos(self) -> Self {
+        Self::acos(self)
+    }
+
     fn rem_euclid(self, rhs: Self) -> Self {
         Self::rem_euclid(self, rhs)
     }
@@ -67,10 +91,18 @@ impl Float for f64 {
         Self::ceil(self)
     }
 
+    fn floor(self) -> Self {
+        Self::floor(self)
+    }
+
     fn trunc(self) -> Self {
         Self::trunc(self)
     }
 
+    fn round(self) -> Self {
+        Self::round(self)
+    }
+
     fn fract(self) -> Self {
         Self::fract(self)
     }
@@ -79,6 +111,10 @@ impl Float for f64 {
         Self::log10(self)
     }
 
+    fn ln(self) -> Self {
+        Self::ln(self)
+    }
+
     fn powf(self, n: Self) -> Self {
         Self::powf(self, n)
     }
@@ -91,6 +127,18 @@ impl Float for f64 {
         Self::sin(self)
     }
 
+    fn cos(self) -> Self {
+        Self::cos(self)
+    }
+
+    fn tan(self) -> Self {
+        Self::tan(self)
+    }
+
+    fn acos(self) -> Self {
+        Self::acos(self)
+    }
+
     fn rem_euclid(self, rhs: Self) -> Self {
         Self::rem_euclid(self, rhs)
     }
@@ -0,0 +1,173 @@
+use alloc::{boxed::Box, sync::Arc};
+use core::sync::atomic::{AtomicU32, Ordering};
+
+use crate::{math::Float, Frame, Seek, Signal};
+
+/// Measures the level of a signal without modifying it
+///
+/// Tracks the root mean square level and peak amplitude of each channel, which can be read from
+/// another thread through a [`MeterControl`], e.g. to display a level meter.
+pub struct Meter<T: ?Sized> {
+    shared: Arc<Shared>,
+    window: f32,
+    release: f32,
+    inner: T,
+}
+
+impl<T: Signal> Meter<T>
+where
+    T::Frame: Frame,
+{
+    /// Measure the level of `signal`
+    ///
+    /// RMS levels are averaged over roughly `window` seconds. Peaks decay by a factor of e every
+    /// `release` seconds, so that brief spikes remain visible.
+    pub fn new(signal: T, window: f32, release: f32) -> (MeterControl, Self) {
+        let channels = T::Frame::ZERO.channels().len();
+        let signal = Self {
+            shared: Arc::new(Shared {
+                channels: (0..channels).map(|_| Level::default()).collect(),
+                overall: Level::default(),
+            }),
+            window,
+            release,
+            inner: signal,
+        };
+        let control = MeterControl(signal.shared.clone());
+        (control, signal)
+    }
+}
+
+impl<T: Signal> Signal for Meter<T>
+where
+    T::Frame: Frame,
+{
+    type Frame = T::Frame;
+
+    fn sample(&mut self, interval: f32, out: &mut [T::Frame]) {
+        self.inner.sample(interval, out);
+        let alpha = 1.0 - (-interval / self.window).exp();
+        let decay = (-interval / self.release).exp();
+        let mut max_mean_square = 0.0f32;
+        let mut max_peak = 0.0f32;
+        // We're the only writer, so the shared state doubles as our own
+        for (i, level) in self.shared.channels.iter().enumerate() {
+            let mut mean_square = level.mean_square();
+            let mut peak = level.peak();
+            for x in out.iter() {
+                let x = x.channels()[i];
+                mean_square = x * x * alpha + mean_square * (1.0 - alpha);
+                peak = (peak * decay).max(x.abs());
+            }
+            level.store(mean_square, peak);
+            max_mean_square = max_mean_square.max(mean_square);
+            max_peak = max_peak.max(peak);
+        }
+        self.shared.overall.store(max_mean_square, max_peak);
+    }
+
+    fn is_finished(&self) -> bool {
+        self.inner.is_finished()
+    }
+
+    fn duration_remaining(&self) -> Option<f32> {
+        self.inner.duration_remaining()
+    }
+}
+
+impl<T: Seek> Seek for Meter<T>
+where
+    T::Frame: Frame,
+{
+    fn seek(&mut self, seconds: f32) {
+        self.inner.seek(seconds);
+    }
+}
+
+/// Thread-safe access to the levels measured by a [`Meter`]
+///
+/// Levels are linear amplitudes. Cloning yields another handle to the same measurements.
+#[derive(Clone)]
+pub struct MeterControl(Arc<Shared>);
+
+impl MeterControl {
+    /// Number of channels being measured
+    pub fn channels(&self) -> usize {
+        self.0.channels.len()
+    }
+
+    /// Root mean square level of `channel`
+    pub fn rms(&self, channel: usize) -> f32 {
+        self.0.channels[channel].mean_square().sqrt()
+    }
+
+    /// Decaying peak amplitude of `channel`
+    pub fn peak(&self, channel: usize) -> f32 {
+        self.0.channels[channel].peak()
+    }
+
+    /// Root mean square level of the loudest channel
+    pub fn max_rms(&self) -> f32 {
+        self.0.overall.mean_square().sqrt()
+    }
+
+    /// Decaying peak amplitude of the loudest channel
+    pub fn max_peak(&self) -> f32 {
+        self.0.overall.peak()
+    }
+}
+
+struct Shared {
+    channels: Box<[Level]>,
+    /// Maximum across all channels
+    overall: Level,
+}
+
+#[derive(Default)]
+struct Level {
+    mean_square: AtomicU32,
+    peak: AtomicU32,
+}
+
+impl Level {
+    fn mean_square(&self) -> f32 {
+        f32::from_bits(self.mean_square.load(Ordering::Relaxed))
+    }
+
+    fn peak(&self) -> f32 {
+        f32::from_bits(self.peak.load(Ordering::Relaxed))
+    }
+
+    fn store(&self, mean_square: f32, peak: f32) {
+        self.mean_square
+            .store(mean_square.to_bits(), Ordering::Relaxed);
+        self.peak.store(peak.to_bits(), Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Constant;
+
+    #[test]
+    fn smoke() {
+        let (c, mut s) = Meter::new(Constant::new([1.0, -0.5]), 0.1, 1.0);
+        assert_eq!(c.channels(), 2);
+        assert_eq!(c.max_rms(), 0.0);
+        let mut buf = [[0.0; 2]; 100];
+        s.sample(0.01, &mut buf);
+        assert_eq!(buf, [[1.0, -0.5]; 100], "signal is unmodified");
+        assert!((c.rms(0) - 1.0).abs() < 1e-3);
+        assert!((c.rms(1) - 0.5).abs() < 1e-3);
+        assert_eq!(c.peak(0), 1.0);
+        assert_eq!(c.peak(1), 0.5);
+        assert_eq!(c.max_rms(), c.rms(0));
+        assert_eq!(c.max_peak(), 1.0);
+
+        s.inner.0 = [0.0; 2];
+        s.sample(0.01, &mut buf);
+        assert!(c.rms(0) < 1e-2);
+        assert!((c.peak(0) - (-1.0f32).exp()).abs() < 1e-3);
+    }
+}
@@ -1,10 +1,20 @@
-use alloc::{boxed::Box, sync::Arc, vec};
-use core::sync::atomic::{AtomicBool, Ordering};
+use alloc::{boxed::Box, sync::Arc, vec, vec::Vec};
+use core::sync::atomic::{AtomicBool, AtomicU32, AtomicUsize, Ordering};
 
-use crate::{frame, set, Frame, Set, SetHandle, Signal};
+use crate::{frame, math::Float, set, swap, Frame, Set, SetHandle, Signal, Smoothed, Tempo};
 
 /// Handle for controlling a [`Mixer`] from another thread
-pub struct MixerControl<T>(SetHandle<ErasedSignal<T>>);
+pub struct MixerControl<T> {
+    set: SetHandle<ErasedSignal<T>>,
+    clock: swap::Receiver<Clock>,
+    /// Incremented by `clear`; signals played before the most recent increment are stopped
+    epoch: Arc<AtomicUsize>,
+    tempo: Tempo,
+    /// Bits of the largest absolute sample in the most recent output block
+    peak: Arc<AtomicU32>,
+    /// State shared with every signal that may still be playing, for [`solo`](Self::solo)
+    voices: Vec<Arc<Shared>>,
+}
 
 impl<T> MixerControl<T> {
     /// Begin playing `signal`, returning a handle that can be used to pause or stop it and access
@@ -19,44 +29,369 @@ impl<T> MixerControl<T> {
     where
         S: Signal<Frame = T> + Send + 'static,
     {
-        let signal = Box::new(MixedSignal::new(signal));
-        let control = Mixed(signal.stop.clone());
-        self.0.insert(signal);
+        self.insert(Box::new(signal), 0.0, 0.0, 0.0)
+    }
+
+    /// Like [`play`](Self::play), but take an already-boxed signal
+    ///
+    /// `play` moves `signal` into a new allocation so that it can be stored alongside signals of
+    /// other types. Passing a box allocated ahead of time, e.g. from a pool, avoids that allocation
+    /// on the calling thread, though a small amount of state shared with the returned handle is
+    /// still allocated. Regardless of how a signal is played, the audio thread never allocates or
+    /// frees memory: finished signals are dropped on the control thread during later calls.
+    pub fn play_boxed(&mut self, signal: Box<dyn Signal<Frame = T> + Send>) -> Mixed {
+        self.insert(signal, 0.0, 0.0, 0.0)
+    }
+
+    /// Like [`play`](Self::play), but ramp the signal's volume up from silence over `fade_in`
+    /// seconds
+    pub fn play_with_fade<S>(&mut self, signal: S, fade_in: f32) -> Mixed
+    where
+        S: Signal<Frame = T> + Send + 'static,
+    {
+        self.insert(Box::new(signal), 0.0, fade_in, 0.0)
+    }
+
+    /// Like [`play`](Self::play), but begin playing `signal` `delay` seconds after [`now`](Self::now)
+    ///
+    /// Playback begins on the first output frame at or after the scheduled time, allowing
+    /// sample-accurate sequencing. If the mixer has already passed the scheduled time by the time
+    /// it receives `signal`, e.g. due to a zero or negative `delay`, playback begins immediately.
+    pub fn play_at<S>(&mut self, signal: S, delay: f64) -> Mixed
+    where
+        S: Signal<Frame = T> + Send + 'static,
+    {
+        let start = self.now() + delay;
+        self.insert(Box::new(signal), start, 0.0, 0.0)
+    }
+
+    /// Like [`play`](Self::play), but begin playing `signal` on the next multiple of `subdivision`
+    /// beats at the mixer's [`tempo`](Self::set_tempo)
+    ///
+    /// For example, a `subdivision` of 1 starts on the next beat, 0.5 on the next eighth note in
+    /// 4/4 time, and 4 on the next bar. The start time is computed by the audio thread when it
+    /// receives `signal`, so it's sample-accurate and never late, assuming the tempo doesn't change
+    /// before then. Signals received exactly on a grid point begin immediately.
+    pub fn play_quantized<S>(&mut self, signal: S, subdivision: f64) -> Mixed
+    where
+        S: Signal<Frame = T> + Send + 'static,
+    {
+        self.insert(Box::new(signal), 0.0, 0.0, subdivision)
+    }
+
+    /// Seconds of output the mixer has produced
+    ///
+    /// Lags behind the audio thread by up to one `sample` call.
+    pub fn now(&mut self) -> f64 {
+        self.clock.refresh();
+        self.clock.received().time
+    }
+
+    /// Number of frames the mixer has produced
+    ///
+    /// Counts exactly the frames written by `sample` calls, so unlike [`now`](Self::now), it
+    /// accumulates no rounding error, making it a reliable time base for synchronizing e.g.
+    /// animation with audio. At 192kHz, takes millions of years to wrap around. Lags behind the
+    /// audio thread by up to one `sample` call.
+    pub fn frames_processed(&mut self) -> u64 {
+        self.clock.refresh();
+        self.clock.received().frames
+    }
+
+    /// Largest absolute sample value, across all channels, in the most recent block of output
+    ///
+    /// Values above 1.0 indicate the output will clip, e.g. when converted to integer samples.
+    /// Reset on every `sample` call, so poll regularly and keep the maximum to detect brief peaks.
+    pub fn peak(&self) -> f32 {
+        f32::from_bits(self.peak.load(Ordering::Relaxed))
+    }
+
+    /// Number of beats the mixer has produced, accounting for changes in tempo
+    ///
+    /// The fractional part is the position within the current beat, e.g. for a metronome display.
+    /// Lags behind the audio thread by up to one `sample` call.
+    pub fn beats(&mut self) -> f64 {
+        self.clock.refresh();
+        self.clock.received().beats
+    }
+
+    /// Get the tempo used for [`play_quantized`](Self::play_quantized), in beats per minute
+    pub fn tempo(&self) -> f32 {
+        self.tempo.bpm()
+    }
+
+    /// Set the tempo used for [`play_quantized`](Self::play_quantized), in beats per minute
+    ///
+    /// Defaults to 120.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bpm` isn't positive and finite.
+    pub fn set_tempo(&mut self, bpm: f32) {
+        self.tempo.set_bpm(bpm);
+    }
+
+    /// Get a handle to the mixer's tempo, e.g. to sync effects with
+    /// [`Lfo::with_tempo`](crate::Lfo::with_tempo) to the same beat
+    pub fn shared_tempo(&self) -> Tempo {
+        self.tempo.clone()
+    }
+
+    /// Number of signals being played
+    ///
+    /// Stopped and finished signals continue to be counted until the mixer next reclaims them
+    /// during a `sample` call.
+    pub fn voice_count(&mut self) -> usize {
+        self.set.len()
+    }
+
+    /// Stop all signals that are currently playing
+    ///
+    /// Signals played after this call are unaffected.
+    pub fn clear(&mut self) {
+        self.epoch.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// [`mute`](Mixed::mute) every signal except `handle`'s, and unmute `handle`'s
+    ///
+    /// A convenience for isolating a single signal, e.g. to find which is too loud. Solo is
+    /// implemented entirely on the control thread by setting each signal's mute flag, so it doesn't
+    /// affect signals played later, and individual signals can be unmuted again afterwards.
+    pub fn solo(&mut self, handle: &Mixed) {
+        self.prune();
+        for voice in &self.voices {
+            voice
+                .muted
+                .store(!Arc::ptr_eq(voice, &handle.0), Ordering::Relaxed);
+        }
+    }
+
+    /// [`unmute`](Mixed::unmute) every signal, e.g. to undo [`solo`](Self::solo)
+    pub fn unmute_all(&mut self) {
+        self.prune();
+        for voice in &self.voices {
+            voice.muted.store(false, Ordering::Relaxed);
+        }
+    }
+
+    /// Forget signals that have been stopped
+    fn prune(&mut self) {
+        self.voices.retain(|x| !x.stop.load(Ordering::Relaxed));
+    }
+
+    fn insert(
+        &mut self,
+        signal: Box<dyn Signal<Frame = T> + Send>,
+        start: f64,
+        fade_in: f32,
+        quantize: f64,
+    ) -> Mixed {
+        let epoch = self.epoch.load(Ordering::Relaxed);
+        let mut signal = MixedSignal::new(signal, start, fade_in, epoch);
+        signal.quantize = quantize;
+        let control = Mixed(signal.shared.clone());
+        self.prune();
+        self.voices.push(signal.shared.clone());
+        self.set.insert(signal);
         control
     }
 }
 
 /// Handle to a signal playing in a [`Mixer`]
-pub struct Mixed(Arc<AtomicBool>);
+pub struct Mixed(Arc<Shared>);
 
 impl Mixed {
     /// Immediately halt playback of the associated signal by its [`Mixer`]
+    ///
+    /// Abruptly cutting off a signal can produce an audible click. See
+    /// [`stop_with_fade`](Self::stop_with_fade) for a smoother alternative.
     pub fn stop(&mut self) {
-        self.0.store(true, Ordering::Relaxed);
+        self.0.stop.store(true, Ordering::Relaxed);
+    }
+
+    /// Ramp the associated signal's volume down to silence over `duration` seconds, then halt it
+    pub fn stop_with_fade(&mut self, duration: f32) {
+        if duration <= 0.0 {
+            self.stop();
+            return;
+        }
+        self.0.fade_out.store(duration.to_bits(), Ordering::Relaxed);
+    }
+
+    /// Suspend playback of the associated signal, without discarding it
+    ///
+    /// A paused signal isn't sampled, so it resumes from where it left off. Like
+    /// [`stop`](Self::stop), pausing abruptly can produce an audible click. See
+    /// [`pause_with_fade`](Self::pause_with_fade) for a smoother alternative.
+    pub fn pause(&mut self) {
+        self.0.paused.store(true, Ordering::Relaxed);
     }
 
-    /// Whether the signal's playback
+    /// Ramp the associated signal's volume down to silence over `duration` seconds, then suspend it
     ///
-    /// Set by both `is_stopped` and signals naturally finishing.
+    /// When the signal is [`resume`](Self::resume)d, its volume ramps back up over the same
+    /// duration.
+    pub fn pause_with_fade(&mut self, duration: f32) {
+        if duration <= 0.0 {
+            self.pause();
+            return;
+        }
+        self.0
+            .fade_pause
+            .store(duration.to_bits(), Ordering::Relaxed);
+    }
+
+    /// Resume playback of a paused signal
+    pub fn resume(&mut self) {
+        self.0.paused.store(false, Ordering::Relaxed);
+        self.0.fade_pause.store(0.0f32.to_bits(), Ordering::Relaxed);
+    }
+
+    /// Silence the associated signal, without pausing or stopping it
+    ///
+    /// The signal's volume ramps down briefly to avoid a click. A muted signal continues to play,
+    /// and to be reclaimed when it finishes, so unmuting it resumes at its current position rather
+    /// than where it was muted.
+    pub fn mute(&mut self) {
+        self.0.muted.store(true, Ordering::Relaxed);
+    }
+
+    /// Restore the volume of a muted signal
+    pub fn unmute(&mut self) {
+        self.0.muted.store(false, Ordering::Relaxed);
+    }
+
+    /// Whether the signal is muted, by [`mute`](Self::mute) or [`MixerControl::solo`]
+    pub fn is_muted(&self) -> bool {
+        self.0.muted.load(Ordering::Relaxed)
+    }
+
+    /// Whether the signal is paused, or fading out to be paused
+    pub fn is_paused(&self) -> bool {
+        self.0.paused.load(Ordering::Relaxed) || self.0.fade_pause.load(Ordering::Relaxed) != 0
+    }
+
+    /// Whether the signal's playback has halted
+    ///
+    /// Set by `stop`, by the completion of a fade started by `stop_with_fade`, and by signals
+    /// naturally finishing.
     pub fn is_stopped(&self) -> bool {
-        self.0.load(Ordering::Relaxed)
+        self.0.stop.load(Ordering::Relaxed)
+    }
+
+    /// Whether the signal itself has finished, as reported by [`Signal::is_finished`]
+    ///
+    /// Unlike [`is_stopped`](Self::is_stopped), not set by stopping the signal early. Updated by the
+    /// audio thread, so lags behind the signal's actual completion by up to one block.
+    pub fn is_finished(&self) -> bool {
+        self.0.finished.load(Ordering::Relaxed)
     }
 }
 
+struct Shared {
+    stop: AtomicBool,
+    finished: AtomicBool,
+    paused: AtomicBool,
+    muted: AtomicBool,
+    /// Duration of the requested fade out, or 0 if none has been requested
+    fade_out: AtomicU32,
+    /// Duration of the requested fade out before pausing, or 0 if the signal should play
+    fade_pause: AtomicU32,
+}
+
 struct MixedSignal<T: ?Sized> {
-    stop: Arc<AtomicBool>,
+    shared: Arc<Shared>,
+    /// Value of the mixer's epoch when the signal was played
+    epoch: usize,
+    /// Mixer time at which to begin playback
+    start: f64,
+    /// Beats to the grid on which playback should begin, or 0 if `start` is already determined
+    quantize: f64,
+    /// Volume, ramped to implement fades
+    gain: Smoothed<f32>,
+    /// Duration of the fade currently in progress
+    fade: f32,
+    fading_out: bool,
+    /// Whether the signal is fading out to be paused, or has finished doing so
+    pausing: bool,
+    /// Volume, ramped to implement muting independently of fades
+    mute: Smoothed<f32>,
+    muted: bool,
     inner: T,
 }
 
 impl<T> MixedSignal<T> {
-    fn new(signal: T) -> Self {
+    fn new(signal: T, start: f64, fade_in: f32, epoch: usize) -> Self {
+        let mut gain = Smoothed::new(1.0);
+        if fade_in > 0.0 {
+            gain = Smoothed::new(0.0);
+            gain.set(1.0);
+        }
         Self {
-            stop: Arc::new(AtomicBool::new(false)),
+            shared: Arc::new(Shared {
+                stop: AtomicBool::new(false),
+                finished: AtomicBool::new(false),
+                paused: AtomicBool::new(false),
+                muted: AtomicBool::new(false),
+                fade_out: AtomicU32::new(0.0f32.to_bits()),
+                fade_pause: AtomicU32::new(0.0f32.to_bits()),
+            }),
+            epoch,
+            start,
+            quantize: 0.0,
+            gain,
+            fade: fade_in,
+            fading_out: false,
+            pausing: false,
+            mute: Smoothed::new(1.0),
+            muted: false,
             inner: signal,
         }
     }
 }
 
+impl<T: Signal + ?Sized> MixedSignal<T>
+where
+    T::Frame: Frame,
+{
+    /// Sample into `staging`, then mix into `out`
+    fn mix(&mut self, interval: f32, staging: &mut [T::Frame], out: &mut [T::Frame]) {
+        let muted = self.shared.muted.load(Ordering::Relaxed);
+        if muted != self.muted {
+            self.muted = muted;
+            self.mute.set(if muted { 0.0 } else { 1.0 });
+        }
+        let mut iter = out.iter_mut();
+        while iter.len() > 0 {
+            let settled = self.gain.progress() >= 1.0 && self.mute.progress() >= 1.0;
+            if settled && self.gain.get() <= 0.0 {
+                // A fade out or pause completed partway through the block
+                return;
+            }
+            let n = iter.len().min(staging.len());
+            let staging = &mut staging[..n];
+            self.inner.sample(interval, staging);
+            if settled {
+                if self.muted {
+                    // Keep playing, but contribute nothing
+                    iter.nth(n - 1);
+                    continue;
+                }
+                for (staged, o) in staging.iter().zip(&mut iter) {
+                    *o = frame::mix(o, staged);
+                }
+            } else {
+                for (staged, o) in staging.iter().zip(&mut iter) {
+                    let gain = self.gain.get() * self.mute.get();
+                    *o = frame::mix(o, &frame::scale(staged, gain));
+                    self.gain.advance(interval / self.fade);
+                    self.mute.advance(interval / MUTE_FADE);
+                }
+            }
+        }
+    }
+}
+
 /// A [`Signal`] that mixes a dynamic set of [`Signal`]s
 pub struct Mixer<T> {
     recv: Inner<T>,
@@ -68,13 +403,44 @@ where
 {
     /// Construct a new mixer
     pub fn new() -> (MixerControl<T>, Self) {
+        Self::with_buffer_size(DEFAULT_BUFFER_SIZE)
+    }
+
+    /// Construct a new mixer which samples its signals `frames` at a time
+    ///
+    /// Larger buffers improve throughput when sampling large blocks, while smaller ones reduce
+    /// memory use. The default is 1024 frames.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `frames` is zero.
+    pub fn with_buffer_size(frames: usize) -> (MixerControl<T>, Self) {
+        assert!(frames > 0, "buffer size must be nonzero");
         let (handle, set) = set();
+        let (clock_send, clock_recv) = swap::swap(Clock::default);
+        let epoch = Arc::new(AtomicUsize::new(0));
+        let tempo = Tempo::new(DEFAULT_TEMPO);
+        let peak = Arc::new(AtomicU32::new(0.0f32.to_bits()));
         (
-            MixerControl(handle),
+            MixerControl {
+                set: handle,
+                clock: clock_recv,
+                epoch: epoch.clone(),
+                tempo: tempo.clone(),
+                peak: peak.clone(),
+                voices: Vec::new(),
+            },
             Self {
                 recv: Inner {
                     set,
-                    buffer: vec![T::ZERO; 1024].into(),
+                    buffer: vec![T::ZERO; frames].into(),
+                    time: 0.0,
+                    beats: 0.0,
+                    frames: 0,
+                    clock: clock_send,
+                    epoch,
+                    tempo,
+                    peak,
                 },
             },
         )
@@ -84,6 +450,25 @@ where
 struct Inner<T> {
     set: Set<ErasedSignal<T>>,
     buffer: Box<[T]>,
+    /// Seconds of output produced so far
+    time: f64,
+    /// Beats of output produced so far
+    beats: f64,
+    /// Frames of output produced so far
+    frames: u64,
+    clock: swap::Sender<Clock>,
+    epoch: Arc<AtomicUsize>,
+    tempo: Tempo,
+    peak: Arc<AtomicU32>,
+}
+
+/// Position of a [`Mixer`] in its output
+#[derive(Debug, Copy, Clone, Default)]
+struct Clock {
+    /// Seconds
+    time: f64,
+    beats: f64,
+    frames: u64,
 }
 
 impl<T: Frame> Signal for Mixer<T> {
@@ -97,34 +482,115 @@ impl<T: Frame> Signal for Mixer<T> {
             *o = T::ZERO;
         }
 
+        let epoch = this.epoch.load(Ordering::Relaxed);
+        // Seconds per beat
+        let beat = 60.0 / f64::from(this.tempo.bpm());
         for i in (0..this.set.len()).rev() {
             let signal = &mut this.set[i];
-            if signal.stop.load(Ordering::Relaxed) || signal.inner.is_finished() {
-                signal.stop.store(true, Ordering::Relaxed);
+            if signal.quantize > 0.0 {
+                // Tolerate rounding error that leaves `beats` just past a grid point
+                let start =
+                    (this.beats / signal.quantize - QUANTIZE_EPSILON).ceil() * signal.quantize;
+                signal.start = this.time + (start - this.beats) * beat;
+                signal.quantize = 0.0;
+            }
+            if signal.inner.is_finished() {
+                signal.shared.finished.store(true, Ordering::Relaxed);
+            }
+            if signal.shared.stop.load(Ordering::Relaxed)
+                || signal.epoch != epoch
+                || signal.shared.finished.load(Ordering::Relaxed)
+            {
+                signal.shared.stop.store(true, Ordering::Relaxed);
                 this.set.remove(i);
                 continue;
             }
-
-            // Sample into `buffer`, then mix into `out`
-            let mut iter = out.iter_mut();
-            while iter.len() > 0 {
-                let n = iter.len().min(this.buffer.len());
-                let staging = &mut this.buffer[..n];
-                signal.inner.sample(interval, staging);
-                for (staged, o) in staging.iter().zip(&mut iter) {
-                    *o = frame::mix(o, staged);
+            let fade_out = f32::from_bits(signal.shared.fade_out.load(Ordering::Relaxed));
+            let paused = signal.shared.paused.load(Ordering::Relaxed);
+            if fade_out > 0.0 && (paused || (signal.pausing && signal.gain.progress() >= 1.0)) {
+                // Already silent, so there's nothing to fade out
+                signal.shared.stop.store(true, Ordering::Relaxed);
+                this.set.remove(i);
+                continue;
+            }
+            if paused {
+                continue;
+            }
+            if !signal.fading_out {
+                let fade_pause = f32::from_bits(signal.shared.fade_pause.load(Ordering::Relaxed));
+                if fade_out > 0.0 {
+                    signal.fading_out = true;
+                    signal.fade = fade_out;
+                    signal.gain.set(0.0);
+                } else if (fade_pause > 0.0) != signal.pausing {
+                    // Fade out when paused, and back in when resumed
+                    signal.pausing = fade_pause > 0.0;
+                    if signal.pausing {
+                        signal.fade = fade_pause;
+                    }
+                    signal.gain.set(if signal.pausing { 0.0 } else { 1.0 });
                 }
             }
+            if signal.pausing && signal.gain.progress() >= 1.0 {
+                // Silenced by a fade, so suspend sampling
+                continue;
+            }
+
+            // Skip output frames that precede the signal's scheduled start
+            let skip = if signal.start > this.time {
+                ((signal.start - this.time) / f64::from(interval)).ceil() as usize
+            } else {
+                0
+            };
+            if skip >= out.len() {
+                continue;
+            }
+
+            signal.mix(interval, &mut this.buffer, &mut out[skip..]);
+            if signal.fading_out && signal.gain.progress() >= 1.0 {
+                signal.shared.stop.store(true, Ordering::Relaxed);
+                this.set.remove(i);
+            }
         }
+
+        let peak = out
+            .iter()
+            .flat_map(|x| x.channels())
+            .fold(0.0f32, |acc, x| acc.max(x.abs()));
+        this.peak.store(peak.to_bits(), Ordering::Relaxed);
+
+        let elapsed = f64::from(interval) * out.len() as f64;
+        this.time += elapsed;
+        this.beats += elapsed / beat;
+        this.frames += out.len() as u64;
+        *this.clock.pending() = Clock {
+            time: this.time,
+            beats: this.beats,
+            frames: this.frames,
+        };
+        this.clock.flush();
     }
 }
 
-type ErasedSignal<T> = Box<MixedSignal<dyn Signal<Frame = T>>>;
+/// Fraction of a subdivision within which [`MixerControl::play_quantized`] considers the mixer to
+/// be on the grid
+const QUANTIZE_EPSILON: f64 = 1e-6;
+
+/// Beats per minute assumed by [`MixerControl::play_quantized`] until the tempo is set
+const DEFAULT_TEMPO: f32 = 120.0;
+
+/// Seconds over which [`Mixed::mute`] and [`Mixed::unmute`] ramp a signal's volume
+const MUTE_FADE: f32 = 0.005;
+
+/// Number of frames [`Mixer::new`] samples its signals at a time
+const DEFAULT_BUFFER_SIZE: usize = 1024;
+
+type ErasedSignal<T> = MixedSignal<Box<dyn Signal<Frame = T> + Send>>;
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{Frames, FramesSignal};
+    use crate::{Constant, Frames, FramesSignal};
 
     #[test]
     fn is_stopped() {
@@ -144,5 +610,225 @@ mod tests {
 
         mixer.sample(0.0, &mut out);
         assert!(handle.is_stopped());
+        assert!(handle.is_finished());
+    }
+
+    #[test]
+    fn play_boxed() {
+        let (mut mixer_control, mut mixer) = Mixer::new();
+        let voices: [Box<dyn Signal<Frame = f32> + Send>; 2] =
+            [Box::new(Constant(1.0)), Box::new(Constant(2.0))];
+        for voice in voices {
+            mixer_control.play_boxed(voice);
+        }
+        let mut out = [0.0; 2];
+        mixer.sample(1.0, &mut out);
+        assert_eq!(out, [3.0; 2]);
+    }
+
+    #[test]
+    fn peak() {
+        let (mut mixer_control, mut mixer) = Mixer::<[f32; 2]>::new();
+        assert_eq!(mixer_control.peak(), 0.0);
+        mixer_control.play(Constant([0.5, -1.5]));
+        let mut out = [[0.0; 2]; 2];
+        mixer.sample(1.0, &mut out);
+        assert_eq!(mixer_control.peak(), 1.5);
+    }
+
+    #[test]
+    fn solo() {
+        let (mut mixer_control, mut mixer) = Mixer::new();
+        let a = mixer_control.play(Constant(1.0));
+        let mut b = mixer_control.play(Constant(2.0));
+        mixer_control.solo(&a);
+        assert!(!a.is_muted() && b.is_muted());
+        let mut out = [0.0; 4];
+        mixer.sample(MUTE_FADE / 2.0, &mut out);
+        assert_eq!(out, [3.0, 2.0, 1.0, 1.0]);
+
+        b.unmute();
+        mixer.sample(MUTE_FADE / 2.0, &mut out);
+        assert_eq!(out, [1.0, 2.0, 3.0, 3.0]);
+    }
+
+    #[test]
+    fn fade() {
+        let (mut mixer_control, mut mixer) = Mixer::new();
+        let mut handle = mixer_control.play_with_fade(Constant(1.0), 2.0);
+        let mut out = [0.0; 6];
+        mixer.sample(0.5, &mut out);
+        assert_eq!(out, [0.0, 0.25, 0.5, 0.75, 1.0, 1.0]);
+
+        handle.stop_with_fade(1.0);
+        mixer.sample(0.25, &mut out);
+        assert_eq!(out, [1.0, 0.75, 0.5, 0.25, 0.0, 0.0]);
+        assert!(handle.is_stopped());
+        assert!(!handle.is_finished());
+    }
+
+    #[test]
+    fn fade_longer_than_buffer() {
+        let (mut mixer_control, mut mixer) = Mixer::with_buffer_size(2);
+        let mut handle = mixer_control.play(Constant(1.0));
+        handle.stop_with_fade(1.0);
+        let mut out = [0.0; 6];
+        mixer.sample(0.5, &mut out);
+        assert_eq!(out, [1.0, 0.5, 0.0, 0.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn pause() {
+        let (mut mixer_control, mut mixer) = Mixer::new();
+        let (_, signal) = FramesSignal::new(Frames::from_slice(1, &[1.0, 2.0, 3.0]), 0.0);
+        let mut handle = mixer_control.play(signal);
+        let mut out = [0.0; 1];
+        mixer.sample(1.0, &mut out);
+        assert_eq!(out, [1.0]);
+
+        handle.pause();
+        mixer.sample(1.0, &mut out);
+        assert_eq!(out, [0.0]);
+        assert!(!handle.is_stopped());
+
+        handle.resume();
+        mixer.sample(1.0, &mut out);
+        assert_eq!(out, [2.0]);
+    }
+
+    #[test]
+    fn pause_with_fade() {
+        let (mut mixer_control, mut mixer) = Mixer::new();
+        let mut handle = mixer_control.play(Constant(1.0));
+        handle.pause_with_fade(1.0);
+        assert!(handle.is_paused());
+        let mut out = [0.0; 6];
+        mixer.sample(0.25, &mut out);
+        assert_eq!(out, [1.0, 0.75, 0.5, 0.25, 0.0, 0.0]);
+        mixer.sample(0.25, &mut out);
+        assert_eq!(out, [0.0; 6]);
+        assert!(!handle.is_stopped());
+
+        handle.resume();
+        assert!(!handle.is_paused());
+        mixer.sample(0.25, &mut out);
+        assert_eq!(out, [0.0, 0.25, 0.5, 0.75, 1.0, 1.0]);
+    }
+
+    #[test]
+    fn stop_with_fade_while_paused() {
+        let (mut mixer_control, mut mixer) = Mixer::new();
+        let mut a = mixer_control.play(Constant(1.0));
+        let mut b = mixer_control.play(Constant(1.0));
+        a.pause();
+        b.pause_with_fade(0.5);
+        let mut out = [0.0; 2];
+        mixer.sample(0.5, &mut out);
+        assert_eq!(out, [1.0, 0.0]);
+
+        a.stop_with_fade(1.0);
+        b.stop_with_fade(1.0);
+        mixer.sample(0.5, &mut out);
+        assert_eq!(out, [0.0; 2]);
+        assert!(a.is_stopped());
+        assert!(b.is_stopped());
+        assert_eq!(mixer_control.voice_count(), 0);
+    }
+
+    #[test]
+    fn pause_fade_longer_than_buffer() {
+        let (mut mixer_control, mut mixer) = Mixer::with_buffer_size(2);
+        let mut handle = mixer_control.play(Constant(1.0));
+        handle.pause_with_fade(1.0);
+        let mut out = [0.0; 6];
+        mixer.sample(0.5, &mut out);
+        assert_eq!(out, [1.0, 0.5, 0.0, 0.0, 0.0, 0.0]);
+        assert!(!handle.is_stopped());
+    }
+
+    #[test]
+    fn clear() {
+        let (mut mixer_control, mut mixer) = Mixer::new();
+        let old = mixer_control.play(Constant(1.0));
+        mixer_control.play(Constant(1.0));
+        assert_eq!(mixer_control.voice_count(), 2);
+        mixer_control.clear();
+        let new = mixer_control.play(Constant(1.0));
+        let mut out = [0.0; 2];
+        mixer.sample(1.0, &mut out);
+        assert_eq!(out, [1.0; 2]);
+        assert!(old.is_stopped());
+        assert!(!new.is_stopped());
+        assert_eq!(mixer_control.voice_count(), 1);
+    }
+
+    #[test]
+    fn play_at() {
+        let (mut mixer_control, mut mixer) = Mixer::new();
+        let mut out = [0.0; 4];
+        mixer.sample(1.0, &mut out);
+        assert_eq!(mixer_control.now(), 4.0);
+        assert_eq!(mixer_control.frames_processed(), 4);
+
+        mixer_control.play_at(Constant(1.0), 5.5);
+        mixer.sample(1.0, &mut out);
+        assert_eq!(out, [0.0; 4]);
+        mixer.sample(1.0, &mut out);
+        assert_eq!(out, [0.0, 0.0, 1.0, 1.0]);
+
+        mixer_control.play_at(Constant(1.0), -1.0);
+        mixer.sample(1.0, &mut out);
+        assert_eq!(out, [2.0; 4]);
+    }
+
+    #[test]
+    fn play_quantized() {
+        let (mut mixer_control, mut mixer) = Mixer::new();
+        mixer_control.set_tempo(30.0);
+        let mut out = [0.0; 4];
+        mixer.sample(0.25, &mut out);
+        assert_eq!(mixer_control.beats(), 0.5);
+
+        // The next bar of four beats begins at 8 seconds
+        mixer_control.play_quantized(Constant(1.0), 4.0);
+        mixer.sample(1.0, &mut out);
+        assert_eq!(out, [0.0; 4]);
+        mixer.sample(1.0, &mut out);
+        assert_eq!(out, [0.0, 0.0, 0.0, 1.0]);
+        assert_eq!(mixer_control.beats(), 4.5);
+    }
+
+    #[test]
+    fn play_quantized_on_grid() {
+        let (mut mixer_control, mut mixer) = Mixer::new();
+        mixer_control.set_tempo(60.0);
+        let mut out = [0.0; 3];
+        // Accumulates a tiny rounding error past 0.3 beats
+        mixer.sample(0.1, &mut out);
+        assert_ne!(mixer_control.beats(), 0.3);
+        mixer_control.play_quantized(Constant(1.0), 0.1);
+        mixer.sample(0.1, &mut out);
+        assert_eq!(out, [1.0; 3]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn zero_tempo() {
+        let (mut mixer_control, _) = Mixer::<f32>::new();
+        mixer_control.set_tempo(0.0);
+    }
+
+    #[test]
+    fn buffer_size() {
+        const DATA: &[f32] = &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0];
+        let mut outs = [[0.0; 8]; 2];
+        for (out, frames) in outs.iter_mut().zip([3, DEFAULT_BUFFER_SIZE]) {
+            let (mut mixer_control, mut mixer) = Mixer::with_buffer_size(frames);
+            mixer_control.play(FramesSignal::from(Frames::from_slice(1, DATA)));
+            mixer_control.play(Constant(1.0));
+            mixer.sample(1.0, out);
+        }
+        assert_eq!(outs[0], outs[1]);
+        assert_eq!(outs[0], [2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 1.0]);
     }
 }
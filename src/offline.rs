@@ -0,0 +1,124 @@
+use alloc::{vec, vec::Vec};
+
+use crate::{
+    dither::{self, DitherState},
+    math::Float,
+    Frame, Signal,
+};
+
+/// Renders signals to memory faster than real time, e.g. for tests or baking assets
+///
+/// The canonical non-realtime counterpart to [`run`](crate::run): drives a signal at a fixed rate
+/// in blocks of a fixed size, exactly as an audio callback would. Output is deterministic, and
+/// depends on the block size only to the extent that the signal does; for signals that process in
+/// fixed-size sub-blocks, such as a [`SpatialScene`](crate::SpatialScene) with
+/// [`with_fixed_blocks`](crate::SpatialScene::with_fixed_blocks), it's identical for any block
+/// size.
+///
+/// ```
+/// # use oddio::{Offline, Sine};
+/// let frames = Offline::new(44100).render_to_vec(&mut Sine::new(0.0, 440.0), 0.5);
+/// assert_eq!(frames.len(), 22050);
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct Offline {
+    rate: u32,
+    block_size: usize,
+}
+
+impl Offline {
+    /// Render at `rate` frames per second, in blocks of 512 frames
+    pub fn new(rate: u32) -> Self {
+        Self {
+            rate,
+            block_size: DEFAULT_BLOCK_SIZE,
+        }
+    }
+
+    /// Sample signals `frames` at a time, like an audio callback with that buffer size
+    ///
+    /// # Panics
+    ///
+    /// Panics if `frames` is zero.
+    pub fn with_block_size(mut self, frames: usize) -> Self {
+        assert!(frames > 0, "block size must be nonzero");
+        self.block_size = frames;
+        self
+    }
+
+    /// Fill `out` with frames from `signal`
+    pub fn render<S: Signal + ?Sized>(&self, signal: &mut S, out: &mut [S::Frame]) {
+        for block in out.chunks_mut(self.block_size) {
+            crate::run(signal, self.rate, block);
+        }
+    }
+
+    /// Collect `duration` seconds of frames from `signal`, rounded to a whole number of frames
+    pub fn render_to_vec<S: Signal + ?Sized>(&self, signal: &mut S, duration: f32) -> Vec<S::Frame>
+    where
+        S::Frame: Frame + Clone,
+    {
+        let mut out = vec![S::Frame::ZERO; self.frames(duration)];
+        self.render(signal, &mut out);
+        out
+    }
+
+    /// Collect `duration` seconds of frames from `signal` as interleaved 16-bit samples, dithered
+    /// with `state` as by [`dither::to_i16`]
+    pub fn render_to_i16<S: Signal + ?Sized>(
+        &self,
+        signal: &mut S,
+        duration: f32,
+        state: &mut DitherState,
+    ) -> Vec<i16>
+    where
+        S::Frame: Frame + Clone,
+    {
+        let channels = S::Frame::ZERO.channels().len();
+        let mut block = vec![S::Frame::ZERO; self.block_size];
+        let mut out = vec![0; self.frames(duration) * channels];
+        for samples in out.chunks_mut(self.block_size * channels) {
+            let block = &mut block[..samples.len() / channels];
+            crate::run(signal, self.rate, block);
+            dither::to_i16(block, state, samples);
+        }
+        out
+    }
+
+    /// Number of frames spanning `duration` seconds
+    fn frames(&self, duration: f32) -> usize {
+        (duration * self.rate as f32 + 0.5).floor() as usize
+    }
+}
+
+/// Number of frames [`Offline::new`] samples signals at a time
+const DEFAULT_BLOCK_SIZE: usize = 512;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Sine, SpatialOptions, SpatialScene};
+
+    #[test]
+    fn block_size_independent() {
+        let render = |block_size: usize| {
+            let (mut control, scene) = SpatialScene::new();
+            let mut scene = scene.with_fixed_blocks(64);
+            control.play(
+                Sine::new(0.0, 440.0),
+                SpatialOptions {
+                    position: [-5.0, 0.0, -1.0].into(),
+                    velocity: [10.0, 0.0, 0.0].into(),
+                    ..SpatialOptions::default()
+                },
+            );
+            Offline::new(44100)
+                .with_block_size(block_size)
+                .render_to_i16(&mut scene, 0.01, &mut DitherState::new())
+        };
+        let a = render(7);
+        assert_eq!(a.len(), 2 * 441);
+        assert!(a.iter().any(|&x| x != 0));
+        assert_eq!(a, render(512));
+    }
+}
@@ -0,0 +1,149 @@
+use alloc::sync::Arc;
+use core::{
+    f32::consts::TAU,
+    sync::atomic::{AtomicU32, Ordering},
+};
+
+use crate::{math::Float, Frame, Reset, Seek, Signal};
+
+/// Attenuates high or low frequencies with a single pole
+///
+/// Much cheaper than a biquad filter like [`LowShelf`](crate::LowShelf), at the cost of a gentle
+/// 6dB per octave slope. Useful for taming harsh high frequencies, or removing rumble.
+pub struct OnePole<T: Signal + ?Sized> {
+    cutoff: Arc<AtomicU32>,
+    high_pass: bool,
+    /// Low-pass filtered signal
+    state: T::Frame,
+    inner: T,
+}
+
+impl<T: Signal> OnePole<T>
+where
+    T::Frame: Frame,
+{
+    /// Attenuate frequencies of `signal` above `cutoff` Hz
+    pub fn low_pass(signal: T, cutoff: f32) -> (OnePoleControl, Self) {
+        Self::new(signal, cutoff, false)
+    }
+
+    /// Attenuate frequencies of `signal` below `cutoff` Hz
+    pub fn high_pass(signal: T, cutoff: f32) -> (OnePoleControl, Self) {
+        Self::new(signal, cutoff, true)
+    }
+
+    fn new(signal: T, cutoff: f32, high_pass: bool) -> (OnePoleControl, Self) {
+        let signal = Self {
+            cutoff: Arc::new(AtomicU32::new(cutoff.to_bits())),
+            high_pass,
+            state: T::Frame::ZERO,
+            inner: signal,
+        };
+        let control = OnePoleControl(signal.cutoff.clone());
+        (control, signal)
+    }
+}
+
+impl<T: Signal> Signal for OnePole<T>
+where
+    T::Frame: Frame,
+{
+    type Frame = T::Frame;
+
+    fn sample(&mut self, interval: f32, out: &mut [T::Frame]) {
+        self.inner.sample(interval, out);
+        let cutoff = f32::from_bits(self.cutoff.load(Ordering::Relaxed));
+        let alpha = alpha(cutoff, interval);
+        for x in out {
+            for (x, y) in x.channels_mut().iter_mut().zip(self.state.channels_mut()) {
+                *y += alpha * (*x - *y);
+                *x = if self.high_pass { *x - *y } else { *y };
+            }
+        }
+    }
+
+    fn is_finished(&self) -> bool {
+        self.inner.is_finished()
+    }
+
+    fn duration_remaining(&self) -> Option<f32> {
+        self.inner.duration_remaining()
+    }
+}
+
+impl<T: Seek> Seek for OnePole<T>
+where
+    T::Frame: Frame,
+{
+    fn seek(&mut self, seconds: f32) {
+        self.inner.seek(seconds);
+    }
+}
+
+impl<T: Reset> Reset for OnePole<T>
+where
+    T::Frame: Frame,
+{
+    fn reset(&mut self) {
+        self.state = T::Frame::ZERO;
+        self.inner.reset();
+    }
+}
+
+/// Thread-safe control for a [`OnePole`] filter
+pub struct OnePoleControl(Arc<AtomicU32>);
+
+impl OnePoleControl {
+    /// Get the cutoff frequency in Hz
+    pub fn cutoff(&self) -> f32 {
+        f32::from_bits(self.0.load(Ordering::Relaxed))
+    }
+
+    /// Set the cutoff frequency in Hz
+    pub fn set_cutoff(&mut self, hz: f32) {
+        self.0.store(hz.to_bits(), Ordering::Relaxed);
+    }
+}
+
+/// Smoothing coefficient of a one-pole low-pass filter with cutoff frequency `cutoff` Hz, given
+/// `interval` seconds between samples
+pub(crate) fn alpha(cutoff: f32, interval: f32) -> f32 {
+    1.0 - (-TAU * cutoff * interval).exp()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_util::peak, Constant, Sample, SignalExt, Sine};
+
+    const RATE: f32 = 44100.0;
+
+    #[test]
+    fn low_pass() {
+        let (_, mut low) = OnePole::low_pass(Sine::new(0.0, 100.0), 1000.0);
+        assert!(peak(&mut low, RATE) > 0.99);
+        let (_, mut high) = OnePole::low_pass(Sine::new(0.0, 10_000.0), 1000.0);
+        assert!(peak(&mut high, RATE) < 0.15);
+    }
+
+    #[test]
+    fn high_pass() {
+        let (_, mut s) = OnePole::high_pass(Constant(1.0), 100.0);
+        assert!(peak(&mut s, RATE) < 1e-3);
+        let (_, mut s) = OnePole::high_pass(Sine::new(0.0, 10_000.0), 100.0);
+        assert!(peak(&mut s, RATE) > 0.99);
+    }
+
+    #[test]
+    fn reset() {
+        let (_, mut s) = OnePole::low_pass(Constant(1.0).gain().1, 100.0);
+        let mut first = [0.0; 64];
+        s.sample(1.0 / RATE, &mut first);
+        let mut second = [0.0; 64];
+        s.sample(1.0 / RATE, &mut second);
+        assert_ne!(first, second);
+        s.reset();
+        s.sample(1.0 / RATE, &mut second);
+        assert_eq!(first, second);
+    }
+}
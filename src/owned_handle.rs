@@ -0,0 +1,105 @@
+use core::ops::{Deref, DerefMut};
+
+use crate::{Reset, Seek, Signal};
+
+/// A signal bundled together with its control, for use on a single thread
+///
+/// Signals and their controls are normally separated so that the control can be used from another
+/// thread while the signal plays on the audio thread. For offline rendering and tests, that
+/// separation is just boilerplate. An `OwnedHandle` dereferences to the control and implements
+/// [`Signal`] itself, so both can be used through one value. Because the signal must be borrowed to
+/// be sampled, the control can't be used from elsewhere while that happens, so this is unsuitable
+/// for real-time playback.
+///
+/// ```
+/// # use oddio::{Constant, OwnedHandle, Signal, SignalExt};
+/// let mut signal = OwnedHandle::new(Constant(1.0).gain());
+/// signal.set_gain(-6.0);
+/// signal.sample(1.0 / 44100.0, &mut [0.0; 64]);
+/// ```
+pub struct OwnedHandle<C, T: ?Sized> {
+    control: C,
+    signal: T,
+}
+
+impl<C, T> OwnedHandle<C, T> {
+    /// Bundle a control with its signal, as returned by e.g. [`Gain::new`](crate::Gain::new)
+    pub fn new((control, signal): (C, T)) -> Self {
+        Self { control, signal }
+    }
+
+    /// Separate the control from its signal
+    pub fn into_inner(self) -> (C, T) {
+        (self.control, self.signal)
+    }
+}
+
+impl<C, T: ?Sized> OwnedHandle<C, T> {
+    /// Access the signal directly
+    pub fn signal(&self) -> &T {
+        &self.signal
+    }
+
+    /// Mutably access the signal directly
+    pub fn signal_mut(&mut self) -> &mut T {
+        &mut self.signal
+    }
+}
+
+impl<C, T: ?Sized> Deref for OwnedHandle<C, T> {
+    type Target = C;
+
+    fn deref(&self) -> &C {
+        &self.control
+    }
+}
+
+impl<C, T: ?Sized> DerefMut for OwnedHandle<C, T> {
+    fn deref_mut(&mut self) -> &mut C {
+        &mut self.control
+    }
+}
+
+impl<C, T: Signal + ?Sized> Signal for OwnedHandle<C, T> {
+    type Frame = T::Frame;
+
+    fn sample(&mut self, interval: f32, out: &mut [T::Frame]) {
+        self.signal.sample(interval, out);
+    }
+
+    fn is_finished(&self) -> bool {
+        self.signal.is_finished()
+    }
+
+    fn duration_remaining(&self) -> Option<f32> {
+        self.signal.duration_remaining()
+    }
+}
+
+impl<C, T: Seek + ?Sized> Seek for OwnedHandle<C, T> {
+    fn seek(&mut self, seconds: f32) {
+        self.signal.seek(seconds);
+    }
+}
+
+impl<C, T: Reset + ?Sized> Reset for OwnedHandle<C, T> {
+    fn reset(&mut self) {
+        self.signal.reset();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Constant, SignalExt};
+
+    #[test]
+    fn control() {
+        let mut signal = OwnedHandle::new(Constant(1.0).gain());
+        signal.set_smoothing(0.0);
+        signal.set_amplitude_ratio(0.5);
+        let mut out = [0.0; 2];
+        signal.sample(1.0, &mut out);
+        assert_eq!(out, [0.5; 2]);
+    }
+}
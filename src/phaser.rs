@@ -0,0 +1,280 @@
+use alloc::{boxed::Box, sync::Arc};
+use core::{
+    f32::consts::{PI, TAU},
+    sync::atomic::{AtomicU32, Ordering},
+};
+
+use crate::{
+    math::Float,
+    tempo::{NoteValue, Tempo, TempoSync},
+    Frame, Reset, Seek, Signal,
+};
+
+/// Produces a swirling effect by mixing a signal with a phase-shifted copy of itself
+///
+/// The copy passes through a cascade of first-order all-pass filters whose break frequency is swept
+/// by an LFO, forming notches that move up and down the spectrum. Every two stages add a notch.
+/// Cost is proportional to the number of stages.
+pub struct Phaser<T: Signal + ?Sized> {
+    shared: Arc<Shared>,
+    center: f32,
+    feedback: f32,
+    mix: f32,
+    /// LFO phase, in [0, 1)
+    phase: f32,
+    /// Tempo to which the LFO is synced, if any
+    sync: Option<TempoSync>,
+    stages: Box<[Stage<T::Frame>]>,
+    /// Most recent output of the last stage
+    last: T::Frame,
+    inner: T,
+}
+
+impl<T: Signal> Phaser<T>
+where
+    T::Frame: Frame,
+{
+    /// Apply a phaser to `signal`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `options.stages` is zero.
+    pub fn new(signal: T, options: PhaserOptions) -> (PhaserControl, Self) {
+        assert!(options.stages > 0, "phaser must have at least one stage");
+        let shared = Arc::new(Shared {
+            rate: AtomicU32::new(options.rate.to_bits()),
+            depth: AtomicU32::new(options.depth.to_bits()),
+        });
+        let signal = Self {
+            shared: shared.clone(),
+            center: options.center,
+            feedback: options.feedback.clamp(-MAX_FEEDBACK, MAX_FEEDBACK),
+            mix: options.mix.clamp(0.0, 1.0),
+            phase: 0.0,
+            sync: None,
+            stages: (0..options.stages)
+                .map(|_| Stage {
+                    x1: T::Frame::ZERO,
+                    y1: T::Frame::ZERO,
+                })
+                .collect(),
+            last: T::Frame::ZERO,
+            inner: signal,
+        };
+        (PhaserControl(shared), signal)
+    }
+
+    /// Sweep the notches once per `note` at `tempo`, rather than at a rate in Hz
+    ///
+    /// See [`Tempo`] for details.
+    pub fn with_tempo(mut self, tempo: Tempo, note: NoteValue) -> Self {
+        self.sync = Some(TempoSync { tempo, note });
+        self
+    }
+
+    /// Current frequency of the sweep in Hz
+    fn lfo_rate(&self) -> f32 {
+        match self.sync {
+            Some(ref sync) => sync.hz(),
+            None => f32::from_bits(self.shared.rate.load(Ordering::Relaxed)),
+        }
+    }
+}
+
+impl<T: Signal> Signal for Phaser<T>
+where
+    T::Frame: Frame,
+{
+    type Frame = T::Frame;
+
+    fn sample(&mut self, interval: f32, out: &mut [T::Frame]) {
+        self.inner.sample(interval, out);
+        let dphase = self.lfo_rate() * interval;
+        let depth = f32::from_bits(self.shared.depth.load(Ordering::Relaxed));
+        // Keep the break frequency safely below Nyquist
+        let max_frequency = 0.45 / interval;
+        for x in out {
+            let frequency =
+                (self.center * 2.0f32.powf(depth * (TAU * self.phase).sin())).min(max_frequency);
+            let t = (PI * frequency * interval).tan();
+            let a = (t - 1.0) / (t + 1.0);
+
+            let mut wet = T::Frame::ZERO;
+            for ((w, &x), &last) in wet
+                .channels_mut()
+                .iter_mut()
+                .zip(x.channels())
+                .zip(self.last.channels())
+            {
+                *w = x + self.feedback * last;
+            }
+            for stage in self.stages.iter_mut() {
+                for ((w, x1), y1) in wet
+                    .channels_mut()
+                    .iter_mut()
+                    .zip(stage.x1.channels_mut())
+                    .zip(stage.y1.channels_mut())
+                {
+                    let y = a * *w + *x1 - a * *y1;
+                    *x1 = *w;
+                    *y1 = y;
+                    *w = y;
+                }
+            }
+            self.last.channels_mut().copy_from_slice(wet.channels());
+            for (x, &w) in x.channels_mut().iter_mut().zip(wet.channels()) {
+                *x = *x * (1.0 - self.mix) + w * self.mix;
+            }
+            self.phase = (self.phase + dphase) % 1.0;
+        }
+    }
+
+    fn is_finished(&self) -> bool {
+        self.inner.is_finished()
+    }
+
+    fn duration_remaining(&self) -> Option<f32> {
+        self.inner.duration_remaining()
+    }
+}
+
+impl<T: Seek> Seek for Phaser<T>
+where
+    T::Frame: Frame,
+{
+    fn seek(&mut self, seconds: f32) {
+        self.phase = (self.phase + seconds * self.lfo_rate()).rem_euclid(1.0);
+        self.inner.seek(seconds);
+    }
+}
+
+impl<T: Reset> Reset for Phaser<T>
+where
+    T::Frame: Frame,
+{
+    fn reset(&mut self) {
+        self.phase = 0.0;
+        for stage in self.stages.iter_mut() {
+            stage.x1 = T::Frame::ZERO;
+            stage.y1 = T::Frame::ZERO;
+        }
+        self.last = T::Frame::ZERO;
+        self.inner.reset();
+    }
+}
+
+/// State of a single all-pass filter
+struct Stage<F> {
+    /// Previous input
+    x1: F,
+    /// Previous output
+    y1: F,
+}
+
+/// Configuration for a [`Phaser`], passed to [`Phaser::new`]
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PhaserOptions {
+    /// Number of all-pass filters
+    pub stages: usize,
+    /// Break frequency of the filters at the middle of the sweep, in Hz
+    pub center: f32,
+    /// Distance the break frequency sweeps above and below `center`, in octaves
+    pub depth: f32,
+    /// Frequency of the sweep in Hz
+    pub rate: f32,
+    /// Proportion of the phase-shifted signal fed back into the filters, clamped to within ±0.95
+    pub feedback: f32,
+    /// Proportion of phase-shifted signal in the output, from 0 (dry) to 1 (wet)
+    ///
+    /// Notches are deepest at 0.5.
+    pub mix: f32,
+}
+
+impl Default for PhaserOptions {
+    fn default() -> Self {
+        Self {
+            stages: 4,
+            center: 800.0,
+            depth: 2.0,
+            rate: 0.5,
+            feedback: 0.3,
+            mix: 0.5,
+        }
+    }
+}
+
+/// Thread-safe control for a [`Phaser`]
+pub struct PhaserControl(Arc<Shared>);
+
+impl PhaserControl {
+    /// Get the frequency of the sweep in Hz
+    pub fn rate(&self) -> f32 {
+        f32::from_bits(self.0.rate.load(Ordering::Relaxed))
+    }
+
+    /// Set the frequency of the sweep in Hz
+    pub fn set_rate(&mut self, hz: f32) {
+        self.0.rate.store(hz.to_bits(), Ordering::Relaxed);
+    }
+
+    /// Get the distance the break frequency sweeps above and below the center, in octaves
+    pub fn depth(&self) -> f32 {
+        f32::from_bits(self.0.depth.load(Ordering::Relaxed))
+    }
+
+    /// Set the distance the break frequency sweeps above and below the center, in octaves
+    pub fn set_depth(&mut self, octaves: f32) {
+        self.0.depth.store(octaves.to_bits(), Ordering::Relaxed);
+    }
+}
+
+struct Shared {
+    rate: AtomicU32,
+    depth: AtomicU32,
+}
+
+/// Largest magnitude of feedback around the all-pass cascade
+///
+/// The cascade has unity gain at every frequency, so any magnitude below 1 is stable; staying well
+/// clear of it keeps resonances around the notches from ringing for too long.
+const MAX_FEEDBACK: f32 = 0.95;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Sine;
+
+    const RATE: u32 = 48000;
+
+    /// Peak level of a `hz` tone through a phaser whose LFO has been advanced by `seek` seconds
+    fn level(hz: f32, seek: f32) -> f32 {
+        let options = PhaserOptions {
+            center: 1000.0,
+            depth: 1.0,
+            rate: 0.1,
+            feedback: 0.0,
+            ..PhaserOptions::default()
+        };
+        let (_, mut phaser) = Phaser::new(Sine::new(0.0, hz), options);
+        phaser.seek(seek);
+        let mut out = [0.0; RATE as usize / 20];
+        phaser.sample(1.0 / RATE as f32, &mut out);
+        // Skip the filters' transient
+        out[out.len() / 2..]
+            .iter()
+            .fold(0.0f32, |acc, x| acc.max(x.abs()))
+    }
+
+    #[test]
+    fn notch_follows_lfo() {
+        // Four stages shift a tone at tan(pi/8) times the break frequency by half a cycle
+        let ratio = (PI / 8.0).tan();
+        // At the start of the sweep, the break frequency is the center
+        assert!(level(1000.0 * ratio, 0.0) < 0.05);
+        assert!(level(2000.0 * ratio, 0.0) > 0.5);
+        // A quarter of the way through, it's an octave higher
+        assert!(level(1000.0 * ratio, 2.5) > 0.5);
+        assert!(level(2000.0 * ratio, 2.5) < 0.05);
+    }
+}
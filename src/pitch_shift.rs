@@ -0,0 +1,146 @@
+use alloc::sync::Arc;
+use core::{
+    slice,
+    sync::atomic::{AtomicU32, Ordering},
+};
+
+use crate::{math::Float, ring::Ring, Sample, Signal};
+
+/// Shifts the pitch of a signal without changing its duration
+///
+/// Implemented by crossfading between two taps on a delay line which sweep through a window at a
+/// rate chosen to produce the desired pitch. Cheap and simple, but produces some warbling, which
+/// grows more pronounced as the pitch is shifted further. Shorter windows are more responsive but
+/// sound rougher.
+///
+/// Introduces latency of up to `window` seconds, averaging half that.
+pub struct PitchShift<T: ?Sized> {
+    semitones: Arc<AtomicU32>,
+    rate: u32,
+    window: f32,
+    /// Position of the first tap within the window, in [0, 1)
+    phase: f32,
+    /// Seconds since `inner` finished
+    tail: f32,
+    queue: Ring,
+    inner: T,
+}
+
+impl<T> PitchShift<T> {
+    /// Apply dynamic pitch shifting to `signal`
+    ///
+    /// `signal` is sampled at `rate` into a delay line spanning `window` seconds. 0.05 is a
+    /// reasonable starting point.
+    pub fn new(signal: T, rate: u32, window: f32) -> (PitchShiftControl, Self) {
+        let signal = Self {
+            semitones: Arc::new(AtomicU32::new(0.0f32.to_bits())),
+            rate,
+            window,
+            phase: 0.0,
+            tail: 0.0,
+            queue: Ring::new((window * rate as f32).ceil() as usize + MAX_CHUNK + 4),
+            inner: signal,
+        };
+        let control = PitchShiftControl(signal.semitones.clone());
+        (control, signal)
+    }
+}
+
+impl<T: Signal<Frame = Sample>> Signal for PitchShift<T> {
+    type Frame = Sample;
+
+    fn sample(&mut self, interval: f32, out: &mut [Sample]) {
+        let semitones = f32::from_bits(self.semitones.load(Ordering::Relaxed));
+        let ratio = 2.0f32.powf(semitones / 12.0);
+        // Taps move at `ratio` times the speed of the signal, so their delay changes by the
+        // difference each second
+        let dphase = (1.0 - ratio) * interval / self.window;
+        // Leave room to interpolate past the most recently written sample
+        let min_delay = 2.0 / self.rate as f32;
+        let chunk_size = ((MAX_CHUNK as f32 / (interval * self.rate as f32)) as usize).max(1);
+
+        for chunk in out.chunks_mut(chunk_size) {
+            let elapsed = interval * chunk.len() as f32;
+            self.queue.write(&mut self.inner, self.rate, elapsed);
+            for (i, o) in chunk.iter_mut().enumerate() {
+                let t = i as f32 * interval - elapsed - min_delay;
+                *o = 0.0;
+                for &phase in &[self.phase, (self.phase + 0.5) % 1.0] {
+                    let mut x = 0.0;
+                    self.queue.sample(
+                        self.rate,
+                        t - phase * self.window,
+                        interval,
+                        slice::from_mut(&mut x),
+                    );
+                    // Triangular windows offset by half a period sum to 1
+                    *o += x * (1.0 - (2.0 * phase - 1.0).abs());
+                }
+                self.phase = (self.phase + dphase).rem_euclid(1.0);
+            }
+        }
+
+        if self.inner.is_finished() {
+            self.tail += interval * out.len() as f32;
+        }
+    }
+
+    fn is_finished(&self) -> bool {
+        self.inner.is_finished() && self.tail > self.window + 2.0 / self.rate as f32
+    }
+
+    fn duration_remaining(&self) -> Option<f32> {
+        let tail = (self.window + 2.0 / self.rate as f32 - self.tail).max(0.0);
+        Some(self.inner.duration_remaining()? + tail)
+    }
+}
+
+/// Thread-safe control for a [`PitchShift`] filter
+pub struct PitchShiftControl(Arc<AtomicU32>);
+
+impl PitchShiftControl {
+    /// Get the current pitch shift in semitones
+    pub fn semitones(&self) -> f32 {
+        f32::from_bits(self.0.load(Ordering::Relaxed))
+    }
+
+    /// Shift pitch by `semitones`
+    ///
+    /// Positive values raise the pitch, and negative values lower it. 12 semitones make an octave.
+    pub fn set_semitones(&mut self, semitones: f32) {
+        self.0.store(semitones.to_bits(), Ordering::Relaxed);
+    }
+}
+
+/// Maximum number of samples written to the delay line at once
+const MAX_CHUNK: usize = 256;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_util::zero_crossings, Constant, Sine};
+
+    const RATE: u32 = 8000;
+
+    #[test]
+    fn preserves_dc() {
+        let (mut c, mut s) = PitchShift::new(Constant(1.0), RATE, 0.05);
+        c.set_semitones(7.0);
+        let mut buf = [0.0; 1024];
+        s.sample(1.0 / RATE as f32, &mut buf);
+        for &x in &buf[512..] {
+            assert!((x - 1.0).abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn octave_up() {
+        let (mut c, mut s) = PitchShift::new(Sine::new(0.0, 100.0), RATE, 0.05);
+        c.set_semitones(12.0);
+        let mut buf = [0.0; RATE as usize];
+        s.sample(1.0 / RATE as f32, &mut buf);
+        // Half a second of 200Hz crosses zero about 200 times. Splicing adds some noise.
+        let n = zero_crossings(&buf[RATE as usize / 2..]);
+        assert!((190..=230).contains(&n), "{} crossings", n);
+    }
+}
@@ -0,0 +1,163 @@
+use alloc::boxed::Box;
+
+use crate::{
+    frame,
+    set::{set, Set, SetHandle},
+    Frame, Seek, Signal,
+};
+
+/// Dynamic audio that can be scrubbed through while it's being recorded
+///
+/// Like [`Stream`](crate::Stream), frames are appended through a [`RecordingControl`] without
+/// either thread waiting for the other. Unlike `Stream`, played frames are retained, so the
+/// recording supports [`Seek`] over everything written so far, e.g. to replay the last few seconds
+/// of microphone input. Seeking beyond the most recently written frame stops there, and playback
+/// that catches up with the recording outputs silence until more frames arrive.
+///
+/// Frames are never discarded, so memory use grows with the length of the recording, plus a small
+/// overhead per [`write`](RecordingControl::write) call. The audio thread never allocates or frees
+/// memory.
+pub struct Recording<T> {
+    rate: u32,
+    blocks: Set<Block<T>>,
+    /// Number of frames received
+    len: usize,
+    /// Playback position, in frames
+    t: f64,
+}
+
+impl<T> Recording<T> {
+    /// Construct an empty recording of audio at `rate` frames per second
+    pub fn new(rate: u32) -> (RecordingControl<T>, Self) {
+        let (handle, blocks) = set();
+        let control = RecordingControl {
+            blocks: handle,
+            len: 0,
+            rate,
+        };
+        let signal = Self {
+            rate,
+            blocks,
+            len: 0,
+            t: 0.0,
+        };
+        (control, signal)
+    }
+
+    /// Account for newly written blocks
+    fn update(&mut self) {
+        self.blocks.update();
+        self.len = self
+            .blocks
+            .last()
+            .map_or(0, |block| block.start + block.frames.len());
+    }
+
+    /// Get frame `index`, which must be less than `self.len`
+    fn get(&self, index: usize) -> T
+    where
+        T: Copy,
+    {
+        let block = &self.blocks[self.blocks.partition_point(|x| x.start <= index) - 1];
+        block.frames[index - block.start]
+    }
+}
+
+impl<T: Frame + Copy> Signal for Recording<T> {
+    type Frame = T;
+
+    fn sample(&mut self, interval: f32, out: &mut [T]) {
+        self.update();
+        let ds = f64::from(interval) * f64::from(self.rate);
+        for (i, o) in out.iter_mut().enumerate() {
+            let s = self.t + ds * i as f64;
+            let x0 = s as usize;
+            *o = if x0 < self.len {
+                let a = self.get(x0);
+                let b = if x0 + 1 < self.len {
+                    self.get(x0 + 1)
+                } else {
+                    a
+                };
+                frame::lerp(&a, &b, (s - x0 as f64) as f32)
+            } else {
+                T::ZERO
+            };
+        }
+        self.t = (self.t + ds * out.len() as f64).min(self.len as f64);
+    }
+}
+
+impl<T: Frame + Copy> Seek for Recording<T> {
+    fn seek(&mut self, seconds: f32) {
+        self.update();
+        self.t = (self.t + f64::from(seconds) * f64::from(self.rate)).clamp(0.0, self.len as f64);
+    }
+}
+
+/// Thread-safe control for a [`Recording`]
+pub struct RecordingControl<T> {
+    blocks: SetHandle<Block<T>>,
+    /// Number of frames written
+    len: usize,
+    rate: u32,
+}
+
+impl<T> RecordingControl<T> {
+    /// Append `frames` to the recording
+    ///
+    /// Each call allocates storage for `frames`, so prefer fewer, larger writes.
+    pub fn write(&mut self, frames: &[T])
+    where
+        T: Copy,
+    {
+        if frames.is_empty() {
+            return;
+        }
+        self.blocks.insert(Block {
+            start: self.len,
+            frames: frames.into(),
+        });
+        self.len += frames.len();
+    }
+
+    /// Duration of audio written so far, in seconds
+    pub fn runtime(&self) -> f64 {
+        self.len as f64 / f64::from(self.rate)
+    }
+}
+
+/// Frames passed to a single [`RecordingControl::write`] call
+struct Block<T> {
+    /// Index of the first frame within the recording
+    start: usize,
+    frames: Box<[T]>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scrub() {
+        let (mut control, mut signal) = Recording::new(1);
+        control.write(&[1.0, 2.0]);
+        control.write(&[3.0]);
+        assert_eq!(control.runtime(), 3.0);
+        let mut out = [0.0; 4];
+        signal.sample(1.0, &mut out);
+        assert_eq!(out, [1.0, 2.0, 3.0, 0.0]);
+
+        signal.seek(-2.0);
+        control.write(&[4.0]);
+        signal.sample(1.0, &mut out);
+        assert_eq!(out, [2.0, 3.0, 4.0, 0.0]);
+
+        // Seeking past the end stops at the latest frame
+        signal.seek(-4.0);
+        signal.seek(10.0);
+        control.write(&[5.0]);
+        signal.sample(1.0, &mut out[..1]);
+        assert_eq!(out[0], 5.0);
+    }
+}
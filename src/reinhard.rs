@@ -1,4 +1,7 @@
-use crate::{math::Float, Frame, Seek, Signal};
+use alloc::sync::Arc;
+use core::sync::atomic::{AtomicU32, Ordering};
+
+use crate::{math::Float, Frame, Reset, Seek, Signal};
 
 /// Smoothly maps a signal of any range into (-1, 1)
 ///
@@ -10,12 +13,38 @@ use crate::{math::Float, Frame, Seek, Signal};
 /// simple clamping introduces audible artifacts.
 ///
 /// See also [`Tanh`](crate::Tanh), which distorts quiet sounds less, and loud sounds more.
-pub struct Reinhard<T>(T);
+pub struct Reinhard<T> {
+    /// Linear gain applied before the operator, or `None` for unity
+    pre_gain: Option<Arc<AtomicU32>>,
+    /// Amplitude below which the signal is unmodified
+    knee: f32,
+    inner: T,
+}
 
 impl<T> Reinhard<T> {
     /// Apply the Reinhard operator to `signal`
     pub fn new(signal: T) -> Self {
-        Self(signal)
+        Self {
+            pre_gain: None,
+            knee: 0.0,
+            inner: signal,
+        }
+    }
+
+    /// Apply the Reinhard operator to `signal` scaled by `pre_gain`, leaving amplitudes below
+    /// `knee` unmodified
+    ///
+    /// `knee` should be in [0, 1). Above the knee, the operator smoothly compresses the remaining
+    /// range, such that the output still never exceeds 1. A larger `pre_gain` increases the amount
+    /// of compression, while a smaller one leaves more headroom.
+    pub fn with_params(signal: T, pre_gain: f32, knee: f32) -> (ReinhardControl, Self) {
+        let shared = Arc::new(AtomicU32::new(pre_gain.to_bits()));
+        let signal = Self {
+            pre_gain: Some(shared.clone()),
+            knee: knee.clamp(0.0, 1.0 - f32::EPSILON),
+            inner: signal,
+        };
+        (ReinhardControl(shared), signal)
     }
 }
 
@@ -26,16 +55,33 @@ where
     type Frame = T::Frame;
 
     fn sample(&mut self, interval: f32, out: &mut [T::Frame]) {
-        self.0.sample(interval, out);
+        self.inner.sample(interval, out);
+        let pre_gain = self
+            .pre_gain
+            .as_ref()
+            .map_or(1.0, |x| f32::from_bits(x.load(Ordering::Relaxed)));
+        let knee = self.knee;
+        let headroom = 1.0 - knee;
         for x in out {
             for channel in x.channels_mut() {
-                *channel /= 1.0 + channel.abs();
+                let x = *channel * pre_gain;
+                let excess = x.abs() - knee;
+                if excess > 0.0 {
+                    let y = knee + excess / (1.0 + excess / headroom);
+                    *channel = if x < 0.0 { -y } else { y };
+                } else {
+                    *channel = x;
+                }
             }
         }
     }
 
     fn is_finished(&self) -> bool {
-        self.0.is_finished()
+        self.inner.is_finished()
+    }
+
+    fn duration_remaining(&self) -> Option<f32> {
+        self.inner.duration_remaining()
     }
 }
 
@@ -45,6 +91,57 @@ where
     T::Frame: Frame,
 {
     fn seek(&mut self, seconds: f32) {
-        self.0.seek(seconds);
+        self.inner.seek(seconds);
+    }
+}
+
+impl<T> Reset for Reinhard<T>
+where
+    T: Signal + Reset,
+    T::Frame: Frame,
+{
+    fn reset(&mut self) {
+        self.inner.reset();
+    }
+}
+
+/// Thread-safe control for a [`Reinhard`] operator
+pub struct ReinhardControl(Arc<AtomicU32>);
+
+impl ReinhardControl {
+    /// Get the linear gain applied before the operator
+    pub fn pre_gain(&self) -> f32 {
+        f32::from_bits(self.0.load(Ordering::Relaxed))
+    }
+
+    /// Set the linear gain applied before the operator
+    pub fn set_pre_gain(&mut self, gain: f32) {
+        self.0.store(gain.to_bits(), Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Constant;
+
+    #[test]
+    fn knee() {
+        let mut plain = Reinhard::new(Constant(-1.0));
+        let mut out = [0.0];
+        plain.sample(1.0, &mut out);
+        assert_eq!(out, [-0.5]);
+
+        let (mut c, mut s) = Reinhard::with_params(Constant(0.4), 1.0, 0.5);
+        s.sample(1.0, &mut out);
+        assert_eq!(out, [0.4], "below the knee");
+
+        c.set_pre_gain(1000.0);
+        s.sample(1.0, &mut out);
+        assert!(out[0] < 1.0 && out[0] > 0.99);
+
+        s.inner.0 = -0.002;
+        s.sample(1.0, &mut out);
+        assert!(out[0] > -1.0 && out[0] < -0.5);
     }
 }
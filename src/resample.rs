@@ -0,0 +1,177 @@
+use alloc::vec::Vec;
+
+use crate::{frame, Frame, Interpolation, Reset, Seek, Signal};
+
+/// Plays a signal that produces frames at a fixed rate, regardless of the output rate
+///
+/// Most signals are sampled at whatever interval they're asked for, but some, such as a
+/// [`Stream`](crate::Stream) fed from a decoder, have a natural rate of their own. `Resample`
+/// samples `signal` at exactly `rate` frames per second and reconstructs the signal between those
+/// frames for output at any other rate.
+///
+/// Interpolation happens on the fly every time the signal is played, and [`Interpolation::Linear`]
+/// in particular dulls high frequencies and admits some aliasing. For static audio data that will
+/// be played many times, converting it once with [`Frames::resampled`](crate::Frames::resampled)
+/// is both cheaper during playback and higher quality.
+pub struct Resample<T: Signal + ?Sized> {
+    /// Source frames per second
+    rate: f64,
+    interpolation: Interpolation,
+    /// Frames recently sampled from `inner`
+    buffer: Vec<T::Frame>,
+    /// Read position in `buffer`, always at least 1 so the preceding frame is available
+    position: f64,
+    inner: T,
+}
+
+impl<T: Signal> Resample<T>
+where
+    T::Frame: Frame,
+{
+    /// Play `signal`, which produces `rate` frames per second, at any output rate
+    pub fn new(signal: T, rate: u32) -> Self {
+        let mut buffer = Vec::with_capacity(CHUNK + 3);
+        buffer.push(T::Frame::ZERO);
+        Self {
+            rate: rate.into(),
+            interpolation: Interpolation::Linear,
+            buffer,
+            position: 1.0,
+            inner: signal,
+        }
+    }
+
+    /// Reconstruct the signal between frames using `interpolation`
+    ///
+    /// Defaults to [`Interpolation::Linear`].
+    pub fn with_interpolation(mut self, interpolation: Interpolation) -> Self {
+        self.interpolation = interpolation;
+        self
+    }
+}
+
+impl<T: Signal + ?Sized> Resample<T>
+where
+    T::Frame: Frame,
+{
+    /// Sample frames from `inner` until those surrounding `position` are available
+    fn fill(&mut self) {
+        while self.position as usize + 2 >= self.buffer.len() {
+            // Discard frames that are no longer needed, keeping the one before `position`
+            let consumed = (self.position as usize - 1).min(self.buffer.len());
+            self.buffer.drain(..consumed);
+            self.position -= consumed as f64;
+
+            let start = self.buffer.len();
+            self.buffer.extend((0..CHUNK).map(|_| T::Frame::ZERO));
+            self.inner
+                .sample((1.0 / self.rate) as f32, &mut self.buffer[start..]);
+        }
+    }
+
+    fn clear(&mut self) {
+        self.buffer.clear();
+        self.buffer.push(T::Frame::ZERO);
+        self.position = 1.0;
+    }
+}
+
+impl<T: Signal + ?Sized> Signal for Resample<T>
+where
+    T::Frame: Frame,
+{
+    type Frame = T::Frame;
+
+    fn sample(&mut self, interval: f32, out: &mut [T::Frame]) {
+        let step = f64::from(interval) * self.rate;
+        for o in out {
+            self.fill();
+            let i = self.position as usize;
+            let fract = (self.position - i as f64) as f32;
+            *o = match self.interpolation {
+                Interpolation::Linear => frame::lerp(&self.buffer[i], &self.buffer[i + 1], fract),
+                Interpolation::Cubic => frame::cubic(
+                    &self.buffer[i - 1],
+                    &self.buffer[i],
+                    &self.buffer[i + 1],
+                    &self.buffer[i + 2],
+                    fract,
+                ),
+            };
+            self.position += step;
+        }
+    }
+
+    fn is_finished(&self) -> bool {
+        self.inner.is_finished()
+    }
+
+    fn duration_remaining(&self) -> Option<f32> {
+        let buffered = (self.buffer.len() as f64 - self.position).max(0.0) / self.rate;
+        Some(self.inner.duration_remaining()? + buffered as f32)
+    }
+}
+
+/// Buffered frames are discarded, so playback resumes at a whole frame of the inner signal.
+impl<T: Seek + ?Sized> Seek for Resample<T>
+where
+    T::Frame: Frame,
+{
+    fn seek(&mut self, seconds: f32) {
+        // `inner` has already been sampled past the current position
+        let buffered = (self.buffer.len() as f64 - self.position) / self.rate;
+        self.inner.seek(seconds - buffered as f32);
+        self.clear();
+    }
+}
+
+impl<T: Reset + ?Sized> Reset for Resample<T>
+where
+    T::Frame: Frame,
+{
+    fn reset(&mut self) {
+        self.inner.reset();
+        self.clear();
+    }
+}
+
+/// Number of frames sampled from the inner signal at a time
+const CHUNK: usize = 64;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Frames, FramesSignal, Sample, Sine};
+    use core::f32::consts::TAU;
+
+    fn ramp() -> FramesSignal<Sample> {
+        let frames = Frames::from_iter(4, (0..256).map(|i| i as Sample));
+        FramesSignal::new(frames, 0.0).1
+    }
+
+    #[test]
+    fn upsample() {
+        let mut s = Resample::new(ramp(), 4);
+        let mut out = [0.0; 5];
+        s.sample(0.125, &mut out);
+        assert_eq!(out, [0.0, 0.5, 1.0, 1.5, 2.0]);
+        // Continues smoothly across the inner signal's chunks
+        for _ in 0..30 {
+            s.sample(0.125, &mut out);
+        }
+        assert_eq!(out, [75.0, 75.5, 76.0, 76.5, 77.0]);
+    }
+
+    #[test]
+    fn cubic() {
+        let mut s =
+            Resample::new(Sine::new(0.0, 1000.0), 8000).with_interpolation(Interpolation::Cubic);
+        let mut out = [0.0; 441];
+        s.sample(1.0 / 44100.0, &mut out);
+        // Skip the first few frames, which are interpolated from silence
+        for (i, &x) in out.iter().enumerate().skip(8) {
+            let expected = (TAU * 1000.0 * i as f32 / 44100.0).sin();
+            assert!((x - expected).abs() < 0.02, "{}: {} != {}", i, x, expected);
+        }
+    }
+}
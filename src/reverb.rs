@@ -0,0 +1,353 @@
+use alloc::{boxed::Box, sync::Arc, vec};
+use core::sync::atomic::{AtomicU32, Ordering};
+
+use crate::{math::Float, Reset, Sample, Signal};
+
+/// Simulates the reflections of a room
+///
+/// A Schroeder-Moorer ("Freeverb") design: eight damped comb filters in parallel followed by four
+/// allpass filters in series, with slightly different delays for each channel to decorrelate them.
+/// Adds the reverberated signal on top of the original.
+pub struct Reverb<T: ?Sized> {
+    tank: Tank,
+    inner: T,
+}
+
+impl<T> Reverb<T> {
+    /// Apply reverb to `signal`, tuned for sample rate `rate`
+    ///
+    /// Memory use is proportional to `rate`. Sampling at a different rate scales the apparent room
+    /// size accordingly.
+    pub fn new(signal: T, rate: u32, options: ReverbOptions) -> (ReverbControl, Self) {
+        let (control, tank) = Tank::new(rate, options);
+        (
+            control,
+            Self {
+                tank,
+                inner: signal,
+            },
+        )
+    }
+}
+
+impl<T: Signal<Frame = [Sample; 2]> + ?Sized> Signal for Reverb<T> {
+    type Frame = [Sample; 2];
+
+    fn sample(&mut self, interval: f32, out: &mut [[Sample; 2]]) {
+        // Input may arrive at any point until the inner signal reports that it's finished
+        let active = !self.inner.is_finished();
+        self.inner.sample(interval, out);
+        self.tank.update(interval);
+        for x in &mut *out {
+            let [l, r] = self.tank.process(x[0] + x[1]);
+            x[0] += l;
+            x[1] += r;
+        }
+        if active {
+            self.tank.sustain();
+        } else {
+            self.tank.decay(interval * out.len() as f32);
+        }
+    }
+
+    fn is_finished(&self) -> bool {
+        self.inner.is_finished() && self.tank.is_silent()
+    }
+}
+
+impl<T: Reset<Frame = [Sample; 2]> + ?Sized> Reset for Reverb<T> {
+    fn reset(&mut self) {
+        self.tank.reset();
+        self.inner.reset();
+    }
+}
+
+/// Configuration for a [`Reverb`], passed to [`Reverb::new`]
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ReverbOptions {
+    /// Length of the reverb tail, from 0 to 1
+    pub room_size: f32,
+    /// How quickly high frequencies decay relative to low ones, from 0 to 1
+    pub damping: f32,
+    /// Amplitude of the reverberated signal
+    pub wet: f32,
+}
+
+impl Default for ReverbOptions {
+    fn default() -> Self {
+        Self {
+            room_size: 0.5,
+            damping: 0.5,
+            wet: 0.3,
+        }
+    }
+}
+
+/// Thread-safe control for a [`Reverb`]
+pub struct ReverbControl(Arc<Shared>);
+
+impl ReverbControl {
+    /// Get the room size
+    pub fn room_size(&self) -> f32 {
+        f32::from_bits(self.0.room_size.load(Ordering::Relaxed))
+    }
+
+    /// Set the room size, from 0 to 1
+    pub fn set_room_size(&mut self, size: f32) {
+        self.0
+            .room_size
+            .store(size.clamp(0.0, 1.0).to_bits(), Ordering::Relaxed);
+    }
+
+    /// Get the damping
+    pub fn damping(&self) -> f32 {
+        f32::from_bits(self.0.damping.load(Ordering::Relaxed))
+    }
+
+    /// Set the damping, from 0 to 1
+    pub fn set_damping(&mut self, damping: f32) {
+        self.0
+            .damping
+            .store(damping.clamp(0.0, 1.0).to_bits(), Ordering::Relaxed);
+    }
+
+    /// Get the amplitude of the reverberated signal
+    pub fn wet(&self) -> f32 {
+        f32::from_bits(self.0.wet.load(Ordering::Relaxed))
+    }
+
+    /// Set the amplitude of the reverberated signal
+    pub fn set_wet(&mut self, wet: f32) {
+        self.0.wet.store(wet.to_bits(), Ordering::Relaxed);
+    }
+}
+
+struct Shared {
+    room_size: AtomicU32,
+    damping: AtomicU32,
+    wet: AtomicU32,
+}
+
+/// Mono-in, stereo-out reverberator shared by [`Reverb`] and the spatial reverb bus
+pub(crate) struct Tank {
+    shared: Arc<Shared>,
+    channels: [Channel; 2],
+    feedback: f32,
+    damping: f32,
+    wet: f32,
+    /// Seconds the tail takes to become inaudible
+    tail: f32,
+    /// Seconds remaining until the tail becomes inaudible, once input has ceased
+    remaining: f32,
+}
+
+impl Tank {
+    pub(crate) fn new(rate: u32, options: ReverbOptions) -> (ReverbControl, Self) {
+        let shared = Arc::new(Shared {
+            room_size: AtomicU32::new(options.room_size.clamp(0.0, 1.0).to_bits()),
+            damping: AtomicU32::new(options.damping.clamp(0.0, 1.0).to_bits()),
+            wet: AtomicU32::new(options.wet.to_bits()),
+        });
+        let scale = rate as f32 / 44100.0;
+        let mut tank = Self {
+            shared: shared.clone(),
+            channels: [Channel::new(scale, 0), Channel::new(scale, STEREO_SPREAD)],
+            feedback: 0.0,
+            damping: 0.0,
+            wet: 0.0,
+            tail: 0.0,
+            remaining: 0.0,
+        };
+        tank.update(1.0 / rate as f32);
+        (ReverbControl(shared), tank)
+    }
+
+    /// Load parameters from the control
+    pub(crate) fn update(&mut self, interval: f32) {
+        let room_size = f32::from_bits(self.shared.room_size.load(Ordering::Relaxed));
+        self.feedback = room_size * 0.28 + 0.7;
+        self.damping = f32::from_bits(self.shared.damping.load(Ordering::Relaxed)) * 0.4;
+        self.wet = f32::from_bits(self.shared.wet.load(Ordering::Relaxed));
+        // Time for the longest comb to decay by 60dB
+        let longest = self.channels[1].combs[COMB_LENGTHS.len() - 1].buffer.len();
+        self.tail = 3.0 * longest as f32 * interval / -self.feedback.log10();
+    }
+
+    #[inline]
+    pub(crate) fn process(&mut self, input: Sample) -> [Sample; 2] {
+        let input = input * INPUT_GAIN;
+        let (feedback, damping, wet) = (self.feedback, self.damping, self.wet);
+        let [ref mut l, ref mut r] = self.channels;
+        [
+            l.process(input, feedback, damping) * wet,
+            r.process(input, feedback, damping) * wet,
+        ]
+    }
+
+    /// Note that input is ongoing
+    pub(crate) fn sustain(&mut self) {
+        self.remaining = self.tail;
+    }
+
+    /// Note that `seconds` have passed without input
+    pub(crate) fn decay(&mut self, seconds: f32) {
+        self.remaining -= seconds;
+    }
+
+    pub(crate) fn is_silent(&self) -> bool {
+        self.remaining <= 0.0
+    }
+
+    pub(crate) fn reset(&mut self) {
+        for channel in &mut self.channels {
+            channel.reset();
+        }
+        self.remaining = 0.0;
+    }
+}
+
+struct Channel {
+    combs: [Comb; 8],
+    allpasses: [Allpass; 4],
+}
+
+impl Channel {
+    fn new(scale: f32, spread: usize) -> Self {
+        Self {
+            combs: COMB_LENGTHS.map(|n| Comb::new(scaled(n + spread, scale))),
+            allpasses: ALLPASS_LENGTHS.map(|n| Allpass::new(scaled(n + spread, scale))),
+        }
+    }
+
+    #[inline]
+    fn process(&mut self, input: Sample, feedback: f32, damping: f32) -> Sample {
+        let mut out = 0.0;
+        for comb in &mut self.combs {
+            out += comb.process(input, feedback, damping);
+        }
+        for allpass in &mut self.allpasses {
+            out = allpass.process(out);
+        }
+        out
+    }
+
+    fn reset(&mut self) {
+        for comb in &mut self.combs {
+            comb.buffer.fill(0.0);
+            comb.store = 0.0;
+        }
+        for allpass in &mut self.allpasses {
+            allpass.buffer.fill(0.0);
+        }
+    }
+}
+
+fn scaled(samples: usize, scale: f32) -> usize {
+    ((samples as f32 * scale) as usize).max(1)
+}
+
+/// Feedback comb filter with a one-pole low-pass in the loop
+struct Comb {
+    buffer: Box<[Sample]>,
+    index: usize,
+    /// Low-pass filter state
+    store: Sample,
+}
+
+impl Comb {
+    fn new(len: usize) -> Self {
+        Self {
+            buffer: vec![0.0; len].into(),
+            index: 0,
+            store: 0.0,
+        }
+    }
+
+    #[inline]
+    fn process(&mut self, input: Sample, feedback: f32, damping: f32) -> Sample {
+        let out = self.buffer[self.index];
+        self.store = out * (1.0 - damping) + self.store * damping;
+        self.buffer[self.index] = input + self.store * feedback;
+        self.index = (self.index + 1) % self.buffer.len();
+        out
+    }
+}
+
+/// Schroeder allpass filter, which diffuses echoes without coloring their spectrum
+struct Allpass {
+    buffer: Box<[Sample]>,
+    index: usize,
+}
+
+impl Allpass {
+    fn new(len: usize) -> Self {
+        Self {
+            buffer: vec![0.0; len].into(),
+            index: 0,
+        }
+    }
+
+    #[inline]
+    fn process(&mut self, input: Sample) -> Sample {
+        let delayed = self.buffer[self.index];
+        self.buffer[self.index] = input + delayed * ALLPASS_FEEDBACK;
+        self.index = (self.index + 1) % self.buffer.len();
+        delayed - input
+    }
+}
+
+/// Comb filter delays in samples at 44.1kHz, chosen to be mutually prime-ish
+const COMB_LENGTHS: [usize; 8] = [1116, 1188, 1277, 1356, 1422, 1491, 1557, 1617];
+
+/// Allpass filter delays in samples at 44.1kHz
+const ALLPASS_LENGTHS: [usize; 4] = [556, 441, 341, 225];
+
+/// Extra delay applied to the right channel, in samples at 44.1kHz
+const STEREO_SPREAD: usize = 23;
+
+const ALLPASS_FEEDBACK: f32 = 0.5;
+
+/// Scaling applied to input to keep the sum of the combs in range
+const INPUT_GAIN: f32 = 0.015;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Constant, Frames, FramesSignal, SignalExt};
+
+    #[test]
+    fn tail() {
+        const RATE: u32 = 8000;
+        let impulse = Frames::from_slice(RATE, &[1.0, 0.0]);
+        let (_, mut reverb) = Reverb::new(
+            FramesSignal::from(impulse).mono_to_stereo(),
+            RATE,
+            ReverbOptions::default(),
+        );
+        let mut out = [[0.0; 2]; 4096];
+        reverb.sample(1.0 / RATE as f32, &mut out);
+        assert_eq!(out[0], [1.0; 2], "dry signal passes through");
+        assert!(out[1..].iter().any(|x| x[0] != 0.0), "tail follows");
+        assert!(
+            out.iter().any(|x| x[0] != x[1]),
+            "channels are decorrelated"
+        );
+        assert!(!reverb.is_finished(), "tail still ringing");
+        for _ in 0..4 {
+            reverb.sample(1.0 / RATE as f32, &mut out);
+        }
+        assert!(reverb.is_finished());
+    }
+
+    #[test]
+    fn reset() {
+        let (_, mut reverb) = Reverb::new(Constant([1.0; 2]), 8000, ReverbOptions::default());
+        let mut first = [[0.0; 2]; 2048];
+        reverb.sample(1.0 / 8000.0, &mut first);
+        let mut second = [[0.0; 2]; 2048];
+        reverb.reset();
+        reverb.sample(1.0 / 8000.0, &mut second);
+        assert_eq!(first[..], second[..]);
+    }
+}
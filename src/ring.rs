@@ -20,6 +20,17 @@ impl Ring {
         signal: &mut S,
         rate: u32,
         dt: f32,
+    ) {
+        self.write_at_speed(signal, rate, dt, 1.0);
+    }
+
+    /// Fill buffer from `signal`, advancing through it `speed` times faster than real time
+    pub fn write_at_speed<S: Signal<Frame = Sample> + ?Sized>(
+        &mut self,
+        signal: &mut S,
+        rate: u32,
+        dt: f32,
+        speed: f32,
     ) {
         debug_assert!(
             dt * rate as f32 <= self.buffer.len() as f32,
@@ -29,7 +40,7 @@ impl Ring {
 
         let start_idx = self.write.ceil() as usize;
         let end_idx = end.ceil() as usize;
-        let interval = 1.0 / rate as f32;
+        let interval = speed / rate as f32;
         if end_idx > start_idx {
             signal.sample(interval, &mut self.buffer[start_idx..end_idx]);
         } else {
@@ -40,6 +51,17 @@ impl Ring {
         self.write = end;
     }
 
+    /// Append `data`, recorded at the ring's sample rate
+    pub fn push(&mut self, data: &[Sample]) {
+        let len = self.buffer.len();
+        let mut i = self.write.ceil() as usize % len;
+        for &x in data {
+            self.buffer[i] = x;
+            i = (i + 1) % len;
+        }
+        self.write = (self.write + data.len() as f32) % len as f32;
+    }
+
     /// Advance write cursor by `dt` given internal sample rate `rate`, as if writing a `Signal`
     /// that produces only zeroes
     pub fn delay(&mut self, rate: u32, dt: f32) {
@@ -90,7 +112,7 @@ mod tests {
         fn sample(&mut self, interval: f32, out: &mut [Sample]) {
             for x in out {
                 let t = self.0;
-                *x = t as f32;
+                *x = t;
                 self.0 = t + interval;
             }
         }
@@ -119,6 +141,16 @@ mod tests {
         assert_out(&mut r, 1, -1.5, 0.25, &[2.5, 2.75, 3.0, 2.25]);
     }
 
+    #[test]
+    fn push() {
+        let mut r = Ring::new(4);
+        r.push(&[1.0, 2.0, 3.0]);
+        r.push(&[4.0, 5.0]);
+        assert_eq!(r.buffer[..], [5.0, 2.0, 3.0, 4.0]);
+        assert_eq!(r.write, 1.0);
+        assert_out(&mut r, 1, -2.5, 1.0, &[3.5, 4.5]);
+    }
+
     #[test]
     fn wrap() {
         let mut r = Ring::new(4);
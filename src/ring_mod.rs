@@ -0,0 +1,151 @@
+use alloc::sync::Arc;
+use core::{
+    f32::consts::TAU,
+    sync::atomic::{AtomicU32, Ordering},
+};
+
+use crate::{math::Float, Frame, Reset, Seek, Signal};
+
+/// Multiplies a signal by a sine wave carrier, for metallic or robotic effects
+///
+/// Every channel of a frame is multiplied by the same carrier value.
+pub struct RingMod<T: ?Sized> {
+    shared: Arc<Shared>,
+    /// Carrier phase in radians
+    phase: f32,
+    inner: T,
+}
+
+impl<T> RingMod<T> {
+    /// Modulate `signal` with a carrier cycling `carrier_hz` times per second, fully wet
+    pub fn new(signal: T, carrier_hz: f32) -> (RingModControl, Self) {
+        let shared = Arc::new(Shared {
+            frequency: AtomicU32::new(carrier_hz.to_bits()),
+            mix: AtomicU32::new(1.0f32.to_bits()),
+        });
+        let signal = Self {
+            shared: shared.clone(),
+            phase: 0.0,
+            inner: signal,
+        };
+        (RingModControl(shared), signal)
+    }
+}
+
+impl<T: Signal> Signal for RingMod<T>
+where
+    T::Frame: Frame,
+{
+    type Frame = T::Frame;
+
+    fn sample(&mut self, interval: f32, out: &mut [T::Frame]) {
+        self.inner.sample(interval, out);
+        let frequency = f32::from_bits(self.shared.frequency.load(Ordering::Relaxed)) * TAU;
+        let mix = f32::from_bits(self.shared.mix.load(Ordering::Relaxed));
+        for (i, x) in out.iter_mut().enumerate() {
+            let carrier = (interval * i as f32 * frequency + self.phase).sin();
+            // Blend between the dry signal and its product with the carrier
+            let gain = 1.0 - mix + mix * carrier;
+            for s in x.channels_mut() {
+                *s *= gain;
+            }
+        }
+        // Wrap for numerical stability no matter how long we play for
+        self.phase = (self.phase + interval * out.len() as f32 * frequency) % TAU;
+    }
+
+    fn is_finished(&self) -> bool {
+        self.inner.is_finished()
+    }
+
+    fn duration_remaining(&self) -> Option<f32> {
+        self.inner.duration_remaining()
+    }
+}
+
+impl<T: Seek> Seek for RingMod<T>
+where
+    T::Frame: Frame,
+{
+    fn seek(&mut self, seconds: f32) {
+        let frequency = f32::from_bits(self.shared.frequency.load(Ordering::Relaxed));
+        self.phase = (self.phase + seconds * TAU * frequency).rem_euclid(TAU);
+        self.inner.seek(seconds);
+    }
+}
+
+impl<T: Reset> Reset for RingMod<T>
+where
+    T::Frame: Frame,
+{
+    fn reset(&mut self) {
+        self.phase = 0.0;
+        self.inner.reset();
+    }
+}
+
+/// Thread-safe control for a [`RingMod`]
+pub struct RingModControl(Arc<Shared>);
+
+impl RingModControl {
+    /// Get the carrier frequency in Hz
+    pub fn frequency(&self) -> f32 {
+        f32::from_bits(self.0.frequency.load(Ordering::Relaxed))
+    }
+
+    /// Set the carrier frequency in Hz
+    pub fn set_frequency(&mut self, hz: f32) {
+        self.0.frequency.store(hz.to_bits(), Ordering::Relaxed);
+    }
+
+    /// Get the proportion of modulated signal in the output
+    pub fn mix(&self) -> f32 {
+        f32::from_bits(self.0.mix.load(Ordering::Relaxed))
+    }
+
+    /// Set the proportion of modulated signal in the output, from 0 (dry) to 1 (wet)
+    pub fn set_mix(&mut self, mix: f32) {
+        self.0
+            .mix
+            .store(mix.clamp(0.0, 1.0).to_bits(), Ordering::Relaxed);
+    }
+}
+
+struct Shared {
+    /// Carrier frequency in Hz
+    frequency: AtomicU32,
+    mix: AtomicU32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Constant;
+
+    #[test]
+    fn modulate() {
+        let (mut c, mut s) = RingMod::new(Constant([1.0, 2.0]), 1.0);
+        let mut out = [[0.0; 2]; 4];
+        s.sample(0.25, &mut out);
+        for (x, expected) in out.iter().zip([0.0, 1.0, 0.0, -1.0]) {
+            assert!((x[0] - expected).abs() < 1e-6);
+            assert!((x[1] - 2.0 * expected).abs() < 1e-6);
+        }
+
+        c.set_mix(0.0);
+        s.sample(0.25, &mut out);
+        assert_eq!(out, [[1.0, 2.0]; 4], "dry");
+    }
+    #[test]
+    fn seek_back_replays() {
+        let (_, mut s) = RingMod::new(Constant(1.0), 3.0);
+        let mut first = [0.0; 8];
+        s.sample(0.01, &mut first);
+        s.seek(-0.08);
+        let mut second = [0.0; 8];
+        s.sample(0.01, &mut second);
+        for (a, b) in first.iter().zip(&second) {
+            assert!((a - b).abs() < 1e-5, "{:?} != {:?}", first, second);
+        }
+    }
+}
@@ -0,0 +1,159 @@
+use alloc::{boxed::Box, sync::Arc};
+use core::{
+    marker::PhantomData,
+    sync::atomic::{AtomicU32, AtomicUsize, Ordering},
+};
+
+use crate::{Frame, Seek, Signal};
+
+/// Records the most recent output of a signal without modifying it
+///
+/// Recorded frames can be read from another thread through a [`ScopeControl`], e.g. to draw an
+/// oscilloscope view.
+pub struct Scope<T: ?Sized> {
+    shared: Arc<Shared>,
+    channels: usize,
+    inner: T,
+}
+
+impl<T: Signal> Scope<T>
+where
+    T::Frame: Frame,
+{
+    /// Record the most recent 2048 frames of `signal`
+    pub fn new(signal: T) -> (ScopeControl<T::Frame>, Self) {
+        Self::with_window(signal, DEFAULT_WINDOW)
+    }
+
+    /// Record the most recent `frames` frames of `signal`
+    pub fn with_window(signal: T, frames: usize) -> (ScopeControl<T::Frame>, Self) {
+        let channels = T::Frame::ZERO.channels().len();
+        let shared = Arc::new(Shared {
+            samples: (0..frames * channels).map(|_| AtomicU32::new(0)).collect(),
+            write: AtomicUsize::new(0),
+        });
+        let control = ScopeControl {
+            shared: shared.clone(),
+            _frame: PhantomData,
+        };
+        let signal = Self {
+            shared,
+            channels,
+            inner: signal,
+        };
+        (control, signal)
+    }
+}
+
+impl<T: Signal> Signal for Scope<T>
+where
+    T::Frame: Frame,
+{
+    type Frame = T::Frame;
+
+    fn sample(&mut self, interval: f32, out: &mut [T::Frame]) {
+        self.inner.sample(interval, out);
+        let window = self.shared.samples.len() / self.channels;
+        if window == 0 {
+            return;
+        }
+        let write = self.shared.write.load(Ordering::Relaxed);
+        // Only the most recent `window` frames can be retained
+        let skip = out.len().saturating_sub(window);
+        for (i, frame) in out.iter().enumerate().skip(skip) {
+            let base = ((write + i) % window) * self.channels;
+            for (x, slot) in frame
+                .channels()
+                .iter()
+                .zip(&self.shared.samples[base..base + self.channels])
+            {
+                slot.store(x.to_bits(), Ordering::Relaxed);
+            }
+        }
+        self.shared
+            .write
+            .store((write + out.len()) % window, Ordering::Release);
+    }
+
+    fn is_finished(&self) -> bool {
+        self.inner.is_finished()
+    }
+
+    fn duration_remaining(&self) -> Option<f32> {
+        self.inner.duration_remaining()
+    }
+}
+
+impl<T: Seek> Seek for Scope<T>
+where
+    T::Frame: Frame,
+{
+    fn seek(&mut self, seconds: f32) {
+        self.inner.seek(seconds);
+    }
+}
+
+/// Thread-safe access to the frames recorded by a [`Scope`]
+pub struct ScopeControl<T> {
+    shared: Arc<Shared>,
+    _frame: PhantomData<T>,
+}
+
+impl<T: Frame> ScopeControl<T> {
+    /// Copy the most recently recorded frames into `out`, oldest first
+    ///
+    /// If `out` is longer than the scope's window, the excess leading frames are zeroed. If the
+    /// signal is sampled while reading, the oldest frames may be replaced by newer data.
+    pub fn read_into(&self, out: &mut [T]) {
+        let channels = T::ZERO.channels().len();
+        let window = self.shared.samples.len() / channels;
+        let n = out.len().min(window);
+        let (excess, out) = out.split_at_mut(out.len() - n);
+        excess.fill_with(|| T::ZERO);
+        let write = self.shared.write.load(Ordering::Acquire);
+        let start = write + window - n;
+        for (i, frame) in out.iter_mut().enumerate() {
+            let base = ((start + i) % window) * channels;
+            for (x, slot) in frame
+                .channels_mut()
+                .iter_mut()
+                .zip(&self.shared.samples[base..base + channels])
+            {
+                *x = f32::from_bits(slot.load(Ordering::Relaxed));
+            }
+        }
+    }
+}
+
+struct Shared {
+    /// Interleaved frames
+    samples: Box<[AtomicU32]>,
+    /// Index of the frame that will be written next
+    write: AtomicUsize,
+}
+
+const DEFAULT_WINDOW: usize = 2048;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Frames, FramesSignal};
+
+    #[test]
+    fn wrap() {
+        let frames = Frames::from_slice(1, &[[1.0, -1.0], [2.0, -2.0], [3.0, -3.0], [4.0, -4.0]]);
+        let (c, mut s) = Scope::with_window(FramesSignal::from(frames), 3);
+        let mut out = [[0.0; 2]; 4];
+        c.read_into(&mut out);
+        assert_eq!(out, [[0.0; 2]; 4]);
+
+        let mut buf = [[0.0; 2]; 2];
+        s.sample(1.0, &mut buf);
+        c.read_into(&mut out[..2]);
+        assert_eq!(out[..2], [[1.0, -1.0], [2.0, -2.0]]);
+
+        s.sample(1.0, &mut buf);
+        c.read_into(&mut out);
+        assert_eq!(out, [[0.0; 2], [2.0, -2.0], [3.0, -3.0], [4.0, -4.0]]);
+    }
+}
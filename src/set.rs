@@ -8,6 +8,59 @@ use core::{
 use crate::spsc;
 
 /// Build a set
+///
+/// The [`SetHandle`] may be used to insert elements from any thread, while the [`Set`] is owned by
+/// the audio thread. Neither side ever waits for the other.
+///
+/// # Example
+///
+/// A minimal mixer:
+///
+/// ```
+/// use oddio::{Sample, Set, Signal};
+///
+/// struct Sum(Set<Box<dyn Signal<Frame = Sample> + Send>>);
+///
+/// impl Signal for Sum {
+///     type Frame = Sample;
+///
+///     fn sample(&mut self, interval: f32, out: &mut [Sample]) {
+///         self.0.update();
+///         out.fill(0.0);
+///         let mut buf = [0.0; 64];
+///         for chunk in out.chunks_mut(buf.len()) {
+///             for signal in self.0.iter_mut() {
+///                 let buf = &mut buf[..chunk.len()];
+///                 signal.sample(interval, buf);
+///                 for (o, x) in chunk.iter_mut().zip(buf) {
+///                     *o += *x;
+///                 }
+///             }
+///         }
+///         for i in (0..self.0.len()).rev() {
+///             if self.0[i].is_finished() {
+///                 self.0.remove(i);
+///             }
+///         }
+///     }
+/// }
+///
+/// let (mut handle, set) = oddio::set();
+/// let mut sum = Sum(set);
+/// handle.insert(Box::new(oddio::Constant(1.0f32)));
+/// handle.insert(Box::new(oddio::Constant(2.0f32)));
+/// let mut out = [0.0; 4];
+/// sum.sample(1.0, &mut out);
+/// assert_eq!(out, [3.0; 4]);
+/// ```
+///
+/// Elements are moved between threads, so the set can only be sent to another thread if they can:
+///
+/// ```compile_fail
+/// fn assert_send<T: Send>(_: T) {}
+/// let (_, set) = oddio::set::<std::rc::Rc<u32>>();
+/// assert_send(set);
+/// ```
 pub fn set<T>() -> (SetHandle<T>, Set<T>) {
     let (msg_send, msg_recv) = spsc::channel(INITIAL_CHANNEL_CAPACITY);
     let (free_send, free_recv) = spsc::channel(INITIAL_SIGNALS_CAPACITY);
@@ -65,6 +118,19 @@ impl<T> SetHandle<T> {
         self.active_signals += 1;
     }
 
+    /// Number of signals in the set
+    ///
+    /// Includes removed signals that haven't yet been freed.
+    pub fn len(&mut self) -> usize {
+        self.gc();
+        self.active_signals
+    }
+
+    /// Whether the set contains no signals
+    pub fn is_empty(&mut self) -> bool {
+        self.len() == 0
+    }
+
     /// Send a message, allocating more storage to do so if necessary
     fn send(&mut self, msg: Msg<T>) {
         if let Err(msg) = self.sender.send(msg, 1) {
@@ -82,11 +148,7 @@ impl<T> SetHandle<T> {
 
     // Free old signals
     fn gc(&mut self) {
-        while self
-            .old_senders
-            .front_mut()
-            .map_or(false, |x| x.is_closed())
-        {
+        while self.old_senders.front_mut().is_some_and(|x| x.is_closed()) {
             self.old_senders.pop_front();
         }
         loop {
@@ -122,13 +184,16 @@ impl<T> SetHandle<T> {
     }
 }
 
-unsafe impl<T> Send for SetHandle<T> {}
-unsafe impl<T> Sync for SetHandle<T> {}
+unsafe impl<T: Send> Send for SetHandle<T> {}
+unsafe impl<T: Send> Sync for SetHandle<T> {}
 
-/// A collection of heterogeneous [`Signal`]s, controlled from another thread by a [`SetHandle`]
+/// A collection of heterogeneous [`Signal`](crate::Signal)s, controlled from another thread by a
+/// [`SetHandle`]
 ///
-/// Constructed by calling [`set`]. A useful primitive for building aggregate [`Signal`]s like
-/// [`Mixer`](crate::Mixer).
+/// Constructed by calling [`set`]. A useful primitive for building aggregate
+/// [`Signal`](crate::Signal)s like [`Mixer`](crate::Mixer). Dereferences to a slice of the current
+/// elements, which only changes in response to [`update`](Self::update) and
+/// [`remove`](Self::remove).
 pub struct Set<T>(UnsafeCell<SetInner<T>>);
 
 struct SetInner<T> {
@@ -168,10 +233,13 @@ impl<T> SetInner<T> {
     }
 }
 
-unsafe impl<T> Send for Set<T> {}
+unsafe impl<T: Send> Send for Set<T> {}
 
 impl<T> Set<T> {
     /// Process changes to the set
+    ///
+    /// Elements inserted through the [`SetHandle`] since the last call are appended. Typically
+    /// called at the start of each `sample` call.
     pub fn update(&mut self) {
         let this = unsafe { &mut (*self.0.get()) };
         this.drain_msgs();
@@ -179,7 +247,8 @@ impl<T> Set<T> {
 
     /// Remove `index` from the set
     ///
-    /// The last element in the set replaces it, as in `Vec::swap_remove`.
+    /// The last element in the set replaces it, as in `Vec::swap_remove`. The element is sent back
+    /// to the [`SetHandle`] to be dropped, so no memory is freed on the calling thread.
     pub fn remove(&mut self, index: usize) {
         let this = unsafe { &mut (*self.0.get()) };
         this.free
@@ -191,8 +260,7 @@ impl<T> Set<T> {
 impl<T> Deref for Set<T> {
     type Target = [T];
     fn deref(&self) -> &[T] {
-        let this = unsafe { &mut (*self.0.get()) };
-        &this.signals
+        unsafe { &(*self.0.get()).signals }
     }
 }
 
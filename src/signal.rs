@@ -1,4 +1,7 @@
-use crate::{flatten_stereo, Sample};
+use crate::{
+    flatten_stereo, Adapt, AdaptControl, AdaptOptions, Gain, GainControl, Map, Reinhard, Sample,
+    Speed, SpeedControl,
+};
 
 /// An audio signal
 ///
@@ -25,6 +28,14 @@ pub trait Signal {
     fn is_finished(&self) -> bool {
         false
     }
+
+    /// Seconds remaining until `is_finished` becomes true, if known
+    ///
+    /// `None` for signals that never finish, or whose length can't be determined in advance.
+    #[inline]
+    fn duration_remaining(&self) -> Option<f32> {
+        None
+    }
 }
 
 impl<T: Signal + ?Sized> Signal for alloc::boxed::Box<T> {
@@ -38,8 +49,67 @@ impl<T: Signal + ?Sized> Signal for alloc::boxed::Box<T> {
     fn is_finished(&self) -> bool {
         (**self).is_finished()
     }
+
+    #[inline]
+    fn duration_remaining(&self) -> Option<f32> {
+        (**self).duration_remaining()
+    }
 }
 
+/// Convenience methods for wrapping [`Signal`]s in combinators
+///
+/// Implemented for all signals. Allows chains of combinators to be written left-to-right, e.g.
+/// `signal.speed().1.mono_to_stereo().reinhard()`. Methods for combinators that have a control
+/// return it alongside the wrapped signal, like the combinator's constructor does.
+pub trait SignalExt: Signal + Sized {
+    /// Apply dynamic amplification to the signal
+    ///
+    /// See [`Gain::new`].
+    fn gain(self) -> (GainControl, Gain<Self>) {
+        Gain::new(self)
+    }
+
+    /// Apply dynamic speed to the signal
+    ///
+    /// See [`Speed::new`].
+    fn speed(self) -> (SpeedControl, Speed<Self>) {
+        Speed::new(self)
+    }
+
+    /// Keep the average level of the signal within a target range
+    ///
+    /// See [`Adapt::new`].
+    fn adapt(self, initial_rms: f32, options: AdaptOptions) -> (AdaptControl, Adapt<Self>) {
+        Adapt::new(self, initial_rms, options)
+    }
+
+    /// Smoothly map the signal into (-1, 1)
+    ///
+    /// See [`Reinhard`].
+    fn reinhard(self) -> Reinhard<Self> {
+        Reinhard::new(self)
+    }
+
+    /// Apply `f` to each frame of the signal
+    ///
+    /// See [`Map`].
+    fn map<F: FnMut(&mut Self::Frame)>(self, f: F) -> Map<Self, F> {
+        Map::new(self, f)
+    }
+
+    /// Adapt a mono signal to stereo by duplicating it
+    ///
+    /// See [`MonoToStereo`].
+    fn mono_to_stereo(self) -> MonoToStereo<Self>
+    where
+        Self: Signal<Frame = Sample>,
+    {
+        MonoToStereo::new(self)
+    }
+}
+
+impl<T: Signal> SignalExt for T {}
+
 /// Audio signals which support seeking
 ///
 /// Should only be implemented for signals which are defined deterministically in terms of absolute
@@ -57,6 +127,24 @@ impl<T: Seek + ?Sized> Seek for alloc::boxed::Box<T> {
     }
 }
 
+/// Signals whose internal state can be returned to how it was at construction
+///
+/// Useful for reproducible offline rendering: resetting a chain and sampling the same input again
+/// produces identical output. Implemented by stateful filters such as [`OnePole`](crate::OnePole),
+/// [`PeakingEq`](crate::PeakingEq), [`Adapt`](crate::Adapt), and [`Gain`](crate::Gain), and
+/// forwarded by combinators to their inner signal. Playback position is unaffected; see [`Seek`].
+pub trait Reset: Signal {
+    /// Discard state accumulated by previous calls to `sample`
+    fn reset(&mut self);
+}
+
+impl<T: Reset + ?Sized> Reset for alloc::boxed::Box<T> {
+    #[inline]
+    fn reset(&mut self) {
+        (**self).reset();
+    }
+}
+
 /// Adapts a mono signal to output stereo by duplicating its output
 pub struct MonoToStereo<T: ?Sized>(T);
 
@@ -82,6 +170,10 @@ impl<T: Signal<Frame = Sample>> Signal for MonoToStereo<T> {
     fn is_finished(&self) -> bool {
         self.0.is_finished()
     }
+
+    fn duration_remaining(&self) -> Option<f32> {
+        self.0.duration_remaining()
+    }
 }
 
 impl<T: Seek + Signal<Frame = Sample>> Seek for MonoToStereo<T> {
@@ -90,6 +182,12 @@ impl<T: Seek + Signal<Frame = Sample>> Seek for MonoToStereo<T> {
     }
 }
 
+impl<T: Reset + Signal<Frame = Sample>> Reset for MonoToStereo<T> {
+    fn reset(&mut self) {
+        self.0.reset()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -107,11 +205,37 @@ mod tests {
         }
     }
 
+    #[test]
+    fn chain() {
+        let (mut c, mut signal) = crate::Constant(2.0).mono_to_stereo().reinhard().gain();
+        c.set_amplitude_ratio(3.0);
+        let mut out = [[0.0; 2]; 2];
+        signal.sample(1.0, &mut out);
+        assert_eq!(out, [[2.0 / 3.0; 2], [2.0; 2]]);
+    }
+
+    #[test]
+    fn frame_multi() {
+        let mut data = [0.0, 1.0, 2.0, 3.0, 4.0, 5.0];
+        assert_eq!(
+            crate::frame_multi::<3>(&mut data),
+            &[[0.0, 1.0, 2.0], [3.0, 4.0, 5.0]]
+        );
+        assert_eq!(crate::frame_stereo(&mut data).len(), 3);
+        assert_eq!(crate::frame_stereo(&mut data[..5]).len(), 2);
+    }
+
+    #[test]
+    #[should_panic]
+    fn frame_multi_misaligned() {
+        crate::frame_multi::<4>(&mut [0.0; 6]);
+    }
+
     #[test]
     fn mono_to_stereo() {
         let mut signal = MonoToStereo::new(CountingSignal(0));
         let mut buf = [[0.0; 2]; 4];
-        signal.sample(1.0, (&mut buf[..]).into());
+        signal.sample(1.0, &mut buf);
         assert_eq!(buf, [[0.0, 0.0], [1.0, 1.0], [2.0, 2.0], [3.0, 3.0]]);
     }
 }
@@ -1,18 +1,20 @@
-use alloc::{boxed::Box, sync::Arc};
+use alloc::{boxed::Box, sync::Arc, vec};
 use core::{
-    ops::{Index, IndexMut},
-    sync::atomic::{AtomicBool, Ordering},
+    f32::consts::TAU,
+    sync::atomic::{AtomicBool, AtomicU32, Ordering},
 };
 
 use crate::{
     math::{add, dot, invert_quat, mix, norm, rotate, scale, sub, Float},
+    one_pole,
+    reverb::Tank,
     ring::Ring,
     set::{set, Set, SetHandle},
-    swap, Sample, Seek, Signal,
+    swap, ReverbControl, ReverbOptions, Sample, Seek, Signal, Smoothed,
 };
 
 type ErasedSpatialBuffered = Box<SpatialSignalBuffered<dyn Signal<Frame = Sample> + Send>>;
-type ErasedSpatial = Box<SpatialSignal<dyn Seek<Frame = Sample> + Send>>;
+type ErasedSpatial = SpatialSignal<Box<dyn Seek<Frame = Sample> + Send>>;
 
 /// An individual buffered spatialized signal
 struct SpatialSignalBuffered<T: ?Sized> {
@@ -31,20 +33,18 @@ impl<T> SpatialSignalBuffered<T> {
     fn new(
         rate: u32,
         inner: T,
-        position: mint::Point3<f32>,
-        velocity: mint::Vector3<f32>,
+        options: &SpatialOptions,
         max_delay: f32,
-        radius: f32,
-    ) -> (swap::Sender<Motion>, Arc<AtomicBool>, Self) {
+        channels: usize,
+    ) -> (Spatial, Self) {
         let mut queue = Ring::new((max_delay * rate as f32).ceil() as usize + 1);
         queue.delay(
             rate,
-            (norm(position.into()) / SPEED_OF_SOUND).min(max_delay),
+            (norm(options.position.into()) / SPEED_OF_SOUND).min(max_delay),
         );
-        let (send, finished, recv) = Common::new(radius, position, velocity);
+        let (handle, recv) = Common::new(options, channels);
         (
-            send,
-            finished,
+            handle,
             Self {
                 rate,
                 max_delay,
@@ -63,16 +63,10 @@ struct SpatialSignal<T: ?Sized> {
 }
 
 impl<T> SpatialSignal<T> {
-    fn new(
-        inner: T,
-        position: mint::Point3<f32>,
-        velocity: mint::Vector3<f32>,
-        radius: f32,
-    ) -> (swap::Sender<Motion>, Arc<AtomicBool>, Self) {
-        let (send, finished, recv) = Common::new(radius, position, velocity);
+    fn new(inner: T, options: &SpatialOptions, channels: usize) -> (Spatial, Self) {
+        let (handle, recv) = Common::new(options, channels);
         (
-            send,
-            finished,
+            handle,
             Self {
                 common: recv,
                 inner,
@@ -82,35 +76,68 @@ impl<T> SpatialSignal<T> {
 }
 
 struct Common {
-    radius: f32,
+    radius: Parameter,
+    /// Playback speed of the signal, independent of Doppler effect
+    pitch: Parameter,
+    cone: Cone,
     motion: swap::Receiver<Motion>,
+    /// Direction the source faces, in world space
+    direction: swap::Receiver<mint::Vector3<f32>>,
+    occlusion: Occlusion,
+    /// Amount of the signal sent to the scene's reverb bus
+    reverb_send: f32,
     state: State,
+    observed: Arc<Observed>,
     /// How long ago the signal finished, if it did
     finished_for: Option<f32>,
     stopped: Arc<AtomicBool>,
+    paused: Arc<AtomicBool>,
 }
 
 impl Common {
-    fn new(
-        radius: f32,
-        position: mint::Point3<f32>,
-        velocity: mint::Vector3<f32>,
-    ) -> (swap::Sender<Motion>, Arc<AtomicBool>, Self) {
+    fn new(options: &SpatialOptions, channels: usize) -> (Spatial, Self) {
         let finished = Arc::new(AtomicBool::new(false));
-        let (send, recv) = swap::swap(|| Motion {
-            position,
-            velocity,
+        let paused = Arc::new(AtomicBool::new(false));
+        let (motion_send, motion_recv) = swap::swap(|| Motion {
+            position: options.position,
+            velocity: options.velocity,
             discontinuity: false,
         });
+        let (direction_send, direction_recv) = swap::swap(|| options.direction);
+        let radius = Parameter::new(options.radius);
+        let pitch = Parameter::new(options.pitch);
+        let occlusion = Occlusion::new(channels);
+        let distance = norm(options.position.into());
+        let observed = Arc::new(Observed::default());
+        observed.store(
+            distance,
+            distance_gain(options.radius, distance)
+                * options.cone.gain(options.direction, options.position),
+        );
         (
-            send,
-            finished.clone(),
+            Spatial {
+                motion: motion_send,
+                direction: direction_send,
+                radius: radius.shared.clone(),
+                pitch: pitch.shared.clone(),
+                occlusion: occlusion.amount.shared.clone(),
+                observed: observed.clone(),
+                finished: finished.clone(),
+                paused: paused.clone(),
+            },
             Self {
                 radius,
-                motion: recv,
-                state: State::new(position),
+                pitch,
+                cone: options.cone,
+                motion: motion_recv,
+                direction: direction_recv,
+                occlusion,
+                reverb_send: options.reverb_send,
+                state: State::new(options.position),
+                observed,
                 finished_for: None,
                 stopped: finished,
+                paused,
             },
         )
     }
@@ -119,7 +146,13 @@ impl Common {
 /// Control for updating the motion of a spatial signal
 pub struct Spatial {
     motion: swap::Sender<Motion>,
+    direction: swap::Sender<mint::Vector3<f32>>,
+    radius: Arc<AtomicU32>,
+    pitch: Arc<AtomicU32>,
+    occlusion: Arc<AtomicU32>,
+    observed: Arc<Observed>,
     finished: Arc<AtomicBool>,
+    paused: Arc<AtomicBool>,
 }
 
 impl Spatial {
@@ -148,19 +181,126 @@ impl Spatial {
         self.motion.flush();
     }
 
+    /// Update the direction the signal faces, which determines its attenuation by
+    /// [`SpatialOptions::cone`]
+    ///
+    /// Like positions, directions are in world space and need not be normalized.
+    pub fn set_direction(&mut self, direction: mint::Vector3<f32>) {
+        *self.direction.pending() = direction;
+        self.direction.flush();
+    }
+
+    /// Change the radius of the signal's source, e.g. for a growing explosion
+    ///
+    /// See [`SpatialOptions::radius`]. Changes take effect smoothly over a short period.
+    pub fn set_radius(&mut self, radius: f32) {
+        self.radius.store(radius.to_bits(), Ordering::Relaxed);
+    }
+
+    /// Change the speed at which the signal plays, independent of Doppler effect
+    ///
+    /// See [`SpatialOptions::pitch`]. Changes take effect smoothly over a short period.
+    pub fn set_pitch(&mut self, pitch: f32) {
+        self.pitch.store(pitch.to_bits(), Ordering::Relaxed);
+    }
+
+    /// Muffle the signal, e.g. when it's behind a wall
+    ///
+    /// `occlusion` ranges from 0, for no effect, to 1, for a fully occluded signal. Occlusion both
+    /// reduces the amplitude of the signal and filters out high frequencies, and is applied after
+    /// distance attenuation. Changes take effect smoothly over a short period.
+    pub fn set_occlusion(&mut self, occlusion: f32) {
+        self.occlusion
+            .store(occlusion.clamp(0.0, 1.0).to_bits(), Ordering::Relaxed);
+    }
+
+    /// Distance in meters between the listener and the signal's source
+    ///
+    /// Updated by the audio thread, so lags behind [`set_motion`](Self::set_motion) by up to one
+    /// block.
+    pub fn current_distance(&self) -> f32 {
+        self.observed.distance()
+    }
+
+    /// Attenuation currently applied to the signal by distance, directivity, and occlusion
+    ///
+    /// Excludes the panning between the listener's ears. Updated by the audio thread, so lags by up
+    /// to one block.
+    pub fn current_gain(&self) -> f32 {
+        self.observed.gain()
+    }
+
     /// Whether the signal has completed and can no longer be heard
     ///
-    /// Accounts for signals still audible due to propagation delay.
+    /// Accounts for signals still audible due to propagation delay. Updated by the audio thread, so
+    /// lags by up to one block.
     pub fn is_finished(&self) -> bool {
         self.finished.load(Ordering::Relaxed)
     }
+
+    /// Suspend playback of the signal, without discarding it
+    ///
+    /// While paused, the signal is silent and frozen in time: sound already propagating towards
+    /// the listener is held rather than lost, and smoothing of its motion and other parameters is
+    /// suspended. Changes made while paused take effect after playback resumes.
+    pub fn pause(&mut self) {
+        self.paused.store(true, Ordering::Relaxed);
+    }
+
+    /// Resume playback of a paused signal from where it left off
+    pub fn resume(&mut self) {
+        self.paused.store(false, Ordering::Relaxed);
+    }
+
+    /// Whether the signal is paused
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::Relaxed)
+    }
 }
 
-/// [`Signal`] for stereo output from a spatial scene
-pub struct SpatialScene {
+/// [`Signal`] for stereo or surround output from a spatial scene
+///
+/// Stereo scenes, constructed with [`new`](Self::new), render the scene as heard by a pair of ears.
+/// Surround scenes, constructed with [`new_surround`](Self::new_surround), instead render one
+/// channel for each of a set of loudspeakers.
+///
+/// # Multiple listeners
+///
+/// A scene renders from the perspective of a single listener. To render several listeners, e.g. for
+/// split-screen, construct one scene per listener and play each source in every scene, positioned
+/// relative to that scene's listener. Sources backed by [`Frames`](crate::Frames) share their
+/// decoded audio through the `Arc`, so this costs no more memory than playing each source once:
+///
+/// ```
+/// # let frames = oddio::Frames::from_slice(44100, &[0.0f32; 16]);
+/// # let (position, velocity) = ([0.0; 3].into(), [0.0; 3].into());
+/// let (mut control_a, mut scene_a) = oddio::SpatialScene::new();
+/// let (mut control_b, mut scene_b) = oddio::SpatialScene::new();
+/// let options = oddio::SpatialOptions { position, velocity, ..Default::default() };
+/// let handle_a = control_a.play(oddio::FramesSignal::from(frames.clone()), options);
+/// let handle_b = control_b.play(oddio::FramesSignal::from(frames), options);
+///
+/// // Each listener gets its own output buffer
+/// let mut out_a = [[0.0; 2]; 64];
+/// let mut out_b = [[0.0; 2]; 64];
+/// oddio::run(&mut scene_a, 44100, &mut out_a);
+/// oddio::run(&mut scene_b, 44100, &mut out_b);
+/// ```
+pub struct SpatialScene<const N: usize = 2> {
+    speakers: [Speaker; N],
     rot: swap::Receiver<mint::Quaternion<f32>>,
     recv_buffered: Set<ErasedSpatialBuffered>,
     recv: Set<ErasedSpatial>,
+    /// Staging buffer for samples from component signals
+    buf: Box<[Sample]>,
+    /// Fixed-size block rendering state, if enabled
+    block: Option<Block<N>>,
+    reverb: Option<ReverbBus>,
+    /// Frames of output produced so far
+    frames: u64,
+    clock: swap::Sender<u64>,
+    /// Bits of the largest absolute sample in the most recent output block
+    peak: Arc<AtomicU32>,
 }
 
 impl SpatialScene {
@@ -168,45 +308,129 @@ impl SpatialScene {
     ///
     /// Samples its component signals at `rate`.
     pub fn new() -> (SpatialSceneControl, Self) {
+        Self::with_buffer_size(DEFAULT_BUFFER_SIZE)
+    }
+
+    /// Like [`new`](Self::new), but samples component signals `frames` at a time
+    ///
+    /// Larger buffers improve throughput when sampling large blocks, while smaller ones reduce
+    /// memory use. The default is 256 frames.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `frames` is zero.
+    pub fn with_buffer_size(frames: usize) -> (SpatialSceneControl, Self) {
+        Self::with_speakers(frames, EARS)
+    }
+}
+
+impl<const N: usize> SpatialScene<N> {
+    /// Create a [`Signal`] for spatializing mono signals for output to the speakers in `layout`
+    ///
+    /// Each speaker's channel is computed with the same model of distance, direction, and delay as
+    /// each ear in a stereo scene, so the cost of each signal in the scene is proportional to the
+    /// number of speakers. [`Speaker::quad`] and [`Speaker::surround_5_1`] provide common layouts.
+    pub fn new_surround(layout: [Speaker; N]) -> (SpatialSceneControl, Self) {
+        Self::with_speakers(DEFAULT_BUFFER_SIZE, layout)
+    }
+
+    fn with_speakers(frames: usize, speakers: [Speaker; N]) -> (SpatialSceneControl, Self) {
+        assert!(frames > 0, "buffer size must be nonzero");
         let (seek_handle, seek_set) = set();
         let (buffered_handle, buffered_set) = set();
         let (rot_send, rot_recv) = swap::swap(|| mint::Quaternion {
             s: 1.0,
             v: [0.0; 3].into(),
         });
+        let (clock_send, clock_recv) = swap::swap(|| 0);
+        let peak = Arc::new(AtomicU32::new(0.0f32.to_bits()));
         let control = SpatialSceneControl {
             rot: rot_send,
+            clock: clock_recv,
+            peak: peak.clone(),
             seek: seek_handle,
             buffered: buffered_handle,
+            channels: N,
         };
         let signal = SpatialScene {
+            speakers,
             rot: rot_recv,
             recv_buffered: buffered_set,
             recv: seek_set,
+            buf: vec![0.0; frames].into(),
+            block: None,
+            reverb: None,
+            frames: 0,
+            clock: clock_send,
+            peak,
         };
         (control, signal)
     }
+
+    /// Add a reverb bus tuned for sample rate `rate`, shared by all signals
+    ///
+    /// Each signal contributes to the bus according to its [`SpatialOptions::reverb_send`], scaled
+    /// by its distance, directivity, and occlusion attenuation. The reverberated result is added to
+    /// the scene's output, and its tail persists after the signals that produced it finish.
+    ///
+    /// The bus costs about as much as a single [`Reverb`](crate::Reverb) regardless of how many
+    /// signals are sending to it, plus a multiply-add per frame for each sending signal. Each
+    /// signal is sampled once per speaker per block into a shared staging buffer, and the send
+    /// reads the samples staged for the first speaker, so routing a signal to the bus never samples
+    /// it again or buffers its propagation delay a second time.
+    pub fn with_reverb(mut self, rate: u32, options: ReverbOptions) -> (ReverbControl, Self) {
+        let (control, tank) = Tank::new(rate, options);
+        self.reverb = Some(ReverbBus {
+            tank,
+            send: vec![0.0; REVERB_BUFFER_SIZE].into(),
+        });
+        (control, self)
+    }
+
+    /// Render internally in blocks of exactly `frames` frames
+    ///
+    /// By default, each call to [`Signal::sample`] is processed as one block, over which motion and
+    /// other parameters are interpolated, so output varies slightly with the size of the output
+    /// buffer. With fixed blocks, output is independent of how the scene is sampled, which allows
+    /// offline renders to exactly match real-time ones. Each block is rendered ahead of time
+    /// using the interval of the call that needed it, so changes made through controls take effect
+    /// up to `frames` frames later than they otherwise would.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `frames` is zero.
+    pub fn with_fixed_blocks(mut self, frames: usize) -> Self {
+        assert!(frames > 0, "block size must be nonzero");
+        self.block = Some(Block {
+            frames: vec![[0.0; N]; frames].into(),
+            consumed: frames,
+        });
+        self
+    }
 }
 
 fn walk_set<T, U>(
-    set: &mut Set<Box<T>>,
+    set: &mut Set<T>,
     get_common: impl Fn(&mut T) -> &mut Common,
     get_inner: impl Fn(&T) -> &U,
     prev_rot: &mint::Quaternion<f32>,
     rot: &mint::Quaternion<f32>,
     elapsed: f32,
-    mut mix_signal: impl FnMut(&mut T, mint::Point3<f32>, mint::Point3<f32>),
+    mut mix_signal: impl FnMut(&mut T, Placement, Placement),
 ) where
-    T: ?Sized,
     U: Signal + ?Sized,
 {
     set.update();
     for i in (0..set.len()).rev() {
         let signal = &mut set[i];
         let common = get_common(signal);
+        if common.paused.load(Ordering::Relaxed) {
+            // Leave all state untouched, so playback resumes seamlessly
+            continue;
+        }
 
-        let prev_position;
-        let next_position;
+        let prev;
+        let next;
         {
             // Compute the signal's smoothed start/end positions over the sampled period
             // TODO: Use historical positions
@@ -225,22 +449,42 @@ fn walk_set<T, U>(
                 debug_assert_eq!(orig_next.position, common.motion.received().position);
             }
 
-            prev_position = rotate(
-                prev_rot,
-                &state.smoothed_position(0.0, common.motion.received()),
-            );
-            next_position = rotate(
-                rot,
-                &state.smoothed_position(elapsed, common.motion.received()),
-            );
+            let prev_direction = *common.direction.received();
+            common.direction.refresh();
+            let next_direction = *common.direction.received();
+
+            let prev_radius = common.radius.get();
+            let next_radius = common.radius.advance(elapsed);
+            let prev_occlusion = common.occlusion.amount.get();
+            let next_occlusion = common.occlusion.amount.advance(elapsed);
+
+            let prev_position = state.smoothed_position(0.0, common.motion.received());
+            let next_position = state.smoothed_position(elapsed, common.motion.received());
+            prev = Placement {
+                position: rotate(prev_rot, &prev_position),
+                radius: prev_radius,
+                gain: common.cone.gain(prev_direction, prev_position)
+                    * Occlusion::gain(prev_occlusion),
+            };
+            next = Placement {
+                position: rotate(rot, &next_position),
+                radius: next_radius,
+                gain: common.cone.gain(next_direction, next_position)
+                    * Occlusion::gain(next_occlusion),
+            };
 
             // Set up for next time
             state.dt += elapsed;
+
+            let distance = norm(next.position.into());
+            common
+                .observed
+                .store(distance, next.gain * distance_gain(next.radius, distance));
         }
 
         // Discard finished sources. If a source is moving away faster than the speed of sound, you
         // might get a pop.
-        let distance = norm(prev_position.into());
+        let distance = norm(prev.position.into());
         match common.finished_for {
             Some(t) => {
                 if t > distance / SPEED_OF_SOUND {
@@ -260,15 +504,30 @@ fn walk_set<T, U>(
             continue;
         }
 
-        mix_signal(signal, prev_position, next_position);
+        mix_signal(signal, prev, next);
     }
 }
 
+/// Location of a signal relative to the listener at a certain time
+#[derive(Debug, Copy, Clone)]
+struct Placement {
+    /// Position in the listener's frame of reference
+    position: mint::Point3<f32>,
+    /// Size of the source
+    radius: f32,
+    /// Attenuation independent of the listener's orientation
+    gain: f32,
+}
+
 /// Control for modifying a [`SpatialScene`]
 pub struct SpatialSceneControl {
     rot: swap::Sender<mint::Quaternion<f32>>,
+    clock: swap::Receiver<u64>,
+    peak: Arc<AtomicU32>,
     seek: SetHandle<ErasedSpatial>,
     buffered: SetHandle<ErasedSpatialBuffered>,
+    /// Number of channels rendered by the scene
+    channels: usize,
 }
 
 impl SpatialSceneControl {
@@ -290,14 +549,21 @@ impl SpatialSceneControl {
     where
         S: Seek<Frame = Sample> + Send + 'static,
     {
-        let (send, finished, recv) =
-            SpatialSignal::new(signal, options.position, options.velocity, options.radius);
-        let signal = Box::new(recv);
-        let handle = Spatial {
-            motion: send,
-            finished,
-        };
-        self.seek.insert(signal);
+        self.play_boxed(Box::new(signal), options)
+    }
+
+    /// Like [`play`](Self::play), but take an already-boxed signal
+    ///
+    /// Avoids allocating storage for `signal` on the calling thread, e.g. when boxes are recycled
+    /// from a pool. State shared with the returned handle is still allocated. The audio thread
+    /// never allocates or frees memory regardless.
+    pub fn play_boxed(
+        &mut self,
+        signal: Box<dyn Seek<Frame = Sample> + Send>,
+        options: SpatialOptions,
+    ) -> Spatial {
+        let (handle, recv) = SpatialSignal::new(signal, &options, self.channels);
+        self.seek.insert(recv);
         handle
     }
 
@@ -322,23 +588,33 @@ impl SpatialSceneControl {
     where
         S: Signal<Frame = Sample> + Send + 'static,
     {
-        let (send, finished, recv) = SpatialSignalBuffered::new(
+        let (handle, recv) = SpatialSignalBuffered::new(
             rate,
             signal,
-            options.position,
-            options.velocity,
+            &options,
             max_distance / SPEED_OF_SOUND + buffer_duration,
-            options.radius,
+            self.channels,
         );
-        let signal = Box::new(recv);
-        let handle = Spatial {
-            motion: send,
-            finished,
-        };
-        self.buffered.insert(signal);
+        self.buffered.insert(Box::new(recv));
         handle
     }
 
+    /// Number of frames the scene has produced
+    ///
+    /// A reliable time base for synchronizing e.g. animation with audio. At 192kHz, takes millions
+    /// of years to wrap around. Lags behind the audio thread by up to one `sample` call.
+    pub fn frames_processed(&mut self) -> u64 {
+        self.clock.refresh();
+        *self.clock.received()
+    }
+
+    /// Largest absolute sample value, across all channels, in the most recent block of output
+    ///
+    /// Values above 1.0 indicate the output will clip. Reset on every `sample` call.
+    pub fn peak(&self) -> f32 {
+        f32::from_bits(self.peak.load(Ordering::Relaxed))
+    }
+
     /// Set the listener's rotation
     ///
     /// An unrotated listener faces -Z, with +X to the right and +Y up.
@@ -351,13 +627,31 @@ impl SpatialSceneControl {
 
 /// Passed to [`SpatialSceneControl::play`]
 #[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SpatialOptions {
     /// Initial position
     pub position: mint::Point3<f32>,
     /// Initial velocity
     pub velocity: mint::Vector3<f32>,
     /// Distance of zero attenuation. Approaching closer does not increase volume.
+    ///
+    /// May be changed later with [`Spatial::set_radius`].
     pub radius: f32,
+    /// Initial direction the source faces, for use with `cone`
+    pub direction: mint::Vector3<f32>,
+    /// Attenuation depending on `direction`, for sources which are louder in some directions than
+    /// others. Isotropic by default.
+    pub cone: Cone,
+    /// Amount of the signal sent to the scene's reverb bus, if any
+    ///
+    /// See [`SpatialScene::with_reverb`]. 0 by default.
+    pub reverb_send: f32,
+    /// Playback speed of the signal, e.g. 2 to play back an octave higher, faster
+    ///
+    /// Useful for adding variety to repeated sounds like footsteps. Combines multiplicatively with
+    /// the pitch shift produced by the Doppler effect. 1 by default. May be changed later with
+    /// [`Spatial::set_pitch`].
+    pub pitch: f32,
 }
 
 impl Default for SpatialOptions {
@@ -366,14 +660,76 @@ impl Default for SpatialOptions {
             position: [0.0; 3].into(),
             velocity: [0.0; 3].into(),
             radius: 0.1,
+            direction: [0.0, 0.0, -1.0].into(),
+            cone: Cone::default(),
+            reverb_send: 0.0,
+            pitch: 1.0,
         }
     }
 }
 
-impl Signal for SpatialScene {
-    type Frame = [Sample; 2];
+/// Directional attenuation of a spatial source, e.g. a loudspeaker or a character's voice
+///
+/// Listeners within `inner_angle` of the source's direction hear it at full volume. Outside
+/// `outer_angle`, its amplitude is scaled by `outer_gain`. In between, the gain is interpolated
+/// linearly with the angle. Angles are in radians, and measure the full width of the cone.
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Cone {
+    /// Width of the cone within which the source is not attenuated
+    pub inner_angle: f32,
+    /// Width of the cone outside which the source is attenuated by `outer_gain`
+    pub outer_angle: f32,
+    /// Amplitude scaling applied outside `outer_angle`
+    pub outer_gain: f32,
+}
+
+impl Cone {
+    /// Attenuation for a source facing `direction` at `position` relative to the listener
+    fn gain(&self, direction: mint::Vector3<f32>, position: mint::Point3<f32>) -> f32 {
+        let to_listener = scale(position.into(), -1.0);
+        let denominator = norm(direction) * norm(to_listener);
+        if denominator < 1e-6 {
+            return 1.0;
+        }
+        let angle = 2.0
+            * (dot(direction, to_listener) / denominator)
+                .clamp(-1.0, 1.0)
+                .acos();
+        if angle <= self.inner_angle {
+            1.0
+        } else if angle >= self.outer_angle {
+            self.outer_gain
+        } else {
+            let t = (angle - self.inner_angle) / (self.outer_angle - self.inner_angle);
+            1.0 + t * (self.outer_gain - 1.0)
+        }
+    }
+}
+
+impl Default for Cone {
+    fn default() -> Self {
+        Self {
+            inner_angle: TAU,
+            outer_angle: TAU,
+            outer_gain: 1.0,
+        }
+    }
+}
+
+impl<const N: usize> SpatialScene<N> {
+    /// Mix all signals into `out` in a single block
+    fn render(&mut self, interval: f32, out: &mut [[Sample; N]]) {
+        if let Some(ref mut reverb) = self.reverb {
+            if out.len() > reverb.send.len() {
+                for chunk in out.chunks_mut(REVERB_BUFFER_SIZE) {
+                    self.render(interval, chunk);
+                }
+                return;
+            }
+            reverb.send[..out.len()].fill(0.0);
+        }
 
-    fn sample(&mut self, interval: f32, out: &mut [[Sample; 2]]) {
         let set = &mut self.recv_buffered;
         // Update set contents
         set.update();
@@ -387,10 +743,12 @@ impl Signal for SpatialScene {
 
         // Zero output in preparation for mixing
         for frame in &mut *out {
-            *frame = [0.0; 2];
+            *frame = [0.0; N];
         }
 
-        let mut buf = [0.0; 256];
+        let speakers = &self.speakers;
+        let buf = &mut self.buf;
+        let mut send = self.reverb.as_mut().map(|x| &mut x.send[..out.len()]);
         let elapsed = interval * out.len() as f32;
         walk_set(
             set,
@@ -399,35 +757,41 @@ impl Signal for SpatialScene {
             &prev_rot,
             &rot,
             elapsed,
-            |signal, prev_position, next_position| {
+            |signal, prev, next| {
                 debug_assert!(signal.max_delay >= elapsed);
 
                 // Extend delay queue with new data
-                signal.queue.write(&mut signal.inner, signal.rate, elapsed);
+                let pitch = signal.common.pitch.advance(elapsed);
+                signal
+                    .queue
+                    .write_at_speed(&mut signal.inner, signal.rate, elapsed, pitch);
 
                 // Mix into output
-                for &ear in &[Ear::Left, Ear::Right] {
-                    let prev_state = EarState::new(prev_position, ear, signal.common.radius);
-                    let next_state = EarState::new(next_position, ear, signal.common.radius);
+                let alpha = signal.common.occlusion.alpha(interval);
+                let mut reverb =
+                    ReverbSend::new(send.as_deref_mut(), signal.common.reverb_send, prev, next);
+                for (channel, speaker) in speakers.iter().enumerate() {
+                    let prev_state = EarState::new(prev, speaker);
+                    let next_state = EarState::new(next, speaker);
 
                     // Clamp into the max length of the delay queue
                     let prev_offset = (prev_state.offset - elapsed).max(-signal.max_delay);
                     let next_offset = next_state.offset.max(-signal.max_delay);
 
                     let dt = (next_offset - prev_offset) / out.len() as f32;
-                    let d_gain = (next_state.gain - prev_state.gain) / out.len() as f32;
 
-                    let mut i = 0;
+                    let rate = signal.rate;
                     let queue = &mut signal.queue;
-                    for chunk in out.chunks_mut(buf.len()) {
-                        let t = prev_offset + i as f32 * dt;
-                        queue.sample(signal.rate, t, dt, &mut buf[..chunk.len()]);
-                        for (s, o) in buf.iter().copied().zip(chunk) {
-                            let gain = prev_state.gain + i as f32 * d_gain;
-                            o[ear as usize] += s * gain;
-                            i += 1;
-                        }
-                    }
+                    let occlusion = &mut signal.common.occlusion;
+                    mix_channel(
+                        out,
+                        channel,
+                        buf,
+                        [prev_state.gain, next_state.gain],
+                        |x| occlusion.filter(channel, alpha, x),
+                        if channel == 0 { reverb.as_mut() } else { None },
+                        |i, chunk| queue.sample(rate, prev_offset + i as f32 * dt, dt, chunk),
+                    );
                 }
             },
         );
@@ -442,32 +806,145 @@ impl Signal for SpatialScene {
             &prev_rot,
             &rot,
             elapsed,
-            |signal, prev_position, next_position| {
-                for &ear in &[Ear::Left, Ear::Right] {
-                    let prev_state = EarState::new(prev_position, ear, signal.common.radius);
-                    let next_state = EarState::new(next_position, ear, signal.common.radius);
-                    signal.inner.seek(prev_state.offset); // Initial real time -> Initial delayed
+            |signal, prev, next| {
+                let alpha = signal.common.occlusion.alpha(interval);
+                // Scales the signal's timeline relative to real time
+                let pitch = signal.common.pitch.advance(elapsed);
+                let mut reverb =
+                    ReverbSend::new(send.as_deref_mut(), signal.common.reverb_send, prev, next);
+                for (channel, speaker) in speakers.iter().enumerate() {
+                    let prev_state = EarState::new(prev, speaker);
+                    let next_state = EarState::new(next, speaker);
+                    signal.inner.seek(prev_state.offset * pitch); // Initial real time -> Initial delayed
 
                     let effective_elapsed = (elapsed + next_state.offset) - prev_state.offset;
                     let dt = effective_elapsed / out.len() as f32;
-                    let d_gain = (next_state.gain - prev_state.gain) / out.len() as f32;
-
-                    let mut i = 0;
-                    for chunk in out.chunks_mut(buf.len()) {
-                        signal.inner.sample(dt, &mut buf[..chunk.len()]);
-                        for (s, o) in buf.iter().copied().zip(chunk) {
-                            let gain = prev_state.gain + i as f32 * d_gain;
-                            o[ear as usize] += s * gain;
-                            i += 1;
-                        }
-                    }
+
+                    let inner = &mut signal.inner;
+                    let occlusion = &mut signal.common.occlusion;
+                    mix_channel(
+                        out,
+                        channel,
+                        buf,
+                        [prev_state.gain, next_state.gain],
+                        |x| occlusion.filter(channel, alpha, x),
+                        if channel == 0 { reverb.as_mut() } else { None },
+                        |_, chunk| inner.sample(dt * pitch, chunk),
+                    );
                     // Final delayed -> Initial real time
-                    signal.inner.seek(-effective_elapsed - prev_state.offset);
+                    signal
+                        .inner
+                        .seek((-effective_elapsed - prev_state.offset) * pitch);
                 }
                 // Initial real time -> Final real time
-                signal.inner.seek(elapsed);
+                signal.inner.seek(elapsed * pitch);
             },
         );
+
+        if let Some(ref mut reverb) = self.reverb {
+            reverb.tank.update(interval);
+            for (o, &s) in out.iter_mut().zip(&*reverb.send) {
+                let [l, r] = reverb.tank.process(s);
+                for (x, speaker) in o.iter_mut().zip(&self.speakers) {
+                    let [wl, wr] = speaker.reverb_weights();
+                    *x += wl * l + wr * r;
+                }
+            }
+        }
+    }
+}
+
+/// Reverb shared by all signals in a [`SpatialScene`]
+struct ReverbBus {
+    tank: Tank,
+    /// Sum of the signals sent to the reverb over the current block
+    send: Box<[Sample]>,
+}
+
+/// A single signal's contribution to the reverb bus over the current block
+struct ReverbSend<'a> {
+    bus: &'a mut [Sample],
+    /// Gain at the start of the block
+    gain: f32,
+    /// Change in gain per frame
+    d_gain: f32,
+}
+
+impl<'a> ReverbSend<'a> {
+    /// Send a signal moving from `prev` to `next` to `bus`, scaled by `amount` and the signal's
+    /// attenuation, or `None` if nothing would be sent
+    fn new(
+        bus: Option<&'a mut [Sample]>,
+        amount: f32,
+        prev: Placement,
+        next: Placement,
+    ) -> Option<Self> {
+        let bus = match bus {
+            Some(x) if amount > 0.0 => x,
+            _ => return None,
+        };
+        let gain =
+            |p: Placement| amount * p.gain * distance_gain(p.radius, norm(p.position.into()));
+        let prev_gain = gain(prev);
+        Some(Self {
+            d_gain: (gain(next) - prev_gain) / bus.len() as f32,
+            gain: prev_gain,
+            bus,
+        })
+    }
+
+    /// Accumulate `samples`, starting `offset` frames into the block
+    fn accumulate(&mut self, offset: usize, samples: &[Sample]) {
+        for (i, (&s, o)) in samples.iter().zip(&mut self.bus[offset..]).enumerate() {
+            *o += s * (self.gain + (offset + i) as f32 * self.d_gain);
+        }
+    }
+}
+
+/// Mix one channel of a signal into `out`, ramping between the `gain`s at the start and end of the
+/// block
+///
+/// `fill(offset, chunk)` samples the signal as heard by the channel's speaker, starting `offset`
+/// frames into the block. Each chunk is sampled exactly once into the shared staging buffer `buf`,
+/// and every destination reads from there: the speaker's channel through `filter`, and the reverb
+/// bus if `reverb` is set. Sending a signal to the reverb therefore costs no additional sampling or
+/// propagation delay buffering.
+fn mix_channel<const N: usize>(
+    out: &mut [[Sample; N]],
+    channel: usize,
+    buf: &mut [Sample],
+    gain: [f32; 2],
+    mut filter: impl FnMut(Sample) -> Sample,
+    mut reverb: Option<&mut ReverbSend<'_>>,
+    mut fill: impl FnMut(usize, &mut [Sample]),
+) {
+    let d_gain = (gain[1] - gain[0]) / out.len() as f32;
+    let mut i = 0;
+    for chunk in out.chunks_mut(buf.len()) {
+        let staged = &mut buf[..chunk.len()];
+        fill(i, staged);
+        if let Some(ref mut reverb) = reverb {
+            reverb.accumulate(i, staged);
+        }
+        for (&s, o) in staged.iter().zip(chunk) {
+            o[channel] += filter(s * (gain[0] + i as f32 * d_gain));
+            i += 1;
+        }
+    }
+}
+
+impl<const N: usize> Signal for SpatialScene<N> {
+    type Frame = [Sample; N];
+
+    fn sample(&mut self, interval: f32, out: &mut [[Sample; N]]) {
+        self.frames += out.len() as u64;
+        *self.clock.pending() = self.frames;
+        self.clock.flush();
+
+        self.fill(interval, out);
+
+        let peak = out.iter().flatten().fold(0.0f32, |acc, x| acc.max(x.abs()));
+        self.peak.store(peak.to_bits(), Ordering::Relaxed);
     }
 
     #[inline]
@@ -476,6 +953,142 @@ impl Signal for SpatialScene {
     }
 }
 
+impl<const N: usize> SpatialScene<N> {
+    /// Write output, rendered directly or through fixed-size blocks, to `out`
+    fn fill(&mut self, interval: f32, mut out: &mut [[Sample; N]]) {
+        let mut block = match self.block.take() {
+            None => {
+                self.render(interval, out);
+                return;
+            }
+            Some(x) => x,
+        };
+        while !out.is_empty() {
+            if block.consumed == block.frames.len() {
+                self.render(interval, &mut block.frames);
+                block.consumed = 0;
+            }
+            let available = &block.frames[block.consumed..];
+            let n = available.len().min(out.len());
+            out[..n].copy_from_slice(&available[..n]);
+            block.consumed += n;
+            out = &mut out[n..];
+        }
+        self.block = Some(block);
+    }
+}
+
+/// Output rendered ahead of time by a [`SpatialScene`] using fixed-size blocks
+struct Block<const N: usize> {
+    frames: Box<[[Sample; N]]>,
+    /// Number of leading frames already returned
+    consumed: usize,
+}
+
+/// Muffling of a signal by obstacles between it and the listener
+struct Occlusion {
+    amount: Parameter,
+    /// Low-pass filter state for each channel
+    lowpass: Box<[Sample]>,
+}
+
+impl Occlusion {
+    fn new(channels: usize) -> Self {
+        Self {
+            amount: Parameter::new(0.0),
+            lowpass: vec![0.0; channels].into(),
+        }
+    }
+
+    /// Amplitude scaling for a certain amount of occlusion
+    fn gain(amount: f32) -> f32 {
+        1.0 + amount * (OCCLUDED_GAIN - 1.0)
+    }
+
+    /// Low-pass filter coefficient for the current amount of occlusion, given the time between
+    /// output samples
+    fn alpha(&self, interval: f32) -> f32 {
+        let amount = self.amount.get();
+        if amount <= 0.0 {
+            return 1.0;
+        }
+        let cutoff = UNOCCLUDED_CUTOFF * (OCCLUDED_CUTOFF / UNOCCLUDED_CUTOFF).powf(amount);
+        one_pole::alpha(cutoff, interval)
+    }
+
+    /// Filter a single sample for `channel`
+    #[inline]
+    fn filter(&mut self, channel: usize, alpha: f32, x: Sample) -> Sample {
+        let y = &mut self.lowpass[channel];
+        *y += alpha * (x - *y);
+        *y
+    }
+}
+
+/// State of a source most recently computed by the audio thread
+#[derive(Default)]
+struct Observed {
+    distance: AtomicU32,
+    gain: AtomicU32,
+}
+
+impl Observed {
+    fn distance(&self) -> f32 {
+        f32::from_bits(self.distance.load(Ordering::Relaxed))
+    }
+
+    fn gain(&self) -> f32 {
+        f32::from_bits(self.gain.load(Ordering::Relaxed))
+    }
+
+    fn store(&self, distance: f32, gain: f32) {
+        self.distance.store(distance.to_bits(), Ordering::Relaxed);
+        self.gain.store(gain.to_bits(), Ordering::Relaxed);
+    }
+}
+
+/// A per-source value set from the [`Spatial`] handle and smoothed on the audio thread
+struct Parameter {
+    shared: Arc<AtomicU32>,
+    value: Smoothed<f32>,
+}
+
+impl Parameter {
+    fn new(value: f32) -> Self {
+        Self {
+            shared: Arc::new(AtomicU32::new(value.to_bits())),
+            value: Smoothed::new(value),
+        }
+    }
+
+    fn get(&self) -> f32 {
+        self.value.get()
+    }
+
+    /// Advance smoothing by `elapsed` seconds, returning the new value
+    #[allow(clippy::float_cmp)]
+    fn advance(&mut self, elapsed: f32) -> f32 {
+        let shared = f32::from_bits(self.shared.load(Ordering::Relaxed));
+        if self.value.target() != &shared {
+            self.value.set(shared);
+        }
+        self.value.advance(elapsed / PARAMETER_SMOOTHING_PERIOD);
+        self.value.get()
+    }
+}
+
+/// Seconds over which to smooth changes to a [`Parameter`]
+const PARAMETER_SMOOTHING_PERIOD: f32 = 0.1;
+
+/// Amplitude scaling of a fully occluded signal
+const OCCLUDED_GAIN: f32 = 0.25;
+
+/// Low-pass cutoff frequency of a fully occluded signal (Hz)
+const OCCLUDED_CUTOFF: f32 = 500.0;
+
+/// Low-pass cutoff frequency of a barely occluded signal (Hz)
+const UNOCCLUDED_CUTOFF: f32 = 20_000.0;
+
 #[derive(Copy, Clone)]
 struct Motion {
     position: mint::Point3<f32>,
@@ -528,85 +1141,178 @@ struct EarState {
 }
 
 impl EarState {
-    fn new(position_wrt_listener: mint::Point3<f32>, ear: Ear, radius: f32) -> Self {
-        let distance = norm(sub(position_wrt_listener, ear.pos()));
-        let offset = distance * (-1.0 / SPEED_OF_SOUND);
-        let distance_gain = radius / distance.max(radius);
-        // 1.0 when ear faces source directly; 0.5 when perpendicular; 0 when opposite
-        let stereo_gain = 0.5
-            + if distance < 1e-3 {
-                0.5
-            } else {
-                dot(
-                    ear.dir(),
-                    scale(position_wrt_listener.into(), 0.5 / distance),
-                )
-            };
+    fn new(placement: Placement, speaker: &Speaker) -> Self {
+        let (gain, delay) = speaker_response(speaker, placement.position, placement.radius);
         Self {
-            offset,
-            gain: stereo_gain * distance_gain,
+            offset: -delay,
+            gain: gain * placement.gain,
         }
     }
 }
 
-#[derive(Debug, Copy, Clone)]
-enum Ear {
-    Left,
-    Right,
+/// Gain and propagation delay in seconds of sound from a source of size `radius` at `position` to
+/// each of the listener's ears
+///
+/// `position` is relative to the listener, who faces -Z with +X to the right and +Y up. These are
+/// the values used by a stereo [`SpatialScene`] before applying the source's [`Cone`] and
+/// occlusion, accounting for attenuation with distance, the head's shadowing of the far ear, and
+/// the difference in arrival time between the ears. Useful for building custom spatializers on
+/// the same acoustic model.
+pub fn ear_gains(position: mint::Point3<f32>, radius: f32) -> [(f32, f32); 2] {
+    [
+        speaker_response(&EARS[0], position, radius),
+        speaker_response(&EARS[1], position, radius),
+    ]
 }
 
-impl<T> Index<Ear> for [T] {
-    type Output = T;
-    fn index(&self, x: Ear) -> &T {
-        &self[x as usize]
-    }
+/// Gain and propagation delay of sound from a source of size `radius` at `position` to `speaker`
+fn speaker_response(speaker: &Speaker, position: mint::Point3<f32>, radius: f32) -> (f32, f32) {
+    let distance = norm(sub(position, speaker.position));
+    let delay = distance * (1.0 / SPEED_OF_SOUND);
+    let distance_gain = distance_gain(radius, distance);
+    // 1.0 when ear faces source directly; 0.5 when perpendicular; 0 when opposite
+    let stereo_gain = 0.5
+        + if distance < 1e-3 {
+            0.5
+        } else {
+            dot(speaker.direction, scale(position.into(), 0.5 / distance))
+        };
+    (stereo_gain * speaker.gain * distance_gain, delay)
 }
 
-impl<T> IndexMut<Ear> for [T] {
-    fn index_mut(&mut self, x: Ear) -> &mut T {
-        &mut self[x as usize]
-    }
+/// Attenuation due to distance from a source of size `radius`
+fn distance_gain(radius: f32, distance: f32) -> f32 {
+    radius / distance.max(radius)
 }
 
-impl Ear {
-    /// Location of the ear wrt a head facing -Z
-    fn pos(self) -> mint::Point3<f32> {
+/// A point at which a [`SpatialScene`] renders a channel of output, e.g. an ear or a loudspeaker
+///
+/// Coordinates are relative to an unrotated listener, who faces -Z with +X to the right and +Y up.
+#[derive(Debug, Copy, Clone)]
+pub struct Speaker {
+    /// Location at which sound is received, which determines its propagation delay
+    ///
+    /// For loudspeakers, typically the origin, since their physical placement around the listener
+    /// already delays the sound they emit.
+    pub position: mint::Point3<f32>,
+    /// Unit vector pointing towards sources which are heard loudest
+    ///
+    /// Sources in this direction are heard at full volume, sources perpendicular to it at half
+    /// volume, and sources in the opposite direction not at all.
+    pub direction: mint::Vector3<f32>,
+    /// Amplitude scaling applied to the channel, e.g. 0 for a low-frequency effects channel that
+    /// should receive no spatialized audio
+    pub gain: f32,
+}
+
+impl Speaker {
+    /// Front left, front right, rear left, and rear right speakers at 45 and 135 degrees from the
+    /// front
+    pub fn quad() -> [Speaker; 4] {
+        let angle = TAU / 8.0;
         [
-            match self {
-                Ear::Left => -HEAD_RADIUS,
-                Ear::Right => HEAD_RADIUS,
-            },
-            0.0,
-            0.0,
+            Self::at(-angle),
+            Self::at(angle),
+            Self::at(-3.0 * angle),
+            Self::at(3.0 * angle),
         ]
-        .into()
     }
 
-    /// Unit vector along which sound is least attenuated
-    fn dir(self) -> mint::Vector3<f32> {
-        // [+-4, 0, -1] normalized
+    /// 5.1 front left, front right, center, LFE, surround left, and surround right speakers
+    ///
+    /// Front speakers lie at 30 degrees from the center, and surround speakers at 110 degrees, as
+    /// recommended by ITU-R BS.775. The LFE channel is silent. This channel order matches
+    /// [`Upmix`](crate::Upmix).
+    pub fn surround_5_1() -> [Speaker; 6] {
+        let front = TAU / 12.0;
+        let surround = TAU * 110.0 / 360.0;
         [
-            match self {
-                Ear::Left => -1.0,
-                Ear::Right => 1.0,
-            } * 4.0
-                / 17.0f32.sqrt(),
-            0.0,
-            -1.0 / 17.0f32.sqrt(),
+            Self::at(-front),
+            Self::at(front),
+            Self::at(0.0),
+            Speaker {
+                gain: 0.0,
+                ..Self::at(0.0)
+            },
+            Self::at(-surround),
+            Self::at(surround),
         ]
-        .into()
+    }
+
+    /// Loudspeaker in the horizontal plane, `azimuth` radians clockwise from the front when viewed
+    /// from above
+    fn at(azimuth: f32) -> Self {
+        Self {
+            position: [0.0; 3].into(),
+            direction: [azimuth.sin(), 0.0, -azimuth.cos()].into(),
+            gain: 1.0,
+        }
+    }
+
+    /// Amplitude of the left and right reverb channels to output from this speaker
+    fn reverb_weights(&self) -> [f32; 2] {
+        let x = self.direction.x;
+        if x < 0.0 {
+            [self.gain, 0.0]
+        } else if x > 0.0 {
+            [0.0, self.gain]
+        } else {
+            [0.5 * self.gain; 2]
+        }
     }
 }
 
+/// Listener's left and right ears, for stereo output
+const EARS: [Speaker; 2] = {
+    // [+-4, 0, -1] normalized
+    const X: f32 = 4.0 * INV_SQRT_17;
+    const Z: f32 = -INV_SQRT_17;
+    [
+        Speaker {
+            position: mint::Point3 {
+                x: -HEAD_RADIUS,
+                y: 0.0,
+                z: 0.0,
+            },
+            direction: mint::Vector3 {
+                x: -X,
+                y: 0.0,
+                z: Z,
+            },
+            gain: 1.0,
+        },
+        Speaker {
+            position: mint::Point3 {
+                x: HEAD_RADIUS,
+                y: 0.0,
+                z: 0.0,
+            },
+            direction: mint::Vector3 { x: X, y: 0.0, z: Z },
+            gain: 1.0,
+        },
+    ]
+};
+
+/// 1 / sqrt(17)
+const INV_SQRT_17: f32 = 0.242_535_63;
+
 /// Rate sound travels from signals to listeners (m/s)
 const SPEED_OF_SOUND: f32 = 343.0;
 
+/// Number of frames [`SpatialScene::new`] samples its signals at a time
+const DEFAULT_BUFFER_SIZE: usize = 256;
+
+/// Maximum number of frames a [`SpatialScene`] with a reverb bus renders at once
+const REVERB_BUFFER_SIZE: usize = 256;
+
 /// Distance from center of head to an ear (m)
 const HEAD_RADIUS: f32 = 0.1075;
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{Constant, Frames, FramesSignal, Sine};
+    use core::f32::consts::PI;
 
     struct FinishedSignal;
 
@@ -626,6 +1332,262 @@ mod tests {
         fn seek(&mut self, _: f32) {}
     }
 
+    #[test]
+    fn cone_gain() {
+        let cone = Cone {
+            inner_angle: PI / 2.0,
+            outer_angle: PI,
+            outer_gain: 0.1,
+        };
+        let gain = |angle: f32| {
+            // Source on the -Z axis, facing `angle` radians away from the listener
+            cone.gain(
+                [angle.sin(), 0.0, angle.cos()].into(),
+                [0.0, 0.0, -2.0].into(),
+            )
+        };
+        assert_eq!(gain(0.0), 1.0);
+        assert_eq!(gain(PI / 4.0 - 1e-3), 1.0);
+        assert!((gain(3.0 * PI / 8.0) - 0.55).abs() < 1e-3);
+        assert_eq!(gain(PI / 2.0 + 1e-3), 0.1);
+        assert_eq!(gain(PI), 0.1);
+        assert_eq!(
+            Cone::default().gain([0.0, 0.0, -1.0].into(), [0.0, 0.0, -2.0].into()),
+            1.0
+        );
+    }
+
+    #[test]
+    fn directional() {
+        let cone = Cone {
+            inner_angle: PI / 2.0,
+            outer_angle: PI,
+            outer_gain: 0.1,
+        };
+        let render = |direction: [f32; 3]| {
+            let (mut control, mut scene) = SpatialScene::new();
+            control.play(
+                Constant(1.0),
+                SpatialOptions {
+                    position: [0.0, 0.0, -1.0].into(),
+                    direction: direction.into(),
+                    cone,
+                    ..SpatialOptions::default()
+                },
+            );
+            let mut out = [[0.0; 2]; 4];
+            scene.sample(0.01, &mut out);
+            out[3][0]
+        };
+        let on_axis = render([0.0, 0.0, 1.0]);
+        let off_axis = render([0.0, 0.0, -1.0]);
+        assert!(on_axis > 0.0);
+        assert!((off_axis / on_axis - 0.1).abs() < 1e-3);
+    }
+
+    #[test]
+    fn occlusion() {
+        let render = |occlusion: f32| {
+            let (mut control, mut scene) = SpatialScene::new();
+            let mut handle = control.play(
+                Constant(1.0),
+                SpatialOptions {
+                    position: [0.0, 0.0, -1.0].into(),
+                    ..SpatialOptions::default()
+                },
+            );
+            handle.set_occlusion(occlusion);
+            let mut out = [[0.0; 2]; 441];
+            // Allow smoothing to settle
+            for _ in 0..20 {
+                scene.sample(1.0 / 44100.0, &mut out);
+            }
+            out[440][0]
+        };
+        let clear = render(0.0);
+        let occluded = render(1.0);
+        assert!(clear > 0.0);
+        assert!((occluded / clear - OCCLUDED_GAIN).abs() < 1e-3);
+    }
+
+    #[test]
+    fn pitch() {
+        // A signal whose value is its own playback time, to measure its speed
+        let ramp = Frames::from_iter(100, (0..400).map(|i| i as f32 / 100.0));
+        let render = |pitch: f32| {
+            let (mut control, mut scene) = SpatialScene::new();
+            control.play(
+                FramesSignal::new(ramp.clone(), 0.0).1,
+                SpatialOptions {
+                    position: [0.0, 0.0, -10.0].into(),
+                    // Receding, for a Doppler shift
+                    velocity: [0.0, 0.0, -50.0].into(),
+                    pitch,
+                    ..SpatialOptions::default()
+                },
+            );
+            // Skip past the propagation delay
+            let mut out = [[0.0; 2]; 10];
+            scene.sample(0.01, &mut out);
+            scene.sample(0.01, &mut out);
+            out[9][0]
+        };
+        let base = render(1.0);
+        assert!(base > 0.0);
+        assert!((render(1.5) / base - 1.5).abs() < 1e-3);
+    }
+
+    #[test]
+    fn pause() {
+        let ramp = Frames::from_iter(100, (0..400).map(|i| i as f32 / 100.0));
+        let options = SpatialOptions {
+            position: [0.0, 0.0, -1.0].into(),
+            velocity: [0.0, 0.0, -10.0].into(),
+            ..SpatialOptions::default()
+        };
+        let (mut control, mut scene) = SpatialScene::new();
+        let mut handle = control.play(FramesSignal::from(ramp.clone()), options);
+        let (mut reference_control, mut reference) = SpatialScene::new();
+        reference_control.play(FramesSignal::from(ramp), options);
+
+        let mut out = [[0.0; 2]; 10];
+        let mut expected = [[0.0; 2]; 10];
+        scene.sample(0.01, &mut out);
+        reference.sample(0.01, &mut expected);
+        handle.pause();
+        for _ in 0..3 {
+            scene.sample(0.01, &mut out);
+            assert_eq!(out, [[0.0; 2]; 10]);
+        }
+        handle.resume();
+        scene.sample(0.01, &mut out);
+        reference.sample(0.01, &mut expected);
+        assert_eq!(out, expected);
+    }
+
+    #[test]
+    fn dynamic_radius() {
+        let (mut control, mut scene) = SpatialScene::new();
+        let mut handle = control.play(
+            Constant(1.0),
+            SpatialOptions {
+                position: [0.0, 0.0, -2.0].into(),
+                radius: 0.5,
+                ..SpatialOptions::default()
+            },
+        );
+        let mut out = [[0.0; 2]; 4];
+        scene.sample(0.01, &mut out);
+        let small = out[3][0];
+        assert!(small > 0.0);
+
+        handle.set_radius(2.0);
+        scene.sample(0.01, &mut out);
+        assert!(out[3][0] > small, "gain rises");
+        assert!(out[3][0] < 4.0 * small, "gain is smoothed");
+        for _ in 0..20 {
+            scene.sample(0.01, &mut out);
+        }
+        assert!((out[3][0] / small - 4.0).abs() < 1e-2);
+    }
+
+    #[test]
+    fn observed() {
+        let (mut control, mut scene) = SpatialScene::new();
+        let mut handle = control.play(
+            Constant(1.0),
+            SpatialOptions {
+                position: [0.0, 0.0, -2.0].into(),
+                radius: 0.5,
+                ..SpatialOptions::default()
+            },
+        );
+        assert_eq!(handle.current_distance(), 2.0);
+        assert_eq!(handle.current_gain(), 0.25);
+
+        handle.set_motion([0.0, 0.0, -4.0].into(), [0.0; 3].into(), true);
+        assert_eq!(handle.current_distance(), 2.0, "not yet updated");
+        scene.sample(0.01, &mut [[0.0; 2]; 4]);
+        assert_eq!(handle.current_distance(), 4.0);
+        assert_eq!(control.frames_processed(), 4);
+        assert_eq!(handle.current_gain(), 0.125);
+    }
+
+    #[test]
+    fn fixed_blocks() {
+        let render = |chunk: usize| {
+            let (mut control, scene) = SpatialScene::new();
+            let mut scene = scene.with_fixed_blocks(32);
+            control.play(
+                Sine::new(0.0, 440.0),
+                SpatialOptions {
+                    position: [-5.0, 0.0, -1.0].into(),
+                    velocity: [10.0, 0.0, 0.0].into(),
+                    ..SpatialOptions::default()
+                },
+            );
+            let mut out = [[0.0; 2]; 700];
+            for chunk in out.chunks_mut(chunk) {
+                scene.sample(1.0 / 44100.0, chunk);
+            }
+            out
+        };
+        let a = render(7);
+        let b = render(100);
+        assert!(a.iter().any(|x| x[0] != 0.0));
+        assert_eq!(a[..], b[..]);
+    }
+
+    #[test]
+    fn reverb_send() {
+        let render = |send: f32| {
+            let (mut control, scene) = SpatialScene::new();
+            let (_, mut scene) = scene.with_reverb(44100, ReverbOptions::default());
+            control.play(
+                Constant(1.0),
+                SpatialOptions {
+                    position: [0.0, 0.0, -1.0].into(),
+                    reverb_send: send,
+                    ..SpatialOptions::default()
+                },
+            );
+            let mut out = [[0.0; 2]; 2048];
+            scene.sample(1.0 / 44100.0, &mut out);
+            out
+        };
+        let dry = render(0.0);
+        let wet = render(1.0);
+        assert_eq!(dry[..1000], wet[..1000], "reverb has not yet echoed");
+        assert!(dry[1000..] != wet[1000..]);
+    }
+
+    #[test]
+    fn surround() {
+        let (mut control, mut scene) = SpatialScene::new_surround(Speaker::surround_5_1());
+        control.play(
+            Constant(1.0),
+            SpatialOptions {
+                // Front left
+                position: [-1.0, 0.0, -1.0].into(),
+                ..SpatialOptions::default()
+            },
+        );
+        let mut out = [[0.0; 6]; 4];
+        scene.sample(0.01, &mut out);
+        let [fl, fr, c, lfe, sl, sr] = out[3];
+        assert!(fl > c && c > fr, "{:?}", out[3]);
+        assert!(fl > sl && sl > sr, "{:?}", out[3]);
+        assert_eq!(lfe, 0.0);
+    }
+
+    #[test]
+    fn ear_gains() {
+        let [left, right] = super::ear_gains([1.0, 0.0, 0.0].into(), 0.1);
+        assert!(left.0 < right.0, "far ear is quieter");
+        assert!(left.1 > right.1, "far ear hears later");
+        assert!((right.1 - (1.0 - HEAD_RADIUS) / SPEED_OF_SOUND).abs() < 1e-6);
+    }
+
     /// Verify that a signal is dropped only after accounting for propagation delay
     #[test]
     fn signal_finished() {
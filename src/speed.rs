@@ -1,13 +1,14 @@
-use alloc::sync::Arc;
+use alloc::{sync::Arc, vec, vec::Vec};
 use core::sync::atomic::{AtomicU32, Ordering};
 
-use crate::{Frame, Signal};
+use crate::{biquad, math::Float, Frame, Reset, Sample, Seek, Signal};
 
 /// Scales rate of playback by a dynamically-adjustable factor
 ///
 /// Higher/lower speeds will naturally result in higher/lower pitched sound respectively.
 pub struct Speed<T: ?Sized> {
     speed: Arc<AtomicU32>,
+    antialias: Option<Antialias>,
     inner: T,
 }
 
@@ -16,11 +17,24 @@ impl<T> Speed<T> {
     pub fn new(signal: T) -> (SpeedControl, Self) {
         let signal = Self {
             speed: Arc::new(AtomicU32::new(1.0f32.to_bits())),
+            antialias: None,
             inner: signal,
         };
         let control = SpeedControl(signal.speed.clone());
         (control, signal)
     }
+
+    /// Apply dynamic speed to `signal`, filtering out frequencies that would alias when sped up
+    ///
+    /// While the speed exceeds 1, `signal` is oversampled and low-pass filtered at the output's
+    /// Nyquist frequency, preventing high-pitched content from folding back down into audible
+    /// artifacts. Considerably more expensive than [`new`](Self::new) at high speeds. Speeds
+    /// above 8 are only partially filtered.
+    pub fn new_filtered(signal: T) -> (SpeedControl, Self) {
+        let (control, mut signal) = Self::new(signal);
+        signal.antialias = Some(Antialias::new());
+        (control, signal)
+    }
 }
 
 impl<T: Signal> Signal for Speed<T>
@@ -31,12 +45,54 @@ where
 
     fn sample(&mut self, interval: f32, out: &mut [T::Frame]) {
         let speed = f32::from_bits(self.speed.load(Ordering::Relaxed));
-        self.inner.sample(interval * speed, out);
+        match self.antialias {
+            Some(ref mut antialias) if speed > 1.0 => {
+                antialias.sample(&mut self.inner, interval * speed, speed, out)
+            }
+            Some(ref mut antialias) => {
+                antialias.reset();
+                self.inner.sample(interval * speed, out);
+            }
+            None => self.inner.sample(interval * speed, out),
+        }
     }
 
     fn is_finished(&self) -> bool {
         self.inner.is_finished()
     }
+
+    /// Assumes the current speed remains constant
+    fn duration_remaining(&self) -> Option<f32> {
+        let speed = f32::from_bits(self.speed.load(Ordering::Relaxed));
+        if speed <= 0.0 {
+            return None;
+        }
+        Some(self.inner.duration_remaining()? / speed)
+    }
+}
+
+/// Seeking is exact only while the speed remains constant. The current speed factor is applied to
+/// the whole seek, so changes in speed since the last `sample` call are not accounted for.
+impl<T: Seek> Seek for Speed<T>
+where
+    T::Frame: Frame,
+{
+    fn seek(&mut self, seconds: f32) {
+        let speed = f32::from_bits(self.speed.load(Ordering::Relaxed));
+        self.inner.seek(seconds * speed);
+    }
+}
+
+impl<T: Reset> Reset for Speed<T>
+where
+    T::Frame: Frame,
+{
+    fn reset(&mut self) {
+        if let Some(ref mut antialias) = self.antialias {
+            antialias.reset();
+        }
+        self.inner.reset();
+    }
 }
 
 /// Thread-safe control for a [`Speed`] filter
@@ -53,3 +109,120 @@ impl SpeedControl {
         self.0.store(factor.to_bits(), Ordering::Relaxed);
     }
 }
+
+/// Oversampling low-pass filter state for [`Speed::new_filtered`]
+struct Antialias {
+    /// Oversampling factor the filter was designed for
+    factor: usize,
+    coefficients: [biquad::Coefficients; 2],
+    /// Per-channel history, independent of the frame type so [`Speed`] needn't constrain `T`
+    state: Vec<[biquad::State<Sample>; 2]>,
+}
+
+impl Antialias {
+    fn new() -> Self {
+        Self {
+            factor: 0,
+            coefficients: [biquad::Coefficients::low_pass(0.5, 1.0); 2],
+            state: Vec::new(),
+        }
+    }
+
+    fn reset(&mut self) {
+        for x in &mut self.state {
+            *x = [biquad::State::ZERO; 2];
+        }
+    }
+
+    /// Sample `inner` at `factor` times the rate implied by `interval`, filter, and decimate
+    fn sample<S>(&mut self, inner: &mut S, interval: f32, speed: f32, out: &mut [S::Frame])
+    where
+        S: Signal + ?Sized,
+        S::Frame: Frame,
+    {
+        let factor = (speed.ceil() as usize).min(MAX_OVERSAMPLE);
+        if factor != self.factor {
+            // Fourth-order Butterworth, just short of the output's Nyquist frequency
+            let cutoff = 0.45 / factor as f32;
+            self.factor = factor;
+            self.coefficients = [
+                biquad::Coefficients::low_pass(cutoff, 0.5412),
+                biquad::Coefficients::low_pass(cutoff, 1.3066),
+            ];
+        }
+        let channels = S::Frame::ZERO.channels().len();
+        if self.state.len() != channels {
+            self.state = vec![[biquad::State::ZERO; 2]; channels];
+        }
+
+        let sub_interval = interval / factor as f32;
+        let mut buffer = [(); 1024].map(|()| S::Frame::ZERO);
+        for chunk in out.chunks_mut(buffer.len() / factor) {
+            let staging = &mut buffer[..chunk.len() * factor];
+            inner.sample(sub_interval, staging);
+            for (o, group) in chunk.iter_mut().zip(staging.chunks(factor)) {
+                for x in group {
+                    for ((o, &x), state) in o
+                        .channels_mut()
+                        .iter_mut()
+                        .zip(x.channels())
+                        .zip(&mut self.state)
+                    {
+                        let x = state[0].process(&self.coefficients[0], &x);
+                        *o = state[1].process(&self.coefficients[1], &x);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Maximum oversampling factor applied by [`Antialias`]
+const MAX_OVERSAMPLE: usize = 8;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Constant, Frames, FramesSignal, Sine};
+
+    #[test]
+    fn seek_scaled() {
+        let (mut c, mut s) = Speed::new(FramesSignal::from(Frames::from_slice(
+            1,
+            &[1.0, 2.0, 3.0, 4.0, 5.0],
+        )));
+        c.set_speed(2.0);
+        assert_eq!(s.duration_remaining(), Some(2.0));
+        s.seek(1.0);
+        let mut buf = [0.0; 2];
+        s.sample(0.5, &mut buf);
+        assert_eq!(buf, [3.0, 4.0]);
+    }
+
+    #[test]
+    fn filtered_passes_dc() {
+        let (mut c, mut s) = Speed::new_filtered(Constant(1.0));
+        c.set_speed(3.0);
+        let mut buf = [0.0; 256];
+        s.sample(1.0 / 44100.0, &mut buf);
+        assert!((buf[255] - 1.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn filtered_attenuates_aliasing() {
+        const RATE: f32 = 1000.0;
+        // Sped up, 400Hz becomes 1200Hz, which is far above the output's Nyquist frequency
+        let peak = |s: &mut Speed<Sine>| {
+            let mut buf = [0.0; 1024];
+            s.sample(1.0 / RATE, &mut buf);
+            s.sample(1.0 / RATE, &mut buf);
+            buf.iter().fold(0.0f32, |acc, x| acc.max(x.abs()))
+        };
+        let (mut c, mut plain) = Speed::new(Sine::new(0.0, 400.0));
+        c.set_speed(3.0);
+        let (mut c, mut filtered) = Speed::new_filtered(Sine::new(0.0, 400.0));
+        c.set_speed(3.0);
+        assert!(peak(&mut plain) > 0.5);
+        assert!(peak(&mut filtered) < 0.1);
+    }
+}
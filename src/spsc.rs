@@ -1,3 +1,22 @@
+//! A wait-free single-producer single-consumer queue
+//!
+//! Useful for feeding data from another thread into a custom [`Signal`](crate::Signal), as
+//! [`Stream`](crate::Stream) does. Neither end ever blocks or allocates, so the [`Receiver`] can be
+//! used from the audio thread.
+//!
+//! Received items are not removed until explicitly [`release`](Receiver::release)d or
+//! [`pop`](Receiver::pop)ped, allowing the receiver to look ahead at any item that's arrived:
+//!
+//! ```
+//! let (mut send, mut recv) = oddio::spsc::channel::<f32>(4);
+//! assert_eq!(send.send_from_slice(&[1.0, 2.0, 3.0]), 3);
+//! recv.update();
+//! assert_eq!(recv.len(), 3);
+//! assert_eq!(recv[2], 3.0);
+//! recv.release(2);
+//! assert_eq!(recv.pop(), Some(3.0));
+//! ```
+
 use crate::alloc::{alloc, boxed::Box, sync::Arc};
 use core::{
     cell::UnsafeCell,
@@ -8,6 +27,10 @@ use core::{
     sync::atomic::{AtomicUsize, Ordering},
 };
 
+/// Construct a channel able to hold `capacity` items at once
+///
+/// One more slot than `capacity` is allocated, because the ring buffer always leaves a slot empty
+/// to distinguish a full queue from an empty one.
 pub fn channel<T>(capacity: usize) -> (Sender<T>, Receiver<T>) {
     let shared = Shared::new(capacity + 1);
     (
@@ -18,6 +41,7 @@ pub fn channel<T>(capacity: usize) -> (Sender<T>, Receiver<T>) {
     )
 }
 
+/// Writing half of a [`channel`]
 pub struct Sender<T> {
     shared: Arc<Shared<T>>,
 }
@@ -67,6 +91,7 @@ impl<T> Sender<T> {
         }
     }
 
+    /// Maximum number of items the channel can hold at once
     pub fn capacity(&self) -> usize {
         self.shared.data.len() - 1
     }
@@ -86,6 +111,8 @@ impl<T> Sender<T> {
     }
 
     /// Append a single item, leaving at least `reserve_slots` for future use
+    ///
+    /// Returns the item if there isn't enough room.
     pub fn send(&mut self, data: T, reserve_slots: usize) -> Result<(), T> {
         let write = self.shared.header.write.load(Ordering::Relaxed);
         let read = self.shared.header.read.load(Ordering::Relaxed);
@@ -103,7 +130,7 @@ impl<T> Sender<T> {
         Ok(())
     }
 
-    /// Whether the receiver has been dropped
+    /// Whether the receiver has been dropped, such that nothing sent will ever be read
     // Could be `&self` since we don't allow new references to be created, but :effort:
     #[allow(clippy::wrong_self_convention)]
     pub fn is_closed(&mut self) -> bool {
@@ -111,6 +138,10 @@ impl<T> Sender<T> {
     }
 }
 
+/// Reading half of a [`channel`]
+///
+/// Items become visible after a call to [`update`](Self::update), and may then be accessed by
+/// index, starting from the oldest.
 pub struct Receiver<T> {
     shared: Arc<Shared<T>>,
     len: usize,
@@ -122,6 +153,11 @@ impl<T> Receiver<T> {
         self.len
     }
 
+    /// Whether no elements are available to read
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
     /// Extend with newly sent items
     pub fn update(&mut self) {
         let old_len = self.len;
@@ -139,15 +175,20 @@ impl<T> Receiver<T> {
         self.len -= n;
     }
 
+    /// Remove and return all available elements, oldest first
     pub fn drain(&mut self) -> Drain<'_, T> {
         Drain { recv: self }
     }
 
     /// Whether the sender has been dropped
+    ///
+    /// Items sent before the sender was dropped may still be available; call
+    /// [`update`](Self::update) to be sure of observing them.
     pub fn is_closed(&self) -> bool {
         Arc::strong_count(&self.shared) == 1
     }
 
+    /// Remove and return the oldest available element, if any
     pub fn pop(&mut self) -> Option<T> {
         if self.len == 0 {
             return None;
@@ -246,6 +287,7 @@ struct Header {
     write: AtomicUsize,
 }
 
+/// Iterator returned by [`Receiver::drain`]
 pub struct Drain<'a, T> {
     recv: &'a mut Receiver<T>,
 }
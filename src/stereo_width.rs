@@ -0,0 +1,113 @@
+use alloc::sync::Arc;
+use core::sync::atomic::{AtomicU32, Ordering};
+
+use crate::{Reset, Sample, Seek, Signal, Smoothed};
+
+/// Narrows or widens the stereo image of a signal
+///
+/// The signal is encoded into mid (sum) and side (difference) components, the side component is
+/// scaled by the width, and the result is decoded back to left and right.
+pub struct StereoWidth<T: ?Sized> {
+    shared: Arc<AtomicU32>,
+    width: Smoothed<f32>,
+    inner: T,
+}
+
+impl<T> StereoWidth<T> {
+    /// Apply dynamic stereo width control to `signal`, initially leaving it unchanged
+    pub fn new(signal: T) -> (StereoWidthControl, Self) {
+        let signal = Self {
+            shared: Arc::new(AtomicU32::new(1.0f32.to_bits())),
+            width: Smoothed::new(1.0),
+            inner: signal,
+        };
+        let handle = StereoWidthControl(signal.shared.clone());
+        (handle, signal)
+    }
+}
+
+impl<T: Signal<Frame = [Sample; 2]>> Signal for StereoWidth<T> {
+    type Frame = [Sample; 2];
+
+    #[allow(clippy::float_cmp)]
+    fn sample(&mut self, interval: f32, out: &mut [[Sample; 2]]) {
+        self.inner.sample(interval, out);
+        let shared = f32::from_bits(self.shared.load(Ordering::Relaxed));
+        if self.width.target() != &shared {
+            self.width.set(shared);
+        }
+        if self.width.progress() == 1.0 && self.width.get() == 1.0 {
+            return;
+        }
+        for [l, r] in out {
+            let width = self.width.get();
+            let mid = (*l + *r) * 0.5;
+            let side = (*l - *r) * 0.5 * width;
+            *l = mid + side;
+            *r = mid - side;
+            self.width.advance(interval / SMOOTHING_PERIOD);
+        }
+    }
+
+    fn is_finished(&self) -> bool {
+        self.inner.is_finished()
+    }
+
+    fn duration_remaining(&self) -> Option<f32> {
+        self.inner.duration_remaining()
+    }
+}
+
+impl<T: Seek<Frame = [Sample; 2]>> Seek for StereoWidth<T> {
+    fn seek(&mut self, seconds: f32) {
+        self.inner.seek(seconds)
+    }
+}
+
+impl<T: Reset<Frame = [Sample; 2]>> Reset for StereoWidth<T> {
+    fn reset(&mut self) {
+        self.width = Smoothed::new(f32::from_bits(self.shared.load(Ordering::Relaxed)));
+        self.inner.reset();
+    }
+}
+
+/// Thread-safe control for a [`StereoWidth`] filter
+pub struct StereoWidthControl(Arc<AtomicU32>);
+
+impl StereoWidthControl {
+    /// Get the current stereo width
+    pub fn width(&self) -> f32 {
+        f32::from_bits(self.0.load(Ordering::Relaxed))
+    }
+
+    /// Set the stereo width, from 0 to 2
+    ///
+    /// 0 collapses the signal to mono, 1 leaves it unchanged, and 2 doubles the difference between
+    /// channels.
+    pub fn set_width(&mut self, width: f32) {
+        self.0
+            .store(width.clamp(0.0, 2.0).to_bits(), Ordering::Relaxed);
+    }
+}
+
+/// Number of seconds over which to smooth a change in width
+const SMOOTHING_PERIOD: f32 = 0.01;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Constant;
+
+    #[test]
+    fn mono() {
+        let (mut c, mut s) = StereoWidth::new(Constant([1.0, 0.0]));
+        let mut buf = [[0.0; 2]; 4];
+        s.sample(0.01, &mut buf);
+        assert_eq!(buf, [[1.0, 0.0]; 4]);
+
+        c.set_width(0.0);
+        s.sample(0.01, &mut buf);
+        s.sample(0.01, &mut buf);
+        assert_eq!(buf, [[0.5, 0.5]; 4]);
+    }
+}
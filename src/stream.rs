@@ -1,15 +1,33 @@
 //! Streaming audio support
 
+use alloc::{boxed::Box, collections::VecDeque, sync::Arc};
+use core::sync::atomic::{AtomicUsize, Ordering};
+
 use crate::{frame, math::Float, spsc, Frame, Signal};
 
 /// Dynamic audio from an external source
+///
+/// Internally a queue of blocks of frames. Blocks are handed back to the [`StreamControl`] once
+/// played, so that the audio thread never frees memory.
 pub struct Stream<T> {
     rate: u32,
-    inner: spsc::Receiver<T>,
-    /// Offset of t=0 from the start of the buffer, in frames
+    blocks: spsc::Receiver<Box<[T]>>,
+    /// Fully consumed blocks, returned to the control to be freed or reused
+    free: spsc::Sender<Box<[T]>>,
+    /// Total number of frames consumed, for the control's accounting
+    consumed: Arc<AtomicUsize>,
+    /// Number of leading blocks whose frames are counted in `len`
+    counted: usize,
+    /// Number of unconsumed frames in `blocks`
+    len: usize,
+    /// Number of frames already consumed from the first block
+    head: usize,
+    /// Offset of t=0 from the first unconsumed frame, in frames
     t: f32,
-    /// Whether `inner` will receive no further updates
+    /// Whether `blocks` will receive no further updates
     stopping: bool,
+    /// Number of frames that must be buffered before playback begins, or 0 once it has
+    prefill: usize,
 }
 
 impl<T> Stream<T> {
@@ -22,94 +40,279 @@ impl<T> Stream<T> {
     /// - `rate` is the stream's sample rate
     /// - `size` dictates the maximum number of buffered frames
     pub fn new(rate: u32, size: usize) -> (StreamControl<T>, Self) {
-        let (send, recv) = spsc::channel(size);
+        Self::with_prefill(rate, size, 0)
+    }
+
+    /// Like [`new`](Self::new), but output silence until at least `prefill_frames` are buffered
+    ///
+    /// Buffering some audio before playback begins absorbs jitter in its arrival, e.g. from the
+    /// network, at the cost of `prefill_frames / rate` seconds of latency. Playback also begins if
+    /// the [`StreamControl`] is dropped first. `prefill_frames` is clamped to `size`.
+    pub fn with_prefill(rate: u32, size: usize, prefill_frames: usize) -> (StreamControl<T>, Self) {
+        // Every block holds at least one frame, so `size` blocks always suffice
+        let (blocks_send, blocks_recv) = spsc::channel(size);
+        let (free_send, free_recv) = spsc::channel(size);
+        let consumed = Arc::new(AtomicUsize::new(0));
         let signal = Self {
             rate,
-            inner: recv,
+            blocks: blocks_recv,
+            free: free_send,
+            consumed: consumed.clone(),
+            counted: 0,
+            len: 0,
+            head: 0,
             t: 0.0,
             stopping: false,
+            prefill: prefill_frames.min(size),
+        };
+        let control = StreamControl {
+            blocks: blocks_send,
+            free: free_recv,
+            recycled: VecDeque::new(),
+            consumed,
+            sent: 0,
+            size,
+            rate,
         };
-        let control = StreamControl(send);
         (control, signal)
     }
 
+    /// Account for newly received blocks
+    fn update(&mut self) {
+        self.blocks.update();
+        for i in self.counted..self.blocks.len() {
+            self.len += self.blocks[i].len();
+        }
+        self.counted = self.blocks.len();
+    }
+
+    /// Get the unconsumed frame at `sample` and its successor, starting the search at `cursor`
+    ///
+    /// `sample` must not decrease between calls sharing a cursor.
     #[inline]
-    fn get(&self, sample: isize) -> T
+    fn get_pair(&self, cursor: &mut Cursor, sample: isize) -> (T, T)
     where
         T: Frame + Copy,
     {
-        if sample < 0 {
-            return T::ZERO;
+        if sample < -1 {
+            return (T::ZERO, T::ZERO);
         }
-        let sample = sample as usize;
-        if sample >= self.inner.len() {
-            return T::ZERO;
+        if sample == -1 {
+            return (T::ZERO, self.get(cursor, 0));
         }
-        self.inner[sample]
+        let a = self.get(cursor, sample);
+        let b = self.get(&mut cursor.clone(), sample + 1);
+        (a, b)
     }
 
-    fn sample_single(&self, s: f32) -> T
+    #[inline]
+    fn get(&self, cursor: &mut Cursor, sample: isize) -> T
+    where
+        T: Frame + Copy,
+    {
+        while cursor.block < self.counted {
+            let block = &self.blocks[cursor.block];
+            let end = cursor.start + block.len() as isize;
+            if sample < end {
+                return block[(sample - cursor.start) as usize];
+            }
+            cursor.block += 1;
+            cursor.start = end;
+        }
+        T::ZERO
+    }
+
+    fn sample_single(&self, cursor: &mut Cursor, s: f32) -> T
     where
         T: Frame + Copy,
     {
         let x0 = s.trunc() as isize;
         let fract = s.fract();
-        let x1 = x0 + 1;
-        let a = self.get(x0);
-        let b = self.get(x1);
+        let (a, b) = self.get_pair(cursor, x0);
         frame::lerp(&a, &b, fract)
     }
 
     fn advance(&mut self, dt: f32) {
         let next = self.t + dt * self.rate as f32;
-        let t = next.min(self.inner.len() as f32);
-        self.inner.release(t as usize);
+        let t = next.min(self.len as f32);
+        let n = t as usize;
+        self.len -= n;
         self.t = t.fract();
+
+        // Hand fully consumed blocks back to the control
+        let mut head = self.head + n;
+        while self.counted > 0 && head >= self.blocks[0].len() {
+            let block = self.blocks.pop().unwrap();
+            head -= block.len();
+            self.counted -= 1;
+            self.free
+                .send(block, 0)
+                .unwrap_or_else(|_| unreachable!("free queue has capacity for every block"));
+        }
+        self.head = head;
+
+        // Publish only once the blocks have been released, so the control never counts frames as
+        // free while the blocks holding them still occupy the queues
+        self.consumed.fetch_add(n, Ordering::Release);
     }
 }
 
+/// Position of a block within a [`Stream`]'s queue
+#[derive(Clone)]
+struct Cursor {
+    /// Index of the block
+    block: usize,
+    /// Index of the block's first frame relative to the first unconsumed frame
+    start: isize,
+}
+
 impl<T: Frame + Copy> Signal for Stream<T> {
     type Frame = T;
 
     fn sample(&mut self, interval: f32, out: &mut [T]) {
-        self.inner.update();
-        if self.inner.is_closed() {
+        self.update();
+        if self.blocks.is_closed() {
             self.stopping = true;
         }
+        if self.prefill != 0 {
+            if self.len < self.prefill && !self.stopping {
+                out.fill(T::ZERO);
+                return;
+            }
+            self.prefill = 0;
+        }
         let s0 = self.t;
         let ds = interval * self.rate as f32;
 
+        let mut cursor = Cursor {
+            block: 0,
+            start: -(self.head as isize),
+        };
         for (i, o) in out.iter_mut().enumerate() {
-            *o = self.sample_single(s0 + ds * i as f32);
+            *o = self.sample_single(&mut cursor, s0 + ds * i as f32);
         }
         self.advance(interval * out.len() as f32);
     }
 
     #[allow(clippy::float_cmp)]
     fn is_finished(&self) -> bool {
-        self.stopping && self.t == self.inner.len() as f32
+        self.stopping && self.t == self.len as f32
     }
 }
 
 /// Thread-safe control for a [`Stream`]
-pub struct StreamControl<T>(spsc::Sender<T>);
+pub struct StreamControl<T> {
+    blocks: spsc::Sender<Box<[T]>>,
+    free: spsc::Receiver<Box<[T]>>,
+    /// Blocks consumed by the stream, available for reuse
+    recycled: VecDeque<Box<[T]>>,
+    /// Total number of frames consumed by the stream
+    consumed: Arc<AtomicUsize>,
+    /// Total number of frames sent to the stream
+    sent: usize,
+    size: usize,
+    rate: u32,
+}
 
 impl<T> StreamControl<T> {
     /// Lower bound to the number of samples that the next `write` call will successfully consume
     pub fn free(&mut self) -> usize {
-        self.0.free()
+        // Blocks holding frames counted as consumed here have already been released, so `gc`
+        // drains them, leaving room in the free queue for everything we then send
+        let buffered = self.buffered();
+        self.gc();
+        self.size - buffered
+    }
+
+    /// Upper bound to the duration of audio buffered and not yet played, in seconds
+    pub fn latency_seconds(&self) -> f32 {
+        self.buffered() as f32 / self.rate as f32
     }
 
     /// Add more samples. Returns the number of samples consumed. Remaining samples should be passed
     /// in again in a future call.
+    ///
+    /// The samples are copied into a block, reusing one the stream has finished playing if one of
+    /// the right length is available. Writing chunks of a consistent size therefore stops
+    /// allocating once playback is underway, but other calls allocate a new block. For full control
+    /// over allocation, use [`write_block`](Self::write_block) and [`recycle`](Self::recycle).
     pub fn write(&mut self, samples: &[T]) -> usize
     where
         T: Copy,
     {
-        self.0.send_from_slice(samples)
+        let n = self.free().min(samples.len());
+        if n == 0 {
+            return 0;
+        }
+        let samples = &samples[..n];
+        let block = match self.recycled.iter().rposition(|x| x.len() == n) {
+            Some(i) => {
+                let mut block = self.recycled.remove(i).unwrap();
+                block.copy_from_slice(samples);
+                block
+            }
+            None => samples.into(),
+        };
+        self.send(block);
+        n
+    }
+
+    /// Add a block of samples without copying it
+    ///
+    /// If there isn't room for the entire block, returns it to be passed in again in a future
+    /// call. Once the stream has played a block, it can be retrieved for reuse with
+    /// [`recycle`](Self::recycle).
+    pub fn write_block(&mut self, block: Box<[T]>) -> Result<(), Box<[T]>> {
+        if block.len() > self.free() {
+            return Err(block);
+        }
+        if !block.is_empty() {
+            self.send(block);
+        }
+        Ok(())
+    }
+
+    /// Whether the [`Stream`] has been dropped, so that nothing more will be played
+    pub fn is_closed(&mut self) -> bool {
+        self.blocks.is_closed()
+    }
+
+    /// Retrieve a block that the stream has finished playing, if any
+    ///
+    /// Allows allocations to be reused for future calls to [`write_block`](Self::write_block). Only
+    /// the few most recently played blocks are retained.
+    pub fn recycle(&mut self) -> Option<Box<[T]>> {
+        self.gc();
+        self.recycled.pop_back()
+    }
+
+    fn send(&mut self, block: Box<[T]>) {
+        self.sent = self.sent.wrapping_add(block.len());
+        self.blocks
+            .send(block, 0)
+            .unwrap_or_else(|_| unreachable!("blocks never outnumber free frames"));
+    }
+
+    fn buffered(&self) -> usize {
+        self.sent
+            .wrapping_sub(self.consumed.load(Ordering::Acquire))
+    }
+
+    /// Collect blocks the stream has finished with
+    fn gc(&mut self) {
+        self.free.update();
+        while let Some(block) = self.free.pop() {
+            if self.recycled.len() == RECYCLE_LIMIT {
+                self.recycled.pop_front();
+            }
+            self.recycled.push_back(block);
+        }
     }
 }
 
+/// Maximum number of played blocks retained by a [`StreamControl`] for reuse
+const RECYCLE_LIMIT: usize = 4;
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -133,6 +336,73 @@ mod tests {
         assert_out(&mut s, &[0.0, 0.0]);
     }
 
+    #[test]
+    fn prefill() {
+        let (mut c, mut s) = Stream::<f32>::with_prefill(1, 4, 3);
+        assert_eq!(c.write(&[1.0, 2.0]), 2);
+        assert_eq!(c.latency_seconds(), 2.0);
+        assert_out(&mut s, &[0.0, 0.0]);
+        assert_eq!(c.write(&[3.0]), 1);
+        assert_out(&mut s, &[1.0, 2.0]);
+        assert_eq!(c.latency_seconds(), 1.0);
+    }
+
+    #[test]
+    fn blocks() {
+        let (mut c, mut s) = Stream::<f32>::new(1, 4);
+        c.write_block(vec![1.0, 2.0, 3.0].into()).unwrap();
+        let rejected = c.write_block(vec![4.0, 5.0].into()).unwrap_err();
+        assert_eq!(c.recycle(), None);
+        assert_out(&mut s, &[1.0, 2.0]);
+        c.write_block(rejected).unwrap();
+        assert_out(&mut s, &[3.0, 4.0, 5.0, 0.0]);
+        assert_eq!(c.recycle().as_deref(), Some(&[4.0, 5.0][..]));
+        assert_eq!(c.recycle().as_deref(), Some(&[1.0, 2.0, 3.0][..]));
+    }
+
+    #[test]
+    fn write_reuses_blocks() {
+        let (mut c, mut s) = Stream::<f32>::new(1, 4);
+        assert_eq!(c.write(&[1.0, 2.0]), 2);
+        assert_out(&mut s, &[1.0, 2.0, 0.0]);
+        assert_eq!(c.write(&[3.0, 4.0]), 2);
+        // The played block was refilled rather than retained for recycling
+        assert_eq!(c.recycle(), None);
+        assert_out(&mut s, &[3.0, 4.0]);
+    }
+
+    #[test]
+    fn partially_consumed() {
+        let (mut c, mut s) = Stream::<f32>::new(1, 4);
+        assert_eq!(c.write(&[1.0, 2.0, 3.0, 4.0]), 4);
+        assert_out(&mut s, &[1.0, 2.0, 3.0]);
+        // The first block is still queued, but only one of its frames is
+        for x in [5.0, 6.0, 7.0] {
+            assert_eq!(c.write(&[x]), 1);
+        }
+        assert_eq!(c.write(&[8.0]), 0);
+        assert_out(&mut s, &[4.0, 5.0]);
+        for x in [8.0, 9.0] {
+            assert_eq!(c.write(&[x]), 1);
+        }
+        assert_eq!(c.free(), 0);
+        assert_out(&mut s, &[6.0, 7.0, 8.0, 9.0, 0.0]);
+        assert_eq!(c.write(&[10.0, 11.0, 12.0, 13.0]), 4);
+        assert_out(&mut s, &[10.0, 11.0, 12.0, 13.0]);
+    }
+
+    #[test]
+    fn interpolate_across_blocks() {
+        let (mut c, mut s) = Stream::<f32>::new(1, 4);
+        c.write(&[0.0, 1.0]);
+        c.write(&[2.0, 3.0]);
+        let mut out = [0.0; 4];
+        s.sample(0.5, &mut out);
+        assert_eq!(out, [0.0, 0.5, 1.0, 1.5]);
+        s.sample(0.5, &mut out);
+        assert_eq!(out, [2.0, 2.5, 3.0, 1.5]);
+    }
+
     #[test]
     fn cleanup() {
         let (mut c, mut s) = Stream::<f32>::new(1, 4);
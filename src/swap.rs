@@ -1,3 +1,23 @@
+//! A wait-free channel that only retains the most recent value sent
+//!
+//! Useful for building custom controllable signals: a control handle owns the [`Sender`] and
+//! publishes parameter updates, while the signal owns the [`Receiver`] and picks up the latest
+//! value at the start of each `sample` call.
+//!
+//! Three slots are allocated up front. The sender writes into one, the receiver reads from
+//! another, and the third holds the most recently sent value. Sending and receiving each swap a
+//! slot index with that third slot using a single atomic operation, so neither side ever waits
+//! for the other or allocates, making both safe to use on a real-time audio thread. Values sent
+//! in between two `refresh` calls are overwritten, so only the latest is observed.
+//!
+//! ```
+//! let (mut send, mut recv) = oddio::swap::swap(|| 0.0f32);
+//! *send.pending() = 1.0;
+//! send.flush();
+//! assert!(recv.refresh());
+//! assert_eq!(*recv.received(), 1.0);
+//! ```
+
 use core::{
     cell::{Cell, UnsafeCell},
     sync::atomic::{AtomicUsize, Ordering},
@@ -5,9 +25,7 @@ use core::{
 
 use alloc::sync::Arc;
 
-/// SPSC queue that only retains the last element sent
-///
-/// Useful for custom controllable signals.
+/// Construct a channel, initializing each of its three slots with `init`
 pub fn swap<T: Send>(mut init: impl FnMut() -> T) -> (Sender<T>, Receiver<T>) {
     let shared = Arc::new(Shared {
         slots: [
@@ -26,6 +44,7 @@ pub fn swap<T: Send>(mut init: impl FnMut() -> T) -> (Sender<T>, Receiver<T>) {
     )
 }
 
+/// Publishes values to a [`Receiver`]
 pub struct Sender<T> {
     index: usize,
     shared: Arc<Shared<T>>,
@@ -33,6 +52,8 @@ pub struct Sender<T> {
 
 impl<T> Sender<T> {
     /// Access the value that will be sent next
+    ///
+    /// Contains a stale value previously sent or initialized, which should usually be overwritten.
     pub fn pending(&mut self) -> &mut T {
         unsafe { &mut *self.shared.slots[self.index].get() }
     }
@@ -47,22 +68,28 @@ impl<T> Sender<T> {
     }
 }
 
+/// Obtains the most recent value published by a [`Sender`]
 pub struct Receiver<T> {
     index: usize,
     shared: Arc<Shared<T>>,
 }
 
 impl<T> Receiver<T> {
-    /// Update the value exposed by `recv`. Returns whether new data was obtained. Consumer only.
+    /// Whether a value has been sent that hasn't yet been obtained by `refresh`
+    pub fn is_fresh(&self) -> bool {
+        self.shared.index.load(Ordering::Relaxed) & FRESH_BIT != 0
+    }
+
+    /// Update the value exposed by `received`. Returns whether new data was obtained.
     pub fn refresh(&mut self) -> bool {
-        if self.shared.index.load(Ordering::Relaxed) & FRESH_BIT == 0 {
+        if !self.is_fresh() {
             return false;
         }
         self.index = self.shared.index.swap(self.index, Ordering::AcqRel) & INDEX_MASK;
         true
     }
 
-    /// Access the most recent data as of the last `refresh` call. Consumer only.
+    /// Access the most recent data as of the last `refresh` call
     pub fn received(&mut self) -> &mut T {
         unsafe { &mut *self.shared.slots[self.index].get() }
     }
@@ -1,15 +1,41 @@
-use crate::{math::Float, Frame, Seek, Signal};
+use alloc::sync::Arc;
+use core::sync::atomic::{AtomicU32, Ordering};
+
+use crate::{math::Float, Frame, Reset, Seek, Signal};
 
 /// Smoothly maps a signal of any range into (-1, 1)
 ///
 /// For each input sample `x`, outputs `x.tanh()`. Similar to [`Reinhard`](crate::Reinhard), but
 /// distorts quiet sounds less, and loud sounds more.
-pub struct Tanh<T>(T);
+///
+/// Constructed with [`with_drive`](Self::with_drive), acts as an adjustable saturator instead.
+pub struct Tanh<T> {
+    /// Drive, or `None` for the plain operator
+    drive: Option<Arc<AtomicU32>>,
+    inner: T,
+}
 
 impl<T> Tanh<T> {
     /// Apply the hypberbolic tangent operator to `signal`
     pub fn new(signal: T) -> Self {
-        Self(signal)
+        Self {
+            drive: None,
+            inner: signal,
+        }
+    }
+
+    /// Saturate `signal` by `drive`
+    ///
+    /// For each input sample `x`, outputs `(drive * x).tanh() / drive.tanh()`, so that full-scale
+    /// input remains full-scale. Drive near 0 is nearly transparent, while large values approach
+    /// hard clipping.
+    pub fn with_drive(signal: T, drive: f32) -> (TanhControl, Self) {
+        let shared = Arc::new(AtomicU32::new(drive.to_bits()));
+        let signal = Self {
+            drive: Some(shared.clone()),
+            inner: signal,
+        };
+        (TanhControl(shared), signal)
     }
 }
 
@@ -20,16 +46,37 @@ where
     type Frame = T::Frame;
 
     fn sample(&mut self, interval: f32, out: &mut [T::Frame]) {
-        self.0.sample(interval, out);
+        self.inner.sample(interval, out);
+        let drive = match self.drive {
+            None => {
+                for x in out {
+                    for channel in x.channels_mut() {
+                        *channel = channel.tanh();
+                    }
+                }
+                return;
+            }
+            Some(ref drive) => f32::from_bits(drive.load(Ordering::Relaxed)),
+        };
+        // Avoid dividing by zero when drive is very small, where the operator is linear anyway
+        let (drive, normalize) = if drive.abs() < 1e-3 {
+            (1e-3, 1.0 / 1e-3f32.tanh())
+        } else {
+            (drive, 1.0 / drive.tanh())
+        };
         for x in out {
             for channel in x.channels_mut() {
-                *channel = channel.tanh();
+                *channel = (drive * *channel).tanh() * normalize;
             }
         }
     }
 
     fn is_finished(&self) -> bool {
-        self.0.is_finished()
+        self.inner.is_finished()
+    }
+
+    fn duration_remaining(&self) -> Option<f32> {
+        self.inner.duration_remaining()
     }
 }
 
@@ -39,6 +86,58 @@ where
     T::Frame: Frame,
 {
     fn seek(&mut self, seconds: f32) {
-        self.0.seek(seconds);
+        self.inner.seek(seconds);
+    }
+}
+
+impl<T> Reset for Tanh<T>
+where
+    T: Signal + Reset,
+    T::Frame: Frame,
+{
+    fn reset(&mut self) {
+        self.inner.reset();
+    }
+}
+
+/// Thread-safe control for a [`Tanh`] saturator
+pub struct TanhControl(Arc<AtomicU32>);
+
+impl TanhControl {
+    /// Get the current drive
+    pub fn drive(&self) -> f32 {
+        f32::from_bits(self.0.load(Ordering::Relaxed))
+    }
+
+    /// Set the drive
+    pub fn set_drive(&mut self, drive: f32) {
+        self.0.store(drive.to_bits(), Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Constant;
+
+    #[test]
+    fn drive() {
+        let mut plain = Tanh::new(Constant(0.5));
+        let mut out = [0.0];
+        plain.sample(1.0, &mut out);
+        assert_eq!(out[0], 0.5f32.tanh());
+
+        let (mut c, mut driven) = Tanh::with_drive(Constant(0.5), 1.0);
+        driven.sample(1.0, &mut out);
+        assert!((out[0] - 0.5f32.tanh() / 1.0f32.tanh()).abs() < 1e-6);
+
+        c.set_drive(0.0);
+        driven.sample(1.0, &mut out);
+        assert!((out[0] - 0.5).abs() < 1e-3, "small drive is nearly linear");
+
+        driven.inner.0 = 1.0;
+        c.set_drive(10.0);
+        driven.sample(1.0, &mut out);
+        assert!((out[0] - 1.0).abs() < 1e-6, "full scale is preserved");
     }
 }
@@ -0,0 +1,114 @@
+use alloc::sync::Arc;
+use core::sync::atomic::{AtomicU32, Ordering};
+
+/// A musical tempo, shared between the code that sets it and any number of tempo-synced signals
+///
+/// Clones refer to the same tempo. Pass a clone to e.g. [`Lfo::with_tempo`](crate::Lfo::with_tempo)
+/// or [`Flanger::with_tempo`](crate::Flanger::with_tempo) to time modulation in musical note
+/// values, and change it at any time with [`set_bpm`](Self::set_bpm). Synced signals pick up the
+/// change at the start of their next block, and modulation continues from its current phase at the
+/// new rate, so retiming is free of discontinuities. Once a signal is synced, rates later set
+/// through its control are ignored.
+///
+/// Delays can be timed in note values too, with [`DelayLine::tap_note`](crate::DelayLine::tap_note).
+#[derive(Clone)]
+pub struct Tempo(Arc<AtomicU32>);
+
+impl Tempo {
+    /// Construct a tempo of `bpm` beats per minute
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bpm` isn't positive and finite.
+    pub fn new(bpm: f32) -> Self {
+        check(bpm);
+        Self(Arc::new(AtomicU32::new(bpm.to_bits())))
+    }
+
+    /// Get the number of beats per minute
+    pub fn bpm(&self) -> f32 {
+        f32::from_bits(self.0.load(Ordering::Relaxed))
+    }
+
+    /// Set the number of beats per minute
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bpm` isn't positive and finite.
+    pub fn set_bpm(&mut self, bpm: f32) {
+        check(bpm);
+        self.0.store(bpm.to_bits(), Ordering::Relaxed);
+    }
+}
+
+fn check(bpm: f32) {
+    assert!(
+        bpm > 0.0 && bpm.is_finite(),
+        "tempo must be positive and finite"
+    );
+}
+
+/// A musical duration, measured in beats, where a beat is a quarter note
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct NoteValue(pub f32);
+
+impl NoteValue {
+    /// Four beats
+    pub const WHOLE: Self = Self(4.0);
+    /// Two beats
+    pub const HALF: Self = Self(2.0);
+    /// One beat
+    pub const QUARTER: Self = Self(1.0);
+    /// Half a beat
+    pub const EIGHTH: Self = Self(0.5);
+    /// A quarter of a beat
+    pub const SIXTEENTH: Self = Self(0.25);
+
+    /// One and a half times this duration
+    pub fn dotted(self) -> Self {
+        Self(self.0 * 1.5)
+    }
+
+    /// Two thirds of this duration, so that three fit in the time of two
+    pub fn triplet(self) -> Self {
+        Self(self.0 * 2.0 / 3.0)
+    }
+
+    /// Length in seconds at `bpm` beats per minute
+    pub fn seconds(self, bpm: f32) -> f32 {
+        self.0 * 60.0 / bpm
+    }
+}
+
+/// A rate expressed as one cycle per note value at a shared tempo
+pub(crate) struct TempoSync {
+    pub(crate) tempo: Tempo,
+    pub(crate) note: NoteValue,
+}
+
+impl TempoSync {
+    /// Cycles per second at the current tempo
+    pub(crate) fn hz(&self) -> f32 {
+        1.0 / self.note.seconds(self.tempo.bpm())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn note_values() {
+        assert_eq!(NoteValue::QUARTER.seconds(120.0), 0.5);
+        assert_eq!(NoteValue::EIGHTH.dotted().seconds(120.0), 0.375);
+        assert_eq!(NoteValue::HALF.triplet().seconds(60.0), 4.0 / 3.0);
+        let mut tempo = Tempo::new(60.0);
+        let sync = TempoSync {
+            tempo: tempo.clone(),
+            note: NoteValue::EIGHTH,
+        };
+        assert_eq!(sync.hz(), 2.0);
+        tempo.set_bpm(120.0);
+        assert_eq!(sync.hz(), 4.0);
+    }
+}
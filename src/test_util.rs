@@ -0,0 +1,32 @@
+//! Helpers shared between the tests of several modules
+
+use core::f32::consts::TAU;
+
+use crate::{math::Float, Frame, Sample, Signal};
+
+/// Peak amplitude of a mono `signal` sampled at `rate`, once any filters have settled
+pub fn peak(signal: &mut impl Signal<Frame = Sample>, rate: f32) -> f32 {
+    let mut buf = [0.0; 4096];
+    // Discard the first block, which includes the transient
+    signal.sample(1.0 / rate, &mut buf);
+    signal.sample(1.0 / rate, &mut buf);
+    buf.iter().fold(0.0f32, |acc, x| acc.max(x.abs()))
+}
+
+/// Amplitude of the `hz` component of `xs`, sampled at `rate`
+pub fn magnitude(xs: &[Sample], hz: f32, rate: f32) -> f32 {
+    let (mut re, mut im) = (0.0, 0.0);
+    for (i, &x) in xs.iter().enumerate() {
+        let phase = TAU * hz * i as f32 / rate;
+        re += x * phase.cos();
+        im += x * phase.sin();
+    }
+    (re * re + im * im).sqrt() * 2.0 / xs.len() as f32
+}
+
+/// Number of times the first channel of `xs` changes sign
+pub fn zero_crossings<T: Frame>(xs: &[T]) -> usize {
+    xs.windows(2)
+        .filter(|w| (w[0].channels()[0] < 0.0) != (w[1].channels()[0] < 0.0))
+        .count()
+}
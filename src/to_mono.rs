@@ -0,0 +1,71 @@
+use crate::{Frame, Reset, Seek, Signal};
+
+/// Collapses a signal to mono while preserving its frame type
+///
+/// Every channel of each output frame holds the average of the input frame's channels. Unlike
+/// [`Downmix`](crate::Downmix), the result can be mixed with other signals of the original frame
+/// type, e.g. to force a source to play in mono on a stereo device.
+pub struct ToMono<T: ?Sized>(T);
+
+impl<T> ToMono<T> {
+    /// Collapse `signal` to mono
+    pub fn new(signal: T) -> Self {
+        Self(signal)
+    }
+}
+
+impl<T: Signal + ?Sized> Signal for ToMono<T>
+where
+    T::Frame: Frame,
+{
+    type Frame = T::Frame;
+
+    fn sample(&mut self, interval: f32, out: &mut [T::Frame]) {
+        self.0.sample(interval, out);
+        for x in out {
+            let channels = x.channels_mut();
+            let mean = channels.iter().sum::<f32>() / channels.len() as f32;
+            channels.fill(mean);
+        }
+    }
+
+    fn is_finished(&self) -> bool {
+        self.0.is_finished()
+    }
+
+    fn duration_remaining(&self) -> Option<f32> {
+        self.0.duration_remaining()
+    }
+}
+
+impl<T: Seek + ?Sized> Seek for ToMono<T>
+where
+    T::Frame: Frame,
+{
+    fn seek(&mut self, seconds: f32) {
+        self.0.seek(seconds);
+    }
+}
+
+impl<T: Reset + ?Sized> Reset for ToMono<T>
+where
+    T::Frame: Frame,
+{
+    fn reset(&mut self) {
+        self.0.reset();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Constant;
+
+    #[test]
+    fn average() {
+        let mut s = ToMono::new(Constant([1.0, -3.0, 5.0]));
+        let mut out = [[0.0; 3]; 2];
+        s.sample(1.0, &mut out);
+        assert_eq!(out, [[1.0; 3]; 2]);
+    }
+}
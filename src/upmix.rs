@@ -0,0 +1,109 @@
+use crate::{Reset, Sample, Seek, Signal};
+
+/// Spreads a stereo signal across `N` channels
+///
+/// Each output channel is a weighted sum of the left and right input channels. This is a simple
+/// matrix upmix for feeding surround output devices from stereo sources, not true spatialization;
+/// see [`SpatialScene`](crate::SpatialScene) for that. Interleaved output buffers can be adapted
+/// with [`frame_multi`](crate::frame_multi).
+pub struct Upmix<const N: usize, T: ?Sized> {
+    matrix: [[f32; 2]; N],
+    inner: T,
+}
+
+impl<T> Upmix<4, T> {
+    /// Upmix stereo `signal` to quadraphonic front left, front right, rear left, rear right
+    ///
+    /// The front channels pass through unchanged, and both rear channels receive half the
+    /// difference between left and right, in the style of Dolby Pro Logic's surround channel.
+    pub fn new(signal: T) -> Self {
+        Self::with_matrix(signal, [[1.0, 0.0], [0.0, 1.0], [0.5, -0.5], [0.5, -0.5]])
+    }
+}
+
+impl<T> Upmix<6, T> {
+    /// Upmix stereo `signal` to 5.1 front left, front right, center, LFE, surround left, surround
+    /// right
+    ///
+    /// The front channels pass through unchanged, the center receives the average of left and
+    /// right, the LFE channel is silent, and both surround channels receive half the difference
+    /// between left and right, in the style of Dolby Pro Logic.
+    pub fn new(signal: T) -> Self {
+        Self::with_matrix(
+            signal,
+            [
+                [1.0, 0.0],
+                [0.0, 1.0],
+                [0.5, 0.5],
+                [0.0, 0.0],
+                [0.5, -0.5],
+                [0.5, -0.5],
+            ],
+        )
+    }
+}
+
+impl<const N: usize, T> Upmix<N, T> {
+    /// Upmix stereo `signal` with a custom matrix
+    ///
+    /// Output channel `i` is `matrix[i][0] * left + matrix[i][1] * right`.
+    pub fn with_matrix(signal: T, matrix: [[f32; 2]; N]) -> Self {
+        Self {
+            matrix,
+            inner: signal,
+        }
+    }
+}
+
+impl<const N: usize, T: Signal<Frame = [Sample; 2]> + ?Sized> Signal for Upmix<N, T> {
+    type Frame = [Sample; N];
+
+    fn sample(&mut self, interval: f32, out: &mut [[Sample; N]]) {
+        const CHUNK_SIZE: usize = 256;
+
+        let mut buf = [[0.0; 2]; CHUNK_SIZE];
+        for chunk in out.chunks_mut(CHUNK_SIZE) {
+            let buf = &mut buf[..chunk.len()];
+            self.inner.sample(interval, buf);
+            for ([l, r], o) in buf.iter().zip(chunk) {
+                for (x, [wl, wr]) in o.iter_mut().zip(&self.matrix) {
+                    *x = wl * l + wr * r;
+                }
+            }
+        }
+    }
+
+    fn is_finished(&self) -> bool {
+        self.inner.is_finished()
+    }
+
+    fn duration_remaining(&self) -> Option<f32> {
+        self.inner.duration_remaining()
+    }
+}
+
+impl<const N: usize, T: Seek<Frame = [Sample; 2]> + ?Sized> Seek for Upmix<N, T> {
+    fn seek(&mut self, seconds: f32) {
+        self.inner.seek(seconds);
+    }
+}
+
+impl<const N: usize, T: Reset<Frame = [Sample; 2]> + ?Sized> Reset for Upmix<N, T> {
+    fn reset(&mut self) {
+        self.inner.reset();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Constant;
+
+    #[test]
+    fn surround() {
+        let mut signal = Upmix::<6, _>::new(Constant::new([1.0, 0.5]));
+        let mut out = [[0.0; 6]; 300];
+        signal.sample(1.0, &mut out);
+        assert_eq!(out, [[1.0, 0.5, 0.75, 0.0, 0.25, 0.25]; 300]);
+    }
+}
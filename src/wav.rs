@@ -0,0 +1,144 @@
+use alloc::{sync::Arc, vec::Vec};
+use core::fmt;
+use std::{error, io};
+
+use crate::{Frames, Sample};
+
+impl Frames<Sample> {
+    /// Decode a WAV file from `reader`
+    ///
+    /// Integer samples are scaled into [-1, 1]. Only mono and stereo files are supported.
+    pub fn from_wav_reader<R: io::Read>(reader: R) -> Result<WavFrames, WavError> {
+        let mut reader = hound::WavReader::new(reader)?;
+        let spec = reader.spec();
+        let samples = match spec.sample_format {
+            hound::SampleFormat::Int => {
+                let scale = 1.0 / (1u32 << (spec.bits_per_sample - 1)) as f32;
+                reader
+                    .samples::<i32>()
+                    .map(|x| x.map(|x| x as f32 * scale))
+                    .collect::<Result<Vec<_>, _>>()?
+            }
+            hound::SampleFormat::Float => reader.samples::<f32>().collect::<Result<Vec<_>, _>>()?,
+        };
+        Ok(match spec.channels {
+            1 => WavFrames::Mono(Frames::from_slice(spec.sample_rate, &samples)),
+            2 => WavFrames::Stereo(Frames::from_iter(
+                spec.sample_rate,
+                samples.chunks_exact(2).map(|x| [x[0], x[1]]),
+            )),
+            n => return Err(WavError::UnsupportedChannels(n)),
+        })
+    }
+
+    /// Decode a WAV file from memory
+    ///
+    /// See [`from_wav_reader`](Self::from_wav_reader).
+    pub fn from_wav_bytes(bytes: &[u8]) -> Result<WavFrames, WavError> {
+        Self::from_wav_reader(bytes)
+    }
+}
+
+/// Audio decoded by [`Frames::from_wav_reader`]
+#[derive(Debug, Clone)]
+pub enum WavFrames {
+    /// Single-channel audio
+    Mono(Arc<Frames<Sample>>),
+    /// Two-channel audio
+    Stereo(Arc<Frames<[Sample; 2]>>),
+}
+
+impl WavFrames {
+    /// Number of samples per second
+    pub fn rate(&self) -> u32 {
+        match *self {
+            WavFrames::Mono(ref x) => x.rate(),
+            WavFrames::Stereo(ref x) => x.rate(),
+        }
+    }
+
+    /// The runtime in seconds
+    pub fn runtime(&self) -> f64 {
+        match *self {
+            WavFrames::Mono(ref x) => x.runtime(),
+            WavFrames::Stereo(ref x) => x.runtime(),
+        }
+    }
+}
+
+/// Errors that may arise when decoding a WAV file
+#[derive(Debug)]
+pub enum WavError {
+    /// The file could not be read or was malformed
+    Decode(hound::Error),
+    /// The file has a number of channels other than 1 or 2
+    UnsupportedChannels(u16),
+}
+
+impl fmt::Display for WavError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            WavError::Decode(ref e) => write!(f, "failed to decode WAV: {}", e),
+            WavError::UnsupportedChannels(n) => write!(f, "unsupported channel count: {}", n),
+        }
+    }
+}
+
+impl error::Error for WavError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            WavError::Decode(ref e) => Some(e),
+            WavError::UnsupportedChannels(_) => None,
+        }
+    }
+}
+
+impl From<hound::Error> for WavError {
+    fn from(x: hound::Error) -> Self {
+        WavError::Decode(x)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encode(spec: hound::WavSpec, samples: &[i16]) -> Vec<u8> {
+        let mut buf = io::Cursor::new(Vec::new());
+        let mut writer = hound::WavWriter::new(&mut buf, spec).unwrap();
+        for &x in samples {
+            writer.write_sample(x).unwrap();
+        }
+        writer.finalize().unwrap();
+        buf.into_inner()
+    }
+
+    fn spec(channels: u16) -> hound::WavSpec {
+        hound::WavSpec {
+            channels,
+            sample_rate: 100,
+            bits_per_sample: 16,
+            sample_format: hound::SampleFormat::Int,
+        }
+    }
+
+    #[test]
+    fn stereo() {
+        let data = encode(spec(2), &[i16::MIN, 0, 0, 16384]);
+        let frames = match Frames::from_wav_bytes(&data).unwrap() {
+            WavFrames::Stereo(x) => x,
+            WavFrames::Mono(_) => panic!("expected stereo"),
+        };
+        assert_eq!(frames.rate(), 100);
+        assert_eq!(&frames[..], &[[-1.0, 0.0], [0.0, 0.5]]);
+    }
+
+    #[test]
+    fn unsupported_channels() {
+        let data = encode(spec(3), &[0, 0, 0]);
+        assert!(matches!(
+            Frames::from_wav_bytes(&data),
+            Err(WavError::UnsupportedChannels(3))
+        ));
+    }
+}
@@ -0,0 +1,186 @@
+use alloc::{boxed::Box, sync::Arc, vec::Vec};
+use core::{
+    f32::consts::TAU,
+    sync::atomic::{AtomicU32, Ordering},
+};
+
+use crate::{math::Float, Frames, Sample, Seek, Signal};
+
+/// An oscillator that repeatedly plays a single cycle of an arbitrary waveform
+///
+/// Richer than [`Sine`](crate::Sine), and much cheaper than summing many sine waves. Playing a
+/// table with strong high harmonics at a high pitch produces aliasing, because harmonics above the
+/// Nyquist frequency fold back into the audible range. To avoid this, construct the oscillator with
+/// [`from_harmonics`](Self::from_harmonics), which prepares progressively band-limited copies of
+/// the waveform and plays whichever is the richest that fits below the Nyquist frequency.
+pub struct Wavetable {
+    /// Tables in decreasing order of bandwidth
+    levels: Box<[Level]>,
+    frequency: Arc<AtomicU32>,
+    /// Position within the current cycle, in [0, 1)
+    phase: f32,
+}
+
+impl Wavetable {
+    /// Play one cycle of a waveform stored in `table`, `frequency_hz` times per second
+    ///
+    /// The table's sample rate is ignored. No band-limiting is performed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `table` is empty.
+    pub fn new(table: Arc<Frames<Sample>>, frequency_hz: f32) -> (WavetableControl, Self) {
+        assert!(
+            !table.is_empty(),
+            "wavetable must contain at least one sample"
+        );
+        Self::from_levels(
+            Box::new([Level {
+                table,
+                harmonics: 0,
+            }]),
+            frequency_hz,
+        )
+    }
+
+    /// Play the waveform made up of harmonics with the given amplitudes, `frequency_hz` times per
+    /// second
+    ///
+    /// `amplitudes[i]` is the amplitude of a sine wave at `i + 1` times the fundamental frequency.
+    /// For example, a sawtooth wave has amplitudes `2 / (π * n)` for each harmonic `n`, and a
+    /// square wave is the same but with every even harmonic omitted.
+    ///
+    /// Builds a "mipmap" of tables: the first contains every harmonic, and each further table
+    /// discards the upper half of the harmonics of the previous one. During playback, the table
+    /// with the most harmonics that all lie below the Nyquist frequency is used. Up to 1023
+    /// harmonics are supported.
+    pub fn from_harmonics(amplitudes: &[f32], frequency_hz: f32) -> (WavetableControl, Self) {
+        let mut levels = Vec::new();
+        let mut harmonics = amplitudes.len().min(TABLE_LEN / 2 - 1);
+        while harmonics > 0 {
+            let table = Frames::from_iter(
+                TABLE_LEN as u32,
+                (0..TABLE_LEN).map(|i| {
+                    let t = i as f32 / TABLE_LEN as f32;
+                    amplitudes[..harmonics]
+                        .iter()
+                        .enumerate()
+                        .map(|(k, &a)| a * (TAU * (k + 1) as f32 * t).sin())
+                        .sum::<f32>()
+                }),
+            );
+            levels.push(Level { table, harmonics });
+            harmonics /= 2;
+        }
+        if levels.is_empty() {
+            levels.push(Level {
+                table: Frames::from_slice(1, &[0.0]),
+                harmonics: 0,
+            });
+        }
+        Self::from_levels(levels.into(), frequency_hz)
+    }
+
+    fn from_levels(levels: Box<[Level]>, frequency_hz: f32) -> (WavetableControl, Self) {
+        let frequency = Arc::new(AtomicU32::new(frequency_hz.to_bits()));
+        let signal = Self {
+            levels,
+            frequency: frequency.clone(),
+            phase: 0.0,
+        };
+        (WavetableControl(frequency), signal)
+    }
+
+    /// Select the richest table without harmonics above the Nyquist frequency
+    fn level(&self, frequency: f32, interval: f32) -> &Frames<Sample> {
+        let limit = 0.5 / (frequency.abs() * interval);
+        let level = self
+            .levels
+            .iter()
+            .find(|level| level.harmonics as f32 <= limit)
+            .unwrap_or(&self.levels[self.levels.len() - 1]);
+        &level.table
+    }
+}
+
+impl Signal for Wavetable {
+    type Frame = Sample;
+
+    fn sample(&mut self, interval: f32, out: &mut [Sample]) {
+        let frequency = f32::from_bits(self.frequency.load(Ordering::Relaxed));
+        let table = self.level(frequency, interval);
+        let len = table.len();
+        let mut phase = self.phase;
+        for x in out {
+            let s = phase * len as f32;
+            let i = (s as usize).min(len - 1);
+            let fract = s - i as f32;
+            let (a, b) = (table[i], table[(i + 1) % len]);
+            *x = a + (b - a) * fract;
+            phase = (phase + frequency * interval).rem_euclid(1.0);
+        }
+        self.phase = phase;
+    }
+}
+
+impl Seek for Wavetable {
+    fn seek(&mut self, seconds: f32) {
+        let frequency = f32::from_bits(self.frequency.load(Ordering::Relaxed));
+        self.phase = (self.phase + seconds * frequency).rem_euclid(1.0);
+    }
+}
+
+/// Thread-safe control for a [`Wavetable`]
+pub struct WavetableControl(Arc<AtomicU32>);
+
+impl WavetableControl {
+    /// Get the number of cycles played per second
+    pub fn frequency(&self) -> f32 {
+        f32::from_bits(self.0.load(Ordering::Relaxed))
+    }
+
+    /// Set the number of cycles played per second
+    pub fn set_frequency(&mut self, hz: f32) {
+        self.0.store(hz.to_bits(), Ordering::Relaxed);
+    }
+}
+
+struct Level {
+    table: Arc<Frames<Sample>>,
+    /// Highest harmonic present, or 0 if unknown
+    harmonics: usize,
+}
+
+/// Number of samples in each table built by [`Wavetable::from_harmonics`]
+const TABLE_LEN: usize = 2048;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn table() {
+        let (mut c, mut s) = Wavetable::new(Frames::from_slice(1, &[0.0, 1.0, 0.0, -1.0]), 1.0);
+        let mut out = [0.0; 6];
+        s.sample(0.125, &mut out);
+        assert_eq!(out, [0.0, 0.5, 1.0, 0.5, 0.0, -0.5]);
+        c.set_frequency(2.0);
+        s.sample(0.125, &mut out);
+        assert_eq!(out, [-1.0, 0.0, 1.0, 0.0, -1.0, 0.0]);
+    }
+
+    #[test]
+    fn band_limited() {
+        let saw = (1..=64).map(|n| 1.0 / n as f32).collect::<Vec<_>>();
+        let (_, s) = Wavetable::from_harmonics(&saw, 100.0);
+        let levels = s.levels.iter().map(|l| l.harmonics).collect::<Vec<_>>();
+        assert_eq!(levels, [64, 32, 16, 8, 4, 2, 1]);
+        // 64 * 100Hz lies below the Nyquist frequency of 22.05kHz
+        assert_eq!(s.level(100.0, 1.0 / 44100.0).len(), TABLE_LEN);
+        // At 1kHz, only 22 harmonics fit
+        assert!(core::ptr::eq(
+            s.level(1000.0, 1.0 / 44100.0),
+            &*s.levels[2].table
+        ));
+    }
+}